@@ -0,0 +1,38 @@
+//revised simplex keeping B⁻¹ up to date with eta matrices against recomputing it from the basis columns every pivot
+//run with: cargo bench --features test-support --bench basis_updates
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use lab4::test_support::random_feasible_lp;
+use lab4::{revised_simplex, SolveResult, SolverError, SolverOptions};
+
+const SIZES: [(usize, usize); 3] = [(20, 40), (50, 100), (100, 200)]; //several hundred pivots a problem at 100x200
+const SEEDS: u64 = 3;
+const UPDATES: [(&str, Option<usize>); 3] = [("eta", None), ("eta_refactor_100", Some(100)), ("recompute", Some(1))];
+
+type Arrays = (ndarray::Array1<f64>, ndarray::Array2<f64>, ndarray::Array1<f64>, Vec<lab4::Relation>);
+
+fn objective(result: Result<SolveResult, SolverError>) -> f64 {
+    match result.expect("generated problems are well formed") {
+        SolveResult::Optimal(solution) => solution.objective,
+        other => panic!("generated problems are feasible and bounded, got {:?}", other),
+    }
+}
+
+fn basis_updates(c: &mut Criterion) {
+    let mut group = c.benchmark_group("basis_updates");
+    group.sample_size(10);
+    for (m, n) in SIZES {
+        let problems: Vec<Arrays> = (0..SEEDS).map(|seed| random_feasible_lp(seed, m, n).0.to_arrays()).collect();
+        let size = format!("{}x{}", m, n);
+        for (name, refactor_frequency) in UPDATES {
+            let options = SolverOptions { refactor_frequency, ..SolverOptions::default() };
+            group.bench_with_input(BenchmarkId::new(name, &size), &problems, |bench, problems| {
+                bench.iter(|| problems.iter().map(|(c, a, b, r)| objective(revised_simplex(c.clone(), a, b, r, &options))).sum::<f64>())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, basis_updates);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+//dense two-phase tableau against the revised simplex on random feasible LPs of growing size
+//run with: cargo bench --features test-support
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use lab4::test_support::random_feasible_lp;
+use lab4::{revised_simplex, simplex_solver_two_phase, SolveResult, SolverError, SolverOptions};
+
+//square-ish problems, then two wide ones with many vars and few rows where the revised method only keeps an m×m B⁻¹
+const SIZES: [(usize, usize); 6] = [(5, 10), (20, 40), (50, 100), (100, 200), (10, 1000), (20, 3000)];
+const SEEDS: u64 = 5;
+
+type Arrays = (ndarray::Array1<f64>, ndarray::Array2<f64>, ndarray::Array1<f64>, Vec<lab4::Relation>);
+
+fn iterations(result: Result<SolveResult, SolverError>) -> usize {
+    match result.expect("generated problems are well formed") {
+        SolveResult::Optimal(solution) => solution.iterations,
+        other => panic!("generated problems are feasible and bounded, got {:?}", other),
+    }
+}
+
+//criterion only reports times, the iterations per solve get printed once per size next to them, with what each
+//method stores: the whole (m+1)×(n+m+1) tableau at least against B⁻¹
+fn dense_vs_revised(c: &mut Criterion) {
+    let options = SolverOptions::default();
+    let mut group = c.benchmark_group("dense_vs_revised");
+    group.sample_size(10); //a 100x200 solve takes a good part of a second
+    for (m, n) in SIZES {
+        let problems: Vec<Arrays> = (0..SEEDS).map(|seed| random_feasible_lp(seed, m, n).0.to_arrays()).collect();
+        let size = format!("{}x{}", m, n);
+        let dense: usize = problems.iter().map(|(c, a, b, r)| iterations(simplex_solver_two_phase(c.clone(), a, b, r, &options))).sum();
+        let revised: usize = problems.iter().map(|(c, a, b, r)| iterations(revised_simplex(c.clone(), a, b, r, &options))).sum();
+        println!(
+            "{}: {:.1} dense iterations, {:.1} revised, {} tableau entries against {} in B⁻¹",
+            size,
+            dense as f64 / SEEDS as f64,
+            revised as f64 / SEEDS as f64,
+            (m + 1) * (n + m + 1),
+            m * m
+        );
+        group.bench_with_input(BenchmarkId::new("dense", &size), &problems, |bench, problems| {
+            bench.iter(|| problems.iter().map(|(c, a, b, r)| iterations(simplex_solver_two_phase(c.clone(), a, b, r, &options))).sum::<usize>())
+        });
+        group.bench_with_input(BenchmarkId::new("revised", &size), &problems, |bench, problems| {
+            bench.iter(|| problems.iter().map(|(c, a, b, r)| iterations(revised_simplex(c.clone(), a, b, r, &options))).sum::<usize>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dense_vs_revised);
+criterion_main!(benches);
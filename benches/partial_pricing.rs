@@ -0,0 +1,41 @@
+//pricing a rotating block of columns against pricing all of them, on wide random feasible LPs
+//run with: cargo bench --features test-support --bench partial_pricing
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use lab4::test_support::random_feasible_lp;
+use lab4::{LpProblem, SolveResult, SolverOptions};
+
+const SIZES: [(usize, usize); 2] = [(10, 1000), (20, 3000)];
+const SEEDS: u64 = 3;
+const BLOCKS: [Option<usize>; 4] = [None, Some(500), Some(100), Some(25)];
+
+fn iterations(problem: &LpProblem, options: &SolverOptions) -> usize {
+    match problem.solve_with(options).expect("generated problems are well formed") {
+        SolveResult::Optimal(solution) => solution.iterations,
+        other => panic!("generated problems are feasible and bounded, got {:?}", other),
+    }
+}
+
+//smaller blocks take more iterations that each price fewer columns, the iterations get printed so the time per
+//iteration can be read off next to criterion's totals
+fn partial_pricing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("partial_pricing");
+    group.sample_size(10);
+    for (m, n) in SIZES {
+        let problems: Vec<LpProblem> = (0..SEEDS).map(|seed| random_feasible_lp(seed, m, n).0).collect();
+        let size = format!("{}x{}", m, n);
+        for partial_pricing in BLOCKS {
+            let name = partial_pricing.map_or("full".to_string(), |block| format!("block_{}", block));
+            let options = SolverOptions { partial_pricing, ..SolverOptions::default() };
+            let total: usize = problems.iter().map(|problem| iterations(problem, &options)).sum();
+            println!("{} {}: {:.1} iterations", size, name, total as f64 / SEEDS as f64);
+            group.bench_with_input(BenchmarkId::new(name, &size), &problems, |bench, problems| {
+                bench.iter(|| problems.iter().map(|problem| iterations(problem, &options)).sum::<usize>())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, partial_pricing);
+criterion_main!(benches);
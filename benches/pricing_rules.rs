@@ -0,0 +1,39 @@
+//Dantzig against steepest edge and Devex pricing, iterations and time of the tableau solver on random feasible LPs
+//run with: cargo bench --features test-support --bench pricing_rules
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use lab4::test_support::random_feasible_lp;
+use lab4::{LpProblem, PivotRule, SolveResult, SolverOptions};
+
+const SIZES: [(usize, usize); 3] = [(20, 40), (30, 60), (10, 100)];
+const SEEDS: u64 = 10;
+const RULES: [(&str, PivotRule); 3] = [("dantzig", PivotRule::Dantzig), ("steepest_edge", PivotRule::SteepestEdge), ("devex", PivotRule::Devex)];
+
+fn iterations(problem: &LpProblem, options: &SolverOptions) -> usize {
+    match problem.solve_with(options).expect("generated problems are well formed") {
+        SolveResult::Optimal(solution) => solution.iterations,
+        other => panic!("generated problems are feasible and bounded, got {:?}", other),
+    }
+}
+
+//a cheaper pivot rule can still lose on time when pricing a column costs more, so both get reported
+fn pricing_rules(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pricing_rules");
+    group.sample_size(20);
+    for (m, n) in SIZES {
+        let problems: Vec<LpProblem> = (0..SEEDS).map(|seed| random_feasible_lp(seed, m, n).0).collect();
+        let size = format!("{}x{}", m, n);
+        for (name, pivot_rule) in RULES {
+            let options = SolverOptions { pivot_rule, ..SolverOptions::default() };
+            let total: usize = problems.iter().map(|problem| iterations(problem, &options)).sum();
+            println!("{} {}: {:.1} iterations", size, name, total as f64 / SEEDS as f64);
+            group.bench_with_input(BenchmarkId::new(name, &size), &problems, |bench, problems| {
+                bench.iter(|| problems.iter().map(|problem| iterations(problem, &options)).sum::<usize>())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pricing_rules);
+criterion_main!(benches);
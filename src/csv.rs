@@ -0,0 +1,127 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::problem::LpProblem;
+use crate::simplex::Relation;
+
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    Parse { line: usize, message: String }, //1-based line number
+    Empty,                                  //not even an objective row
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Io(err) => write!(f, "read error: {}", err),
+            CsvError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            CsvError::Empty => write!(f, "no objective row"),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<io::Error> for CsvError {
+    fn from(err: io::Error) -> Self {
+        CsvError::Io(err)
+    }
+}
+
+//comma separated coefficients, one row per line:
+//
+//  max,3,2
+//  1,1,<=,4
+//  1,3,>=,6
+//
+//the first row is the objective, led by max or min (max without one), every other row is the constraint coeffs
+//followed by <=, >= or = and the rhs. blank lines and lines starting with # are skipped, fields are trimmed and
+//can't be quoted
+pub fn parse_csv(reader: impl BufRead) -> Result<LpProblem, CsvError> {
+    let mut problem: Option<LpProblem> = None;
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_no = idx + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = trimmed.split(',').map(str::trim).collect();
+        let error = |message: String| CsvError::Parse { line: line_no, message };
+        let number = |field: &str| field.parse::<f64>().map_err(|_| error(format!("'{}' is not a number", field)));
+
+        let Some(current) = problem.take() else {
+            let (sense, coeffs) = match fields[0] {
+                "max" => (Some(true), &fields[1..]),
+                "min" => (Some(false), &fields[1..]),
+                _ => (None, &fields[..]),
+            };
+            let objective = coeffs.iter().map(|field| number(field)).collect::<Result<_, _>>()?;
+            let objective = LpProblem::new().objective(objective);
+            problem = Some(if sense == Some(false) { objective.minimize() } else { objective.maximize() });
+            continue;
+        };
+        if fields.len() < 3 {
+            return Err(error("expected coefficients, a relation and a rhs".to_string()));
+        }
+        let relation = match fields[fields.len() - 2] {
+            "<=" => Relation::Le,
+            ">=" => Relation::Ge,
+            "=" => Relation::Eq,
+            other => return Err(error(format!("expected <=, >= or = before the rhs, found '{}'", other))),
+        };
+        let coeffs = fields[..fields.len() - 2].iter().map(|field| number(field)).collect::<Result<_, _>>()?;
+        problem = Some(current.add_constraint(coeffs, relation, number(fields[fields.len() - 1])?));
+    }
+    problem.ok_or(CsvError::Empty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::SolveResult;
+    use crate::test_support::{assert_objective_close, assert_solution_close};
+
+    const FIXTURE: &str = "# the example from parse_csv\nmax, 3, 2\n1, 1, <=, 4\n\n1, 3, >=, 6\n";
+
+    #[test]
+    fn solves_the_fixture() {
+        let problem = parse_csv(FIXTURE.as_bytes()).unwrap();
+        assert_eq!(problem.constraints.len(), 2);
+        let SolveResult::Optimal(solution) = problem.solve().unwrap() else {
+            panic!("the fixture has an optimum");
+        };
+        assert_solution_close(&[3.0, 1.0], &solution.structural().to_owned(), 1e-9);
+        assert_objective_close(11.0, solution.objective, 1e-9);
+
+        let min = parse_csv("min,3,2\n1,1,<=,4\n1,3,>=,6\n".as_bytes()).unwrap();
+        let SolveResult::Optimal(solution) = min.solve().unwrap() else {
+            panic!("the fixture has an optimum");
+        };
+        assert_objective_close(4.0, solution.objective, 1e-9); //(0, 2)
+    }
+
+    #[test]
+    fn reports_bad_lines() {
+        match parse_csv("max,1\n1,=<,2\n".as_bytes()) {
+            Err(CsvError::Parse { line: 2, .. }) => {}
+            other => panic!("expected a parse error on line 2, got {:?}", other),
+        }
+        match parse_csv("max,1\n1,<=,two\n".as_bytes()) {
+            Err(CsvError::Parse { line: 2, .. }) => {}
+            other => panic!("expected a parse error on line 2, got {:?}", other),
+        }
+        assert!(matches!(parse_csv("# nothing\n".as_bytes()), Err(CsvError::Empty)));
+    }
+
+    #[test]
+    fn fixture_round_trips_through_the_builder_and_lp_text() {
+        let built = LpProblem::new().objective(vec![3.0, 2.0]).add_constraint(vec![1.0, 1.0], Relation::Le, 4.0).add_constraint(vec![1.0, 3.0], Relation::Ge, 6.0).maximize();
+        let problem = parse_csv(FIXTURE.as_bytes()).unwrap();
+        assert_eq!(problem, built);
+        //LP text names the columns, they come back as x1 and x2
+        let named = problem.clone().var_names(vec!["x1".to_string(), "x2".to_string()]);
+        assert_eq!(crate::lp_format::parse_lp(&crate::lp_format::write_lp(&problem)).unwrap(), named);
+    }
+}
@@ -0,0 +1,313 @@
+use ndarray::prelude::*;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::scalar::Scalar;
+use crate::simplex::{
+    basic_values, binding_rows, complement_column, degenerate_columns, epsilon, pivot_operation, zero_reduced_costs, ColumnBounds,
+    PivotRule, Relation, Rewrite, Sense, Solution, SolveResult, SolverError, SolverOptions, Timer, Trace,
+};
+
+//dual simplex on the tableau of a solution that is still dual feasible (no negative reduced cost) but has a negative rhs,
+//which is what adding a cut or lowering a rhs leaves behind. the leaving row is the most negative rhs, the entering
+//column the smallest d_j / -a_rj over the negative entries of that row. Infeasible if the row has none
+pub fn dual_simplex<T: Scalar>(solution: &Solution<T>, options: &SolverOptions) -> Result<SolveResult<T>, SolverError> {
+    if let Some(rewritten) = solution.rewritten() {
+        return Ok(match dual_simplex(&rewritten, options)? {
+            SolveResult::Optimal(next) => SolveResult::Optimal(Box::new(solution.read_back(*next))),
+            other => other,
+        });
+    }
+    let timer = Timer::start();
+    let eps = epsilon::<T>(options);
+    let num_x = solution.x.len();
+    let last_row = solution.tableau.nrows() - 1;
+    let rhs_col = solution.tableau.ncols() - 1;
+    if let Some(col) = (0..num_x).find(|&j| solution.tableau[[last_row, j]] < -eps.clone()) {
+        return Err(SolverError::NotDualFeasible { col });
+    }
+
+    let mut tableau = solution.tableau.clone();
+    let mut basis = solution.basis.clone();
+    let mut bounds = ColumnBounds::of_solution(solution);
+    let before = bounds.unflip(basic_values(&tableau, &basis));
+    let start_value = tableau[[last_row, rhs_col]].clone();
+    let max_iterations = options.max_iterations.unwrap_or(50 * (last_row + rhs_col));
+    let mut trace = Trace { pivot_sizes: solution.pivot_sizes.clone(), ..Trace::new() };
+
+    loop {
+        //a basic variable above its upper bound is complemented, then it shows up as a negative rhs like the rest
+        for row in 0..basis.len() {
+            let col = basis[row];
+            if bounds.upper(col).is_some_and(|upper| tableau[[row, rhs_col]] > upper.clone() + eps.clone()) {
+                complement_column(&mut tableau, &basis, &mut bounds, col);
+            }
+        }
+
+        let infeasible = (0..basis.len()).filter(|&row| tableau[[row, rhs_col]] < -eps.clone());
+        let leaving = match options.pivot_rule {
+            PivotRule::Bland => infeasible.min_by_key(|&row| basis[row]),
+            _ => infeasible.fold(None, |best: Option<usize>, row| match best {
+                Some(best) if tableau[[best, rhs_col]] <= tableau[[row, rhs_col]] => Some(best),
+                _ => Some(row),
+            }),
+        };
+        let Some(pivot_row) = leaving else {
+            break;
+        };
+
+        //dual ratio test, first (lowest index) column on ties
+        let mut entering: Option<(usize, T)> = None;
+        for col in (0..num_x).filter(|col| !basis.contains(col)) {
+            let coeff = tableau[[pivot_row, col]].clone();
+            if coeff >= -eps.clone() {
+                continue;
+            }
+            let ratio = tableau[[last_row, col]].clone() / -coeff;
+            if entering.as_ref().is_none_or(|(_, best)| ratio < *best) {
+                entering = Some((col, ratio));
+            }
+        }
+        let Some((pivot_col, _)) = entering else {
+            return Ok(SolveResult::Infeasible); //the row can't be made nonnegative
+        };
+
+        if trace.iterations >= max_iterations {
+            return Ok(SolveResult::IterationLimit(tableau));
+        }
+        trace.note_pivot(&tableau[[pivot_row, pivot_col]]);
+        let ratio = tableau[[last_row, pivot_col]].clone() / -tableau[[pivot_row, pivot_col]].clone();
+        let leaving = basis[pivot_row];
+        pivot_operation(&mut tableau, pivot_row, pivot_col)?;
+        basis[pivot_row] = pivot_col;
+        trace.iterations += 1;
+        trace.log(options, pivot_col, Some((pivot_row, leaving)), ratio, tableau[[last_row, rhs_col]].clone());
+        #[cfg(feature = "std")]
+        if options.verbose {
+            eprintln!("iteration {}: dual pivot on row {}, column {}", trace.iterations, pivot_row, pivot_col);
+        }
+    }
+
+    //same shift as alternate_vertex, x can include lower bounds the tableau doesn't know about
+    let after = bounds.unflip(basic_values(&tableau, &basis));
+    let mut x = solution.x.clone();
+    for (j, val) in x.iter_mut().enumerate() {
+        *val += after[j].clone() - before[j].clone();
+    }
+    let change = tableau[[last_row, rhs_col]].clone() - start_value;
+    let objective = match solution.sense {
+        Sense::Maximize => solution.objective.clone() + change,
+        Sense::Minimize => solution.objective.clone() - change,
+    };
+
+    Ok(SolveResult::Optimal(Box::new(Solution {
+        binding_constraints: binding_rows(&tableau, &solution.unit_columns, solution.num_vars, &x, &eps),
+        x,
+        objective,
+        iterations: solution.iterations + trace.iterations,
+        pivot_sizes: trace.pivot_sizes,
+        solve_time: solution.solve_time + timer.elapsed(),
+        alternate_optima: zero_reduced_costs(&tableau, &basis, num_x, &eps),
+        degenerate: degenerate_columns(&tableau, &basis, num_x, &eps),
+        at_upper: (0..num_x).map(|j| bounds.is_flipped(j)).collect(),
+        basis,
+        tableau,
+        history: Vec::new(),
+        log: trace.log,
+        ..solution.clone()
+    })))
+}
+
+impl<T: Scalar> Solution<T> {
+    //the same tableau with coeffs·x (rel) rhs appended as a new row and slack column, the slack goes after the other x
+    //columns. the objective row doesn't change, so the result is ready for dual_simplex when the cut is violated.
+    //an = row becomes a <= and a >= row. on a rewritten solution coeffs are laid onto the rewritten columns each
+    //column of x is made of, and the new slack becomes a column of both
+    pub fn add_constraint(&self, coeffs: &[T], relation: Relation, rhs: T) -> Solution<T> {
+        let sign = match relation {
+            Relation::Le => T::one(),
+            Relation::Ge => -T::one(),
+            Relation::Eq => return self.add_constraint(coeffs, Relation::Le, rhs.clone()).add_constraint(coeffs, Relation::Ge, rhs),
+        };
+        if let Some(rewrite) = &self.rewrite {
+            let mut mapped = vec![T::zero(); rewrite.x.len()];
+            for (terms, coeff) in rewrite.columns.iter().zip(coeffs) {
+                for (col, factor) in terms {
+                    mapped[*col] += factor.clone() * coeff.clone();
+                }
+            }
+            let mut extended = Rewrite { x: Array1::zeros(0), ..rewrite.clone() };
+            extended.columns.push(vec![(rewrite.x.len(), T::one())]);
+            if !extended.violations.is_empty() {
+                extended.violations.push(Vec::new());
+            }
+            let extended = Solution { rewrite: Some(extended), ..self.clone() };
+            return extended.read_back(self.rewritten().unwrap().add_constraint(&mapped, relation, rhs));
+        }
+        let num_x = self.x.len();
+        let num_rows = self.tableau.nrows() - 1;
+        let rhs_col = self.tableau.ncols() - 1;
+        let bounds = ColumnBounds::of_solution(self);
+        let values = basic_values(&self.tableau, &self.basis);
+
+        //the tableau columns move away from their current values, a flipped column in the opposite direction of x
+        let mut row = Array1::<T>::zeros(rhs_col + 1);
+        row[rhs_col] = sign.clone() * rhs;
+        for (j, coeff) in coeffs.iter().enumerate().take(num_x) {
+            let coeff = sign.clone() * coeff.clone();
+            let column_coeff = if bounds.is_flipped(j) { -coeff.clone() } else { coeff.clone() };
+            row[rhs_col] += column_coeff.clone() * values[j].clone() - coeff * self.x[j].clone();
+            row[j] = column_coeff;
+        }
+        for (i, &col) in self.basis.iter().enumerate() {
+            let factor = row[col].clone();
+            if !factor.is_zero() {
+                row.zip_mut_with(&self.tableau.row(i), |val, basic| *val -= factor.clone() * basic.clone());
+            }
+        }
+
+        //the new slack column goes in at num_x, ahead of any artificial columns
+        let mut tableau = Array2::<T>::zeros((num_rows + 2, rhs_col + 2));
+        let old_cols: Vec<usize> = (0..=rhs_col).map(|j| if j < num_x { j } else { j + 1 }).collect();
+        for (i, old_row) in self.tableau.rows().into_iter().enumerate() {
+            let new_row = if i < num_rows { i } else { num_rows + 1 };
+            for (j, val) in old_row.iter().enumerate() {
+                tableau[[new_row, old_cols[j]]] = val.clone();
+            }
+        }
+        for (j, val) in row.iter().enumerate() {
+            tableau[[num_rows, old_cols[j]]] = val.clone();
+        }
+        tableau[[num_rows, num_x]] = T::one();
+        let shift = |&col: &usize| if col < num_x { col } else { col + 1 };
+
+        let mut basis: Vec<usize> = self.basis.iter().map(shift).collect();
+        basis.push(num_x);
+        let mut unit_columns: Vec<usize> = self.unit_columns.iter().map(shift).collect();
+        unit_columns.push(num_x);
+        let mut x = self.x.to_vec();
+        x.push(row[rhs_col].clone());
+        let mut costs = self.costs.to_vec();
+        costs.insert(num_x, T::zero());
+        let mut row_signs = self.row_signs.clone();
+        row_signs.push(sign);
+
+        let x = Array1::from(x);
+        Solution {
+            binding_constraints: binding_rows(&tableau, &unit_columns, self.num_vars, &x, &T::zero()),
+            x,
+            costs: Array1::from(costs),
+            row_signs,
+            at_upper: self.at_upper.iter().copied().chain(core::iter::once(false)).collect(),
+            upper_bounds: self.upper_bounds.iter().cloned().chain(core::iter::once(None)).collect(),
+            lower_bounds: self.lower_bounds.iter().cloned().chain(core::iter::once(T::zero())).collect(),
+            degenerate: degenerate_columns(&tableau, &basis, num_x + 1, &T::zero()),
+            basis,
+            unit_columns,
+            tableau,
+            history: Vec::new(),
+            log: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    //add_constraint followed by dual_simplex: the retained basis plus the new slack stays dual feasible, so only the
+    //new row's infeasibility has to be pivoted away. coeffs are over the columns of x, like in add_constraint
+    pub fn add_constraint_and_resolve(
+        &self,
+        coeffs: &[T],
+        relation: Relation,
+        rhs: T,
+        options: &SolverOptions,
+    ) -> Result<SolveResult<T>, SolverError> {
+        dual_simplex(&self.add_constraint(coeffs, relation, rhs), options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem::LpProblem;
+    use crate::test_support::{assert_objective_close, assert_solution_close, expect_optimal, wyndor};
+
+    //the warm resolve of problem + cut agrees with solving it from scratch, x and objective
+    #[track_caller]
+    fn resolves_like_a_cold_solve(problem: LpProblem, cut: &[f64], relation: Relation, rhs: f64) {
+        let options = SolverOptions::default();
+        let warm = expect_optimal(expect_optimal(problem.solve().unwrap()).add_constraint_and_resolve(cut, relation, rhs, &options).unwrap());
+        let cold = expect_optimal(problem.add_constraint(cut.to_vec(), relation, rhs).solve().unwrap());
+        assert_objective_close(cold.objective, warm.objective, 1e-9);
+        assert_solution_close(&cold.structural().to_vec(), &warm.structural().to_owned(), 1e-9);
+        if relation != Relation::Eq {
+            assert_solution_close(&cold.x.to_vec(), &warm.x, 1e-9); //an = cut is a <= and a >= row when added warm
+        }
+        assert_eq!(warm.x.len(), warm.rewrite.as_ref().map_or(warm.x.len(), |rewrite| rewrite.columns.len()));
+    }
+
+    #[test]
+    fn cut_on_a_plain_problem() {
+        resolves_like_a_cold_solve(wyndor(), &[1.0, 1.0], Relation::Le, 7.0);
+        resolves_like_a_cold_solve(wyndor(), &[0.0, 1.0], Relation::Eq, 5.0);
+        //a cut that already holds changes nothing
+        resolves_like_a_cold_solve(wyndor(), &[1.0, 0.0], Relation::Ge, 1.0);
+    }
+
+    #[test]
+    fn cut_on_a_free_var() {
+        //min x1 + 2x2 st x1 + x2 >= -3 with x1 free is -3 at (-3, 0), x1 >= -1 moves it to -1
+        let problem = LpProblem::new().objective(vec![1.0, 2.0]).add_constraint(vec![1.0, 1.0], Relation::Ge, -3.0).free(0).minimize();
+        resolves_like_a_cold_solve(problem.clone(), &[1.0, 0.0], Relation::Ge, -1.0);
+        let warm = expect_optimal(expect_optimal(problem.solve().unwrap()).add_constraint_and_resolve(&[1.0], Relation::Ge, -1.0, &SolverOptions::default()).unwrap());
+        assert_objective_close(-1.0, warm.objective, 1e-9);
+    }
+
+    #[test]
+    fn cut_on_ranged_and_soft_rows() {
+        let ranged = wyndor().add_range(vec![1.0, -1.0], -3.0, 1.0);
+        resolves_like_a_cold_solve(ranged.clone(), &[0.0, 1.0], Relation::Le, 4.5);
+        //the ranged row's slack is 1 - x1 + x2, so x1 + x2 + slack <= 2 is x2 <= 0.5
+        let warm = expect_optimal(expect_optimal(ranged.solve().unwrap()).add_constraint_and_resolve(&[1.0, 1.0, 0.0, 0.0, 0.0, 1.0], Relation::Le, 2.0, &SolverOptions::default()).unwrap());
+        let cold = expect_optimal(ranged.add_constraint(vec![0.0, 2.0], Relation::Le, 1.0).solve().unwrap());
+        assert_objective_close(3.0 * 1.5 + 5.0 * 0.5, cold.objective, 1e-9);
+        assert_objective_close(cold.objective, warm.objective, 1e-9);
+        assert_solution_close(&cold.structural().to_vec(), &warm.structural().to_owned(), 1e-9);
+
+        let soft = wyndor().add_soft_constraint(vec![1.0, 1.0], Relation::Ge, 9.0, 1.0);
+        let warm = expect_optimal(expect_optimal(soft.solve().unwrap()).add_constraint_and_resolve(&[1.0, 0.0], Relation::Le, 1.0, &SolverOptions::default()).unwrap());
+        let cold = expect_optimal(soft.add_constraint(vec![1.0, 0.0], Relation::Le, 1.0).solve().unwrap());
+        assert_objective_close(cold.objective, warm.objective, 1e-9);
+        assert_solution_close(&cold.structural().to_vec(), &warm.structural().to_owned(), 1e-9);
+        assert_solution_close(&cold.soft_violations, &Array1::from(warm.soft_violations.clone()), 1e-9);
+    }
+
+    #[test]
+    fn dual_simplex_reoptimizes_after_a_cut() {
+        let solution = expect_optimal(wyndor().solve().unwrap());
+        //x1 + x2 <= 7 cuts (2, 6) off, the dual feasible tableau is left with a negative rhs
+        let cut = solution.add_constraint(&[1.0, 1.0], Relation::Le, 7.0);
+        let rhs_col = cut.tableau.ncols() - 1;
+        assert!((0..cut.basis.len()).any(|row| cut.tableau[[row, rhs_col]] < 0.0));
+        let resolved = expect_optimal(dual_simplex(&cut, &SolverOptions::default()).unwrap());
+        //the optimum moves along 2x2 <= 12 to (1, 6) for 33, one dual pivot
+        assert_objective_close(33.0, resolved.objective, 1e-9);
+        assert_solution_close(&[1.0, 6.0], &resolved.structural().to_owned(), 1e-9);
+        assert_eq!(resolved.iterations - solution.iterations, 1);
+    }
+
+    #[test]
+    fn dual_simplex_finds_an_infeasible_cut() {
+        let solution = expect_optimal(wyndor().solve().unwrap());
+        let cut = solution.add_constraint(&[1.0, 1.0], Relation::Ge, 11.0); //x1 <= 4 and x2 <= 6 leave at most 10
+        assert_eq!(dual_simplex(&cut, &SolverOptions::default()).unwrap(), SolveResult::Infeasible);
+    }
+
+    #[test]
+    fn dual_simplex_needs_a_dual_feasible_tableau() {
+        let mut solution = expect_optimal(wyndor().solve().unwrap());
+        let last_row = solution.tableau.nrows() - 1;
+        let col = (0..solution.x.len()).find(|col| !solution.basis.contains(col)).unwrap();
+        solution.tableau[[last_row, col]] = -1.0;
+        assert_eq!(dual_simplex(&solution, &SolverOptions::default()), Err(SolverError::NotDualFeasible { col }));
+    }
+}
@@ -0,0 +1,160 @@
+use ndarray::{Array1, Array2};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Pow, Zero};
+
+use crate::problem::LpProblem;
+use crate::simplex::{check_dimensions, flip_objective, two_phase, PivotRule, Relation, Sense, SolveResult, SolverError, SolverOptions, VariableBounds};
+
+pub type ExactResult = SolveResult<BigRational>;
+
+//the same problem solved in exact rational arithmetic, so a vertex at 1/3 comes back as 1/3. every coeff is read as
+//the shortest decimal that prints as it (0.1 is 1/10, not the double closest to it). epsilon is 0 and Bland's rule
+//keeps degenerate pivots from cycling, which exact ties make a real risk. x is laid out like in solve, except that
+//a ranged row counts as its <= and >= rows. soft rows get violation columns like in solve, folded back into their
+//slacks and soft_violations afterwards
+pub fn solve_exact(problem: &LpProblem) -> Result<ExactResult, SolverError> {
+    let num_vars = problem.num_vars();
+    let original = problem;
+    let (problem, violations) = problem.with_violation_columns();
+    let problem = problem.ranges_as_constraints();
+    let split_at = problem.num_vars();
+    let split: Vec<usize> = (0..problem.bounds.len()).filter(|&var| problem.bounds[var].0 == f64::NEG_INFINITY).collect();
+    let problem = problem.split_free(&split);
+    let (c, a, b, relations) = problem.to_arrays();
+    check_dimensions(&c, &a, &b, Some(&relations))?;
+
+    let bound = |j: usize| problem.bounds.get(j).copied().unwrap_or((0.0, f64::INFINITY));
+    let bounds = VariableBounds {
+        lower: (0..a.ncols()).map(|j| to_rational(bound(j).0)).collect(),
+        width: (0..a.ncols()).map(|j| Some(bound(j)).filter(|(_, hi)| hi.is_finite()).map(|(lo, hi)| to_rational(hi) - to_rational(lo))).collect(),
+    };
+    let c: Array1<BigRational> = c.mapv(to_rational);
+    let a: Array2<BigRational> = a.mapv(to_rational);
+    let b: Array1<BigRational> = b.mapv(to_rational);
+    let options = SolverOptions { pivot_rule: PivotRule::Bland, epsilon: 0.0, ..SolverOptions::default() };
+    let result = match problem.sense {
+        Sense::Maximize => two_phase(c, &a, &b, &relations, bounds, None, &options)?,
+        Sense::Minimize => flip_objective(two_phase(-c, &a, &b, &relations, bounds, None, &options)?),
+    };
+
+    match result {
+        SolveResult::Optimal(mut solution) => {
+            let mut x = solution.x.to_vec();
+            for (k, &var) in split.iter().enumerate() {
+                let negative = x[split_at + k].clone();
+                x[var] -= negative;
+            }
+            if !violations.is_empty() {
+                solution.soft_violations = fold_violations(original, &violations, &mut x, split_at + split.len());
+            }
+            x.drain(num_vars..split_at + split.len());
+            solution.x = Array1::from(x);
+            solution.num_vars = num_vars;
+            solution.var_names = problem.var_names.clone();
+            solution.objective += to_rational(problem.objective_constant);
+            Ok(SolveResult::Optimal(solution))
+        }
+        other => Ok(other),
+    }
+}
+
+//what every soft row was violated by, and its slacks in x (from first_slack on) put back in terms of the row as
+//given like solve_soft does, the <= and >= slack of a ranged row both
+fn fold_violations(problem: &LpProblem, violations: &[(usize, usize, f64)], x: &mut [BigRational], first_slack: usize) -> Vec<BigRational> {
+    let mut slacks = Vec::new(); //(row, its relation) of every slack column, in order
+    for (row, con) in problem.constraints.iter().enumerate() {
+        match (con.relation, con.lower) {
+            (Relation::Eq, _) => {}
+            (relation, None) => slacks.push((row, relation)),
+            (_, Some(_)) => slacks.extend([(row, Relation::Le), (row, Relation::Ge)]),
+        }
+    }
+    let mut amounts = vec![BigRational::zero(); problem.constraints.len()];
+    for &(row, col, sign) in violations {
+        let signed = if sign > 0.0 { x[col].clone() } else { -x[col].clone() };
+        amounts[row] += x[col].clone();
+        for (k, _) in slacks.iter().enumerate().filter(|(_, slack)| slack.0 == row) {
+            match slacks[k].1 {
+                Relation::Le => x[first_slack + k] += signed.clone(),
+                _ => x[first_slack + k] -= signed.clone(),
+            }
+        }
+    }
+    amounts
+}
+
+//f64 Display never uses an exponent, so the digits around the point are the whole value
+fn to_rational(val: f64) -> BigRational {
+    let text = format!("{}", val);
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    let digits: BigInt = format!("{}{}", whole, fraction).parse().expect("a finite f64 prints as digits");
+    BigRational::new(digits, BigInt::from(10u32).pow(fraction.len() as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::ToPrimitive;
+    use crate::test_support::expect_optimal;
+
+    fn ratio(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(BigInt::from(numer), BigInt::from(denom))
+    }
+
+    #[test]
+    fn soft_rows_pay_their_penalty() {
+        //x1 >= 5 can't hold next to x1 + x2 <= 4, falling 1 short costs 2
+        let problem = LpProblem::new()
+            .objective(vec![1.0, 1.0])
+            .add_constraint(vec![1.0, 1.0], Relation::Le, 4.0)
+            .add_soft_constraint(vec![1.0, 0.0], Relation::Ge, 5.0, 2.0)
+            .maximize();
+        let solution = expect_optimal(solve_exact(&problem).unwrap());
+        assert_eq!(solution.objective, ratio(2, 1));
+        assert_eq!(solution.x.to_vec(), [ratio(4, 1), ratio(0, 1), ratio(0, 1), ratio(-1, 1)]);
+        assert_eq!(solution.soft_violations, [ratio(0, 1), ratio(1, 1)]);
+
+        let SolveResult::Optimal(float) = problem.solve().unwrap() else { panic!("same problem in f64") };
+        let x: Vec<f64> = solution.x.iter().map(|val| val.to_f64().unwrap()).collect();
+        assert_eq!(x, float.x.to_vec());
+    }
+
+    #[test]
+    fn soft_ranged_rows_fold_into_both_slacks() {
+        //max x2 with 2 <= x1 - x2 <= 3 soft at 3 per unit and x1 <= 1 hard: x = (1, 0) leaves the row 1 short
+        let mut problem = LpProblem::new()
+            .objective(vec![0.0, 1.0])
+            .add_constraint(vec![1.0, 0.0], Relation::Le, 1.0)
+            .add_range(vec![1.0, -1.0], 2.0, 3.0)
+            .maximize();
+        problem.constraints[1].penalty = Some(3.0);
+        let solution = expect_optimal(solve_exact(&problem).unwrap());
+        assert_eq!(solution.objective, ratio(-3, 1));
+        assert_eq!(solution.soft_violations, [ratio(0, 1), ratio(1, 1)]);
+        //x1 - x2 = 1 against both ends of the range: 3 - 1 on the <= side, 1 - 2 on the >= side
+        let ints = |vals: &[i64]| vals.iter().map(|&val| ratio(val, 1)).collect::<Vec<_>>();
+        assert_eq!(solution.x.to_vec(), ints(&[1, 0, 0, 2, -1]));
+    }
+
+    #[test]
+    fn thirds_come_back_exact() {
+        //max x1 + x2 st x1 + 2x2 <= 1, 2x1 + x2 <= 1, optimum 2/3 at (1/3, 1/3)
+        let problem = LpProblem::new().objective(vec![1.0, 1.0]).add_constraint(vec![1.0, 2.0], Relation::Le, 1.0).add_constraint(vec![2.0, 1.0], Relation::Le, 1.0).maximize();
+        let solution = expect_optimal(solve_exact(&problem).unwrap());
+        assert_eq!(solution.objective, ratio(2, 3));
+        assert_eq!(solution.x.to_vec(), [ratio(1, 3), ratio(1, 3), ratio(0, 1), ratio(0, 1)]);
+        let SolveResult::Optimal(float) = problem.solve().unwrap() else { panic!("same problem in f64") };
+        assert_ne!(BigRational::from_float(float.x[0]), Some(ratio(1, 3)));
+    }
+
+    #[test]
+    fn decimal_coeffs_are_read_as_written() {
+        //max x st 0.1x <= 0.3 is x = 3, dividing the doubles gives 2.9999999999999996
+        let problem = LpProblem::new().objective(vec![1.0]).add_constraint(vec![0.1], Relation::Le, 0.3).maximize();
+        assert_eq!(expect_optimal(solve_exact(&problem).unwrap()).x[0], ratio(3, 1));
+        assert_ne!(0.3 / 0.1, 3.0);
+    }
+}
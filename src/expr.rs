@@ -0,0 +1,198 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::problem::LpProblem;
+use crate::simplex::Relation;
+
+//sum of coeff * named var plus a constant, built with var() and the arithmetic operators, e.g.
+//var("x") * 2.0 + var("y") * 3.0 - 1.0. a name shows up once, repeated ones get their coeffs added up
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LinExpr {
+    pub terms: Vec<(String, f64)>, //in the order the names first appeared
+    pub constant: f64,
+}
+
+//1 * name
+pub fn var(name: &str) -> LinExpr {
+    LinExpr { terms: vec![(name.to_string(), 1.0)], constant: 0.0 }
+}
+
+//expr (rel) rhs, what LinExpr::leq, geq and equals give and LpProblem::add_expr_constraint takes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprConstraint {
+    pub expr: LinExpr,
+    pub relation: Relation,
+    pub rhs: f64,
+}
+
+impl LinExpr {
+    fn add_term(&mut self, name: &str, coeff: f64) {
+        match self.terms.iter_mut().find(|(term, _)| term == name) {
+            Some((_, total)) => *total += coeff,
+            None => self.terms.push((name.to_string(), coeff)),
+        }
+    }
+
+    //rust's comparison operators have to return bool, so these stand in for <=, >= and =
+    pub fn leq(self, rhs: f64) -> ExprConstraint {
+        ExprConstraint { expr: self, relation: Relation::Le, rhs }
+    }
+
+    pub fn geq(self, rhs: f64) -> ExprConstraint {
+        ExprConstraint { expr: self, relation: Relation::Ge, rhs }
+    }
+
+    pub fn equals(self, rhs: f64) -> ExprConstraint {
+        ExprConstraint { expr: self, relation: Relation::Eq, rhs }
+    }
+}
+
+impl Add for LinExpr {
+    type Output = LinExpr;
+
+    fn add(mut self, other: LinExpr) -> LinExpr {
+        for (name, coeff) in &other.terms {
+            self.add_term(name, *coeff);
+        }
+        self.constant += other.constant;
+        self
+    }
+}
+
+impl Sub for LinExpr {
+    type Output = LinExpr;
+
+    fn sub(self, other: LinExpr) -> LinExpr {
+        self + -other
+    }
+}
+
+impl Add<f64> for LinExpr {
+    type Output = LinExpr;
+
+    fn add(mut self, constant: f64) -> LinExpr {
+        self.constant += constant;
+        self
+    }
+}
+
+impl Sub<f64> for LinExpr {
+    type Output = LinExpr;
+
+    fn sub(self, constant: f64) -> LinExpr {
+        self + -constant
+    }
+}
+
+impl Mul<f64> for LinExpr {
+    type Output = LinExpr;
+
+    fn mul(mut self, factor: f64) -> LinExpr {
+        for (_, coeff) in &mut self.terms {
+            *coeff *= factor;
+        }
+        self.constant *= factor;
+        self
+    }
+}
+
+impl Mul<LinExpr> for f64 {
+    type Output = LinExpr;
+
+    fn mul(self, expr: LinExpr) -> LinExpr {
+        expr * self
+    }
+}
+
+impl Neg for LinExpr {
+    type Output = LinExpr;
+
+    fn neg(self) -> LinExpr {
+        self * -1.0
+    }
+}
+
+impl LpProblem {
+    //column of a named var, a new name becomes a new structural var after the existing ones. unnamed vars are
+    //named x1, x2, ... first, like named_values calls them
+    fn column_of(&mut self, name: &str) -> usize {
+        while self.var_names.len() < self.num_vars() {
+            self.var_names.push(format!("x{}", self.var_names.len() + 1));
+        }
+        match self.var_names.iter().position(|known| known == name) {
+            Some(col) => col,
+            None => {
+                self.var_names.push(name.to_string());
+                self.var_names.len() - 1
+            }
+        }
+    }
+
+    //coeffs of expr by column, and the columns grown to any new names
+    fn expr_coeffs(&mut self, expr: &LinExpr) -> Vec<f64> {
+        let cols: Vec<(usize, f64)> = expr.terms.iter().map(|(name, coeff)| (self.column_of(name), *coeff)).collect();
+        let mut coeffs = vec![0.0; self.var_names.len()];
+        for (col, coeff) in cols {
+            coeffs[col] += coeff;
+        }
+        coeffs
+    }
+
+    //the objective as an expression over named vars, its constant is the objective constant
+    pub fn objective_expr(mut self, expr: LinExpr) -> Self {
+        self.objective = self.expr_coeffs(&expr);
+        self.objective_constant = expr.constant;
+        self
+    }
+
+    //a row written as an expression over named vars, the expression's constant moves to the rhs
+    pub fn add_expr_constraint(mut self, constraint: ExprConstraint) -> Self {
+        let coeffs = self.expr_coeffs(&constraint.expr);
+        self.add_constraint(coeffs, constraint.relation, constraint.rhs - constraint.expr.constant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::SolveResult;
+
+    #[test]
+    fn repeated_names_add_up() {
+        let expr = var("x") * 2.0 + 3.0 * var("y") - var("x") + 1.5 - 0.5;
+        assert_eq!(expr.terms, vec![("x".to_string(), 1.0), ("y".to_string(), 3.0)]);
+        assert_eq!(expr.constant, 1.0);
+        assert_eq!(-expr.clone() + expr, LinExpr { terms: vec![("x".to_string(), 0.0), ("y".to_string(), 0.0)], constant: 0.0 });
+    }
+
+    #[test]
+    fn model_built_from_expressions() {
+        //wyndor over doors and windows: max 3 doors + 5 windows + 10 st doors <= 4, 2 windows <= 12,
+        //3 doors + 2 windows + 1 <= 19, 46 at (2, 6)
+        let (doors, windows) = (var("doors"), var("windows"));
+        let problem = LpProblem::new()
+            .objective_expr(doors.clone() * 3.0 + windows.clone() * 5.0 + 10.0)
+            .add_expr_constraint(doors.clone().leq(4.0))
+            .add_expr_constraint((2.0 * windows.clone()).leq(12.0))
+            .add_expr_constraint((3.0 * doors + 2.0 * windows + 1.0).leq(19.0))
+            .maximize();
+        assert_eq!(problem.var_names, vec!["doors", "windows"]);
+        assert_eq!(problem.constraints[2].coeffs, vec![3.0, 2.0]);
+        assert_eq!(problem.constraints[2].rhs, 18.0);
+        let SolveResult::Optimal(solution) = problem.solve().unwrap() else { panic!("the model has an optimum") };
+        assert_eq!(solution.objective, 46.0);
+        assert_eq!(solution.value_of("doors"), Some(2.0));
+        assert_eq!(solution.value_of("windows"), Some(6.0));
+    }
+
+    #[test]
+    fn new_names_extend_an_unnamed_problem() {
+        //x1 is already there unnamed and gets its default name, z becomes the second column
+        let problem = LpProblem::new().objective(vec![1.0]).add_constraint(vec![1.0], Relation::Le, 3.0).add_expr_constraint((var("x1") + var("z")).equals(5.0));
+        assert_eq!(problem.var_names, vec!["x1", "z"]);
+        assert_eq!(problem.num_vars(), 2);
+        assert_eq!((problem.constraints[1].coeffs.clone(), problem.constraints[1].relation), (vec![1.0, 1.0], Relation::Eq));
+    }
+}
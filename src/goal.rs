@@ -0,0 +1,173 @@
+use ndarray::{s, Array1};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use crate::problem::LpProblem;
+use crate::simplex::{Relation, SolveResult, SolverError, SolverOptions};
+
+//coeffs·x + under - over = target, each unit of deviation costs its penalty
+#[derive(Debug, Clone, PartialEq)]
+pub struct Goal {
+    pub coeffs: Vec<f64>,
+    pub target: f64,
+    pub under_penalty: f64, //per unit coeffs·x falls short of the target
+    pub over_penalty: f64,  //per unit it overshoots
+    pub priority: usize,    //0 is the most important level, levels are solved in increasing order
+}
+
+impl Goal {
+    //both deviations penalized by 1, top priority
+    pub fn new(coeffs: Vec<f64>, target: f64) -> Self {
+        Goal { coeffs, target, under_penalty: 1.0, over_penalty: 1.0, priority: 0 }
+    }
+
+    pub fn penalties(mut self, under: f64, over: f64) -> Self {
+        self.under_penalty = under;
+        self.over_penalty = over;
+        self
+    }
+
+    pub fn priority(mut self, priority: usize) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+//preemptive goal programming: the constraints and bounds of `problem` are hard, its objective is ignored
+//every priority level minimizes its weighted deviations with the levels above it held at what they reached
+//x holds the structural vars, an (under, over) pair per goal, then the slack/surplus vars of the hard rows,
+//objective is the weighted deviation of the last level
+pub fn solve_goals(problem: &LpProblem, goals: &[Goal], options: &SolverOptions) -> Result<SolveResult, SolverError> {
+    let num_vars = problem.num_vars().max(goals.iter().map(|goal| goal.coeffs.len()).max().unwrap_or(0));
+    let num_cols = num_vars + 2 * goals.len();
+    let num_hard_slacks = problem.constraints.iter().filter(|con| con.relation != Relation::Eq).count();
+
+    let mut base = LpProblem { objective: Vec::new(), objective_constant: 0.0, ..problem.clone() }.minimize();
+    //the deviations are always named, unnamed structural vars keep the x1, x2, ... names they'd get anyway
+    base.var_names.truncate(num_vars);
+    while base.var_names.len() < num_vars {
+        base.var_names.push(format!("x{}", base.var_names.len() + 1));
+    }
+    for g in 1..=goals.len() {
+        base.var_names.push(format!("d{}_under", g));
+        base.var_names.push(format!("d{}_over", g));
+    }
+    for (g, goal) in goals.iter().enumerate() {
+        let mut coeffs = goal.coeffs.clone();
+        coeffs.resize(num_cols, 0.0);
+        coeffs[num_vars + 2 * g] = 1.0;
+        coeffs[num_vars + 2 * g + 1] = -1.0;
+        base = base.add_constraint(coeffs, Relation::Eq, goal.target);
+    }
+
+    let mut levels: Vec<usize> = goals.iter().map(|goal| goal.priority).collect();
+    levels.sort_unstable();
+    levels.dedup();
+    let mut last = None;
+    for level in levels {
+        let mut objective = vec![0.0; num_cols];
+        for (g, goal) in goals.iter().enumerate().filter(|(_, goal)| goal.priority == level) {
+            objective[num_vars + 2 * g] = goal.under_penalty;
+            objective[num_vars + 2 * g + 1] = goal.over_penalty;
+        }
+        let solution = match base.clone().objective(objective.clone()).solve_with(options)? {
+            SolveResult::Optimal(solution) => solution,
+            other => return Ok(other),
+        };
+        //the lower levels may not make this one worse, up to rounding
+        let reached = solution.objective + options.epsilon * (1.0 + solution.objective.abs());
+        base = base.add_constraint(objective, Relation::Le, reached);
+        last = Some(solution);
+    }
+
+    let Some(mut solution) = last else {
+        return base.solve_with(options); //no goals, any feasible point will do
+    };
+    //the <= rows holding the levels come after the hard ones, their slacks get cut off
+    solution.x = solution.x.slice(s![..num_cols + num_hard_slacks]).to_owned();
+    solution.binding_constraints.retain(|&row| row < problem.constraints.len() + goals.len());
+    Ok(SolveResult::Optimal(solution))
+}
+
+//lexicographic optimization in the problem's sense: objectives[0] is optimized over the problem's constraints, then
+//each next one with every earlier objective held at the value it reached by an = row. the problem's own objective is
+//ignored. returns the last level's result, x cut back to the structural vars and the slacks of the problem's rows,
+//and the value each level reached. a level that isn't optimal stops the chain and is returned with the values of
+//the levels before it
+pub fn solve_lexicographic(
+    problem: &LpProblem,
+    objectives: &[Array1<f64>],
+    options: &SolverOptions,
+) -> Result<(SolveResult, Vec<f64>), SolverError> {
+    let num_vars = problem.num_vars().max(objectives.iter().map(|objective| objective.len()).max().unwrap_or(0));
+    let num_cols = num_vars + problem.constraints.iter().filter(|con| con.relation != Relation::Eq).count();
+    let mut base = LpProblem { objective: Vec::new(), objective_constant: 0.0, ..problem.clone() };
+    let mut values = Vec::with_capacity(objectives.len());
+    let mut last = None;
+    for objective in objectives {
+        let solution = match base.clone().objective(objective.to_vec()).solve_with(options)? {
+            SolveResult::Optimal(solution) => solution,
+            other => return Ok((other, values)),
+        };
+        base = base.add_constraint(objective.to_vec(), Relation::Eq, solution.objective);
+        values.push(solution.objective);
+        last = Some(solution);
+    }
+
+    let Some(mut solution) = last else {
+        return Ok((base.solve_with(options)?, values)); //no objectives, any feasible point will do
+    };
+    solution.x = solution.x.slice(s![..num_cols]).to_owned();
+    solution.binding_constraints.retain(|&row| row < problem.constraints.len());
+    Ok((SolveResult::Optimal(solution), values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{assert_objective_close, assert_solution_close, expect_optimal};
+
+    //x1 + x2 <= 10, goals x1 >= 6 (only falling short counts) and x2 = 7, they can't both be met
+    fn two_goals(first: usize, second: usize) -> (LpProblem, Vec<Goal>) {
+        let problem = LpProblem::new().add_constraint(vec![1.0, 1.0], Relation::Le, 10.0);
+        let goals = vec![Goal::new(vec![1.0, 0.0], 6.0).penalties(1.0, 0.0).priority(first), Goal::new(vec![0.0, 1.0], 7.0).priority(second)];
+        (problem, goals)
+    }
+
+    #[test]
+    fn higher_priority_goal_is_met_first() {
+        let (problem, goals) = two_goals(0, 1);
+        let solution = expect_optimal(solve_goals(&problem, &goals, &SolverOptions::default()).unwrap());
+        //x1 = 6 exactly, which leaves x2 = 4, 3 short of its goal
+        assert_solution_close(&[6.0, 4.0, 0.0, 0.0, 3.0, 0.0, 0.0], &solution.x, 1e-9);
+        assert_objective_close(3.0, solution.objective, 1e-9);
+        assert_eq!(solution.value_of("d2_under"), Some(solution.x[4]));
+
+        let (problem, goals) = two_goals(1, 0);
+        let swapped = expect_optimal(solve_goals(&problem, &goals, &SolverOptions::default()).unwrap());
+        assert_solution_close(&[3.0, 7.0, 3.0, 0.0, 0.0, 0.0, 0.0], &swapped.x, 1e-9);
+    }
+
+    #[test]
+    fn a_later_objective_does_not_degrade_an_earlier_one() {
+        //max x1 then max x2 over x1 + x2 <= 10, x1 <= 8: alone x2 would go to 10, after x1 = 8 only 2 are left
+        let problem = LpProblem::new().add_constraint(vec![1.0, 1.0], Relation::Le, 10.0).add_constraint(vec![1.0, 0.0], Relation::Le, 8.0).maximize();
+        let (result, values) = solve_lexicographic(&problem, &[ndarray::array![1.0, 0.0], ndarray::array![0.0, 1.0]], &SolverOptions::default()).unwrap();
+        assert_eq!(values, vec![8.0, 2.0]);
+        assert_solution_close(&[8.0, 2.0], &expect_optimal(result).structural().to_owned(), 1e-9);
+        let (_, swapped) = solve_lexicographic(&problem, &[ndarray::array![0.0, 1.0], ndarray::array![1.0, 0.0]], &SolverOptions::default()).unwrap();
+        assert_eq!(swapped, vec![10.0, 0.0]);
+    }
+
+    #[test]
+    fn lexicographic_levels_hold_what_they_reached() {
+        //max x1 + x2 over x1 + x2 <= 10, x1 <= 8, then max x2 on that face, then max x1 which can't move any more
+        let problem = LpProblem::new().add_constraint(vec![1.0, 1.0], Relation::Le, 10.0).add_constraint(vec![1.0, 0.0], Relation::Le, 8.0).maximize();
+        let objectives = [ndarray::array![1.0, 1.0], ndarray::array![0.0, 1.0], ndarray::array![1.0, 0.0]];
+        let (result, values) = solve_lexicographic(&problem, &objectives, &SolverOptions::default()).unwrap();
+        assert_eq!(values, vec![10.0, 10.0, 0.0]);
+        let solution = expect_optimal(result);
+        assert_eq!(solution.x.len(), 4);
+        assert_solution_close(&[0.0, 10.0], &solution.structural().to_owned(), 1e-9);
+    }
+}
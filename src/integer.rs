@@ -0,0 +1,387 @@
+use ndarray::Array1;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::problem::{Constraint, LpProblem};
+use crate::simplex::{Branching, Relation, Sense, Solution, SolveResult, SolverError, SolverOptions};
+use crate::verify::verify_solution;
+
+//how far from an integer a value may be and still count as one
+const INTEGRALITY_TOLERANCE: f64 = 1e-6;
+
+//relaxation objectives of the (down, up) children of a branch, None for an infeasible one
+type ChildBounds = (Option<f64>, Option<f64>);
+
+//branch and bound over the LP relaxation, the listed structural variables have to come out integer, the others stay
+//continuous. branching adds x_i <= floor and x_i >= ceil rows at the end, so x is cut back to the original problem's
+//columns. options.branching picks the variable to branch on, Solution::nodes says how many nodes it took
+pub fn solve_integer(problem: &LpProblem, integer_vars: &[usize]) -> Result<SolveResult, SolverError> {
+    solve_integer_with(problem, integer_vars, &SolverOptions::default())
+}
+
+pub fn solve_integer_with(
+    problem: &LpProblem,
+    integer_vars: &[usize],
+    options: &SolverOptions,
+) -> Result<SolveResult, SolverError> {
+    solve_integer_from(problem, integer_vars, None, options)
+}
+
+//solve_integer_with seeded with a known integer point (x, its objective) as the incumbent, so nodes that can't beat
+//it are pruned from the root on. a seed breaking a row, a bound or integrality is ignored, and the objective x really
+//has is used when the given one is better. when the search finds nothing better, the result is the seed's integer
+//values with the continuous vars solved for again
+pub fn solve_integer_from(
+    problem: &LpProblem,
+    integer_vars: &[usize],
+    seed: Option<(Array1<f64>, f64)>,
+    options: &SolverOptions,
+) -> Result<SolveResult, SolverError> {
+    let is_integer = |x: &Array1<f64>| integer_vars.iter().all(|&var| x.get(var).is_some_and(|val| (val - Float::round(*val)).abs() <= INTEGRALITY_TOLERANCE));
+    let seed = seed.filter(|(x, _)| is_integer(x) && verify_solution(problem, x, INTEGRALITY_TOLERANCE).is_ok());
+    //objective a node has to beat
+    let mut bound = seed.as_ref().map(|(x, claimed)| {
+        let actual = problem.evaluate_objective(x);
+        match problem.sense {
+            Sense::Maximize => claimed.min(actual),
+            Sense::Minimize => claimed.max(actual),
+        }
+    });
+    let mut incumbent: Option<Box<Solution>> = None;
+    let mut nodes = vec![problem.clone()];
+    let mut root = true;
+    let mut explored = 0;
+
+    while let Some(node) = nodes.pop() {
+        explored += 1;
+        let solution = match node.solve_with(options)? {
+            SolveResult::Optimal(solution) => solution,
+            SolveResult::Infeasible => continue,
+            //an unbounded root means the integer problem is unbounded or infeasible, deeper down it can't happen
+            other if root => return Ok(other),
+            SolveResult::Unbounded => continue,
+            limit @ SolveResult::IterationLimit(_) => return Ok(limit),
+        };
+        root = false;
+
+        //prune by bound, the relaxation can't beat the incumbent
+        if bound.is_some_and(|best| !is_better(solution.objective, best, problem.sense)) {
+            continue;
+        }
+
+        //the fractional integer variables, most fractional first
+        let mut fractional: Vec<(usize, f64)> = integer_vars
+            .iter()
+            .map(|&var| (var, solution.x[var] - Float::floor(solution.x[var])))
+            .filter(|&(_, frac)| frac > INTEGRALITY_TOLERANCE && frac < 1.0 - INTEGRALITY_TOLERANCE)
+            .collect();
+        fractional.sort_by(|a, b| (a.1 - 0.5).abs().total_cmp(&(b.1 - 0.5).abs()));
+
+        let branch = match options.branching {
+            Branching::MostFractional => fractional.first().map(|&(var, _)| (var, (Some(solution.objective), Some(solution.objective)))),
+            Branching::Strong { candidates } => strong_branch(&node, &solution, &fractional[..candidates.max(1).min(fractional.len())], options)?,
+        };
+        match branch {
+            Some((var, (down_bound, up_bound))) => {
+                //children that can't beat the incumbent are dropped, the more promising one goes on top, down on ties
+                let (down, up) = children(&node, var, solution.x[var]);
+                let mut kept: Vec<(LpProblem, f64)> = [(up, up_bound), (down, down_bound)]
+                    .into_iter()
+                    .filter_map(|(child, bound)| Some((child, bound?)))
+                    .filter(|&(_, child_bound)| bound.is_none_or(|best| is_better(child_bound, best, problem.sense)))
+                    .collect();
+                kept.sort_by(|a, b| match problem.sense {
+                    Sense::Maximize => a.1.total_cmp(&b.1),
+                    Sense::Minimize => b.1.total_cmp(&a.1),
+                });
+                nodes.extend(kept.into_iter().map(|(child, _)| child));
+            }
+            None => {
+                bound = Some(solution.objective);
+                incumbent = Some(solution);
+            }
+        }
+    }
+    #[cfg(feature = "std")]
+    if options.verbose {
+        eprintln!("branch and bound explored {} nodes", explored);
+    }
+
+    if let (None, Some((x, _))) = (&incumbent, &seed) {
+        let mut fixed = problem.clone();
+        for &var in integer_vars {
+            fixed = fixed.bound(var, Float::round(x[var]), Float::round(x[var]));
+        }
+        if let SolveResult::Optimal(solution) = fixed.solve_with(options)? {
+            incumbent = Some(solution);
+        }
+    }
+
+    Ok(match incumbent {
+        Some(mut solution) => {
+            let num_rows = problem.constraints.len();
+            let num_cols = problem.num_vars() + problem.constraints.iter().filter(|con| con.relation != Relation::Eq).count();
+            solution.x = solution.x.slice(ndarray::s![..num_cols]).to_owned();
+            solution.binding_constraints.retain(|&row| row < num_rows);
+            solution.soft_violations.truncate(num_rows);
+            if let Some(rewrite) = &mut solution.rewrite {
+                rewrite.columns.truncate(num_cols);
+                rewrite.violations.truncate(num_rows);
+            }
+            solution.nodes = explored;
+            SolveResult::Optimal(solution)
+        }
+        None => SolveResult::Infeasible,
+    })
+}
+
+//node with x_var <= floor(value) added, and with x_var >= ceil(value)
+fn children(node: &LpProblem, var: usize, value: f64) -> (LpProblem, LpProblem) {
+    let mut unit = vec![0.0; node.num_vars()];
+    unit[var] = 1.0;
+    let up = node.clone().add_constraint(unit.clone(), Relation::Ge, Float::ceil(value));
+    (node.clone().add_constraint(unit, Relation::Le, Float::floor(value)), up)
+}
+
+//the child relaxation's objective, None if it's infeasible. one that isn't solved to optimality keeps the node's bound
+fn child_bound(child: &LpProblem, bound: f64, options: &SolverOptions) -> Result<Option<f64>, SolverError> {
+    Ok(match child.solve_with(options)? {
+        SolveResult::Optimal(solution) => Some(solution.objective),
+        SolveResult::Infeasible => None,
+        _ => Some(bound),
+    })
+}
+
+//solves both children of every candidate and scores it by the product of how much each child's bound gets worse,
+//an infeasible child counts as infinitely worse. returns the best one with its children's bounds, None only if there
+//are no candidates
+fn strong_branch(
+    node: &LpProblem,
+    solution: &Solution,
+    candidates: &[(usize, f64)],
+    options: &SolverOptions,
+) -> Result<Option<(usize, ChildBounds)>, SolverError> {
+    let degradation = |bound: Option<f64>| match (bound, node.sense) {
+        (None, _) => f64::INFINITY,
+        (Some(bound), Sense::Maximize) => (solution.objective - bound).max(INTEGRALITY_TOLERANCE),
+        (Some(bound), Sense::Minimize) => (bound - solution.objective).max(INTEGRALITY_TOLERANCE),
+    };
+    let mut best = None;
+    let mut best_score = f64::NEG_INFINITY;
+    for &(var, _) in candidates {
+        let (down, up) = children(node, var, solution.x[var]);
+        let bounds = (child_bound(&down, solution.objective, options)?, child_bound(&up, solution.objective, options)?);
+        let score = degradation(bounds.0) * degradation(bounds.1);
+        if score > best_score {
+            best = Some((var, bounds));
+            best_score = score;
+        }
+    }
+    Ok(best)
+}
+
+fn is_better(objective: f64, best: f64, sense: Sense) -> bool {
+    match sense {
+        Sense::Maximize => objective > best + INTEGRALITY_TOLERANCE,
+        Sense::Minimize => objective < best - INTEGRALITY_TOLERANCE,
+    }
+}
+
+impl Solution {
+    //Gomory fractional cut from the basic row with the most fractional value: over the nonbasic columns t_j of that
+    //row, sum frac(a_j) t_j >= frac(rhs) holds at every integer point but not at this vertex. needs every column of
+    //x, slacks included, to be integer at integer points, which a pure integer problem with integer data has, and the
+    //cut's own surplus is integer too, so cuts can be added round after round. the cut is over the columns of x,
+    //a flipped column's t_j = u_j - x_j folded in, ready for add_constraint_and_resolve. None if the vertex is integer,
+    //or on a rewritten solution when the cut needs a tableau column that isn't a column of x on its own (x⁺ of a
+    //split var, a soft row's rewritten slack)
+    pub fn gomory_cut(&self) -> Option<Constraint> {
+        if let Some(rewrite) = &self.rewrite {
+            let cut = self.rewritten()?.gomory_cut()?;
+            let mut coeffs = vec![0.0; self.x.len()];
+            for (col, &coeff) in cut.coeffs.iter().enumerate().filter(|(_, coeff)| **coeff != 0.0) {
+                let j = rewrite.columns.iter().position(|terms| terms[..] == [(col, 1.0)])?;
+                coeffs[j] = coeff;
+            }
+            return Some(Constraint { coeffs, ..cut });
+        }
+        let num_x = self.x.len();
+        let rhs_col = self.tableau.ncols() - 1;
+        let frac = |val: f64| {
+            let frac = val - Float::floor(val);
+            if (INTEGRALITY_TOLERANCE..=1.0 - INTEGRALITY_TOLERANCE).contains(&frac) { frac } else { 0.0 }
+        };
+        let row = (0..self.basis.len())
+            .filter(|&row| self.basis[row] < num_x && frac(self.tableau[[row, rhs_col]]) > 0.0)
+            .min_by(|&a, &b| (frac(self.tableau[[a, rhs_col]]) - 0.5).abs().total_cmp(&(frac(self.tableau[[b, rhs_col]]) - 0.5).abs()))?;
+
+        let mut coeffs = vec![0.0; num_x];
+        let mut rhs = frac(self.tableau[[row, rhs_col]]);
+        for (j, coeff) in coeffs.iter_mut().enumerate().filter(|(j, _)| !self.basis.contains(j)) {
+            let sign = if self.at_upper[j] { -1.0 } else { 1.0 };
+            *coeff = sign * frac(self.tableau[[row, j]]);
+            rhs += *coeff * self.x[j]; //t_j is 0, so x_j sits at its shift
+        }
+        Some(Constraint { coeffs, relation: Relation::Ge, rhs, lower: None, penalty: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{assert_objective_close, expect_optimal, random_feasible_lp};
+
+    #[track_caller]
+    fn assert_integer(problem: &LpProblem, solution: &Solution, vars: &[usize]) {
+        assert!(verify_solution(problem, &solution.x, 1e-6).is_ok(), "{:?}", solution.x);
+        for &var in vars {
+            assert!((solution.x[var] - Float::round(solution.x[var])).abs() <= INTEGRALITY_TOLERANCE, "x{} = {}", var + 1, solution.x[var]);
+        }
+    }
+
+    //max 8x1 + 5x2 st x1 + x2 <= 6, 9x1 + 5x2 <= 45: the relaxation gives 41.25 at (3.75, 2.25), the integers 40 at (5, 0)
+    fn textbook() -> LpProblem {
+        LpProblem::new()
+            .objective(vec![8.0, 5.0])
+            .add_constraint(vec![1.0, 1.0], Relation::Le, 6.0)
+            .add_constraint(vec![9.0, 5.0], Relation::Le, 45.0)
+            .maximize()
+    }
+
+    #[test]
+    fn branches_off_the_fractional_relaxation() {
+        assert_objective_close(41.25, expect_optimal(textbook().solve().unwrap()).objective, 1e-9);
+        let solution = expect_optimal(solve_integer(&textbook(), &[0, 1]).unwrap());
+        assert_objective_close(40.0, solution.objective, 1e-9);
+        assert_eq!(solution.x.len(), 4); //x1, x2 and the two slacks, the branching rows are cut off again
+        assert!((solution.x[0] - 5.0).abs() < 1e-9 && solution.x[1].abs() < 1e-9, "{:?}", solution.x);
+        assert!(solution.nodes > 1);
+        //only x2 integer: x2 = 2 leaves x1 = 35/9 from the second row
+        let mixed = expect_optimal(solve_integer(&textbook(), &[1]).unwrap());
+        assert_objective_close(8.0 * 35.0 / 9.0 + 10.0, mixed.objective, 1e-9);
+    }
+
+    #[test]
+    fn knapsack() {
+        //weights 12 2 1 4 1, values 4 2 1 10 2, capacity 15: everything but the 12 for 15
+        let mut problem = LpProblem::new().objective(vec![4.0, 2.0, 1.0, 10.0, 2.0]).add_constraint(vec![12.0, 2.0, 1.0, 4.0, 1.0], Relation::Le, 15.0).maximize();
+        for item in 0..5 {
+            problem = problem.bound(item, 0.0, 1.0);
+        }
+        let vars: Vec<usize> = (0..5).collect();
+        let solution = expect_optimal(solve_integer(&problem, &vars).unwrap());
+        assert_objective_close(15.0, solution.objective, 1e-9);
+        assert_integer(&problem, &solution, &vars);
+        assert_eq!(solution.structural().iter().map(|val| Float::round(*val)).collect::<Vec<_>>(), vec![0.0, 1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn no_integer_point_is_infeasible() {
+        //2x1 = 1 with x1 integer, and 1.2 <= x1 + x2 <= 1.8 with both integer
+        let half = LpProblem::new().objective(vec![1.0]).add_constraint(vec![2.0], Relation::Eq, 1.0).maximize();
+        assert_eq!(solve_integer(&half, &[0]).unwrap(), SolveResult::Infeasible);
+        let gap = LpProblem::new().objective(vec![1.0, 1.0]).add_range(vec![1.0, 1.0], 1.2, 1.8).maximize();
+        assert_eq!(solve_integer(&gap, &[0, 1]).unwrap(), SolveResult::Infeasible);
+        assert!(matches!(solve_integer(&gap, &[0]).unwrap(), SolveResult::Optimal(_)));
+    }
+
+    #[test]
+    fn strong_branching_reaches_the_same_optimum_in_fewer_nodes() {
+        let vars: Vec<usize> = (0..4).collect();
+        let strong = SolverOptions { branching: Branching::Strong { candidates: 4 }, ..SolverOptions::default() };
+        let (mut most_fractional_nodes, mut strong_nodes) = (0, 0);
+        for seed in 0..30 {
+            let (problem, _) = random_feasible_lp(seed, 4, 4);
+            let by_fraction = expect_optimal(solve_integer_with(&problem, &vars, &SolverOptions::default()).unwrap());
+            let by_strong = expect_optimal(solve_integer_with(&problem, &vars, &strong).unwrap());
+            assert_objective_close(by_fraction.objective, by_strong.objective, 1e-9);
+            assert_integer(&problem, &by_strong, &vars);
+            assert!(by_fraction.nodes >= 1 && by_strong.nodes >= 1);
+            most_fractional_nodes += by_fraction.nodes;
+            strong_nodes += by_strong.nodes;
+        }
+        //a strong node solves every candidate's children too, but the tree it searches is smaller
+        assert!(strong_nodes < most_fractional_nodes, "strong {} vs most fractional {}", strong_nodes, most_fractional_nodes);
+    }
+
+    #[test]
+    fn a_seeded_search_explores_no_more_nodes() {
+        let vars: Vec<usize> = (0..4).collect();
+        let options = SolverOptions::default();
+        let mut pruned = false;
+        for seed in 0..15 {
+            let (problem, _) = random_feasible_lp(seed, 4, 4);
+            let cold = expect_optimal(solve_integer_with(&problem, &vars, &options).unwrap());
+            let incumbent = (cold.structural().to_owned(), cold.objective);
+            let seeded = expect_optimal(solve_integer_from(&problem, &vars, Some(incumbent), &options).unwrap());
+            assert_objective_close(cold.objective, seeded.objective, 1e-9);
+            assert_integer(&problem, &seeded, &vars);
+            assert!(seeded.nodes <= cold.nodes, "seed {}: {} seeded vs {} cold", seed, seeded.nodes, cold.nodes);
+            pruned |= seeded.nodes < cold.nodes;
+        }
+        assert!(pruned);
+    }
+
+    #[test]
+    fn a_seed_is_bounded_by_its_own_objective() {
+        let options = SolverOptions::default();
+        let cold = expect_optimal(solve_integer(&textbook(), &[0, 1]).unwrap());
+        //(4, 1) is feasible and integer at 37, short of the 40 at (5, 0)
+        let near = expect_optimal(solve_integer_from(&textbook(), &[0, 1], Some((Array1::from(vec![4.0, 1.0]), 37.0)), &options).unwrap());
+        assert_objective_close(40.0, near.objective, 1e-9);
+        assert!(near.nodes <= cold.nodes, "{} seeded vs {} cold", near.nodes, cold.nodes);
+        //the origin claiming 1000 would prune every node, it only counts for the 0 it has
+        for claimed in [1000.0, 37.0] {
+            let inflated = expect_optimal(solve_integer_from(&textbook(), &[0, 1], Some((Array1::from(vec![0.0, 0.0]), claimed)), &options).unwrap());
+            assert_objective_close(40.0, inflated.objective, 1e-9);
+        }
+        let near_inflated = expect_optimal(solve_integer_from(&textbook(), &[0, 1], Some((Array1::from(vec![4.0, 1.0]), 1000.0)), &options).unwrap());
+        assert_objective_close(40.0, near_inflated.objective, 1e-9);
+    }
+
+    #[test]
+    fn an_infeasible_seed_is_ignored() {
+        let (problem, _) = random_feasible_lp(3, 4, 4);
+        let vars: Vec<usize> = (0..4).collect();
+        let options = SolverOptions::default();
+        let cold = expect_optimal(solve_integer_with(&problem, &vars, &options).unwrap());
+        let wrong = (Array1::from(vec![1e6; 4]), cold.objective + 1e6);
+        let seeded = expect_optimal(solve_integer_from(&problem, &vars, Some(wrong), &options).unwrap());
+        assert_objective_close(cold.objective, seeded.objective, 1e-9);
+        assert_eq!(seeded.nodes, cold.nodes);
+    }
+
+    #[test]
+    fn gomory_cut_removes_the_fractional_vertex() {
+        let relaxation = expect_optimal(textbook().solve().unwrap());
+        let cut = relaxation.gomory_cut().expect("(3.75, 2.25) is fractional");
+        let lhs = |x: &[f64]| cut.coeffs.iter().zip(x).map(|(coeff, val)| coeff * val).sum::<f64>();
+        assert!(lhs(relaxation.x.as_slice().unwrap()) < cut.rhs - 1e-9);
+        //every integer point of the region, with its slacks, keeps the cut
+        for x1 in 0..=6 {
+            for x2 in 0..=6 {
+                let (s1, s2) = (6 - x1 - x2, 45 - 9 * x1 - 5 * x2);
+                if s1 >= 0 && s2 >= 0 {
+                    assert!(lhs(&[x1, x2, s1, s2].map(f64::from)) >= cut.rhs - 1e-9, "({}, {}) cut off", x1, x2);
+                }
+            }
+        }
+        let tightened = expect_optimal(relaxation.add_constraint_and_resolve(&cut.coeffs, cut.relation, cut.rhs, &SolverOptions::default()).unwrap());
+        assert!(tightened.objective < 41.25 - 1e-9 && tightened.objective >= 40.0 - 1e-9, "{}", tightened.objective);
+    }
+
+    #[test]
+    fn gomory_rounds_reach_the_integer_optimum() {
+        let mut solution = expect_optimal(textbook().solve().unwrap());
+        let mut rounds = 0;
+        while let Some(cut) = solution.gomory_cut() {
+            solution = expect_optimal(solution.add_constraint_and_resolve(&cut.coeffs, cut.relation, cut.rhs, &SolverOptions::default()).unwrap());
+            rounds += 1;
+            assert!(rounds < 50, "no integer vertex after {} cuts", rounds);
+        }
+        assert_objective_close(40.0, solution.objective, 1e-6);
+        assert!((solution.x[0] - 5.0).abs() < 1e-6 && solution.x[1].abs() < 1e-6, "{:?}", solution.x);
+    }
+}
@@ -0,0 +1,49 @@
+use serde_json::json;
+
+use crate::simplex::SolveResult;
+
+//one JSON object per result, e.g.
+//{"status":"optimal","objective":11.0,"iterations":2,"basis":[0,1,4],"x":{"x":3.0,"y":1.0,"s1":0.0,...}}
+//x is keyed the same way as Solution::named_values
+pub fn to_json(result: &SolveResult) -> String {
+    let value = match result {
+        SolveResult::Optimal(solution) => json!({
+            "status": "optimal",
+            "objective": solution.objective,
+            "iterations": solution.iterations,
+            "basis": solution.basis,
+            "x": solution.named_values(),
+        }),
+        SolveResult::Unbounded => json!({ "status": "unbounded" }),
+        SolveResult::Infeasible => json!({ "status": "infeasible" }),
+        SolveResult::IterationLimit(_) => json!({ "status": "iteration_limit" }),
+    };
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem::LpProblem;
+    use crate::simplex::Relation;
+
+    #[test]
+    fn x_is_keyed_like_named_values() {
+        let problem = LpProblem::new()
+            .objective(vec![3.0, 2.0])
+            .add_constraint(vec![1.0, 1.0], Relation::Le, 4.0)
+            .add_constraint(vec![1.0, 3.0], Relation::Ge, 6.0)
+            .var_names(vec!["x".to_string(), "y".to_string()])
+            .maximize();
+        let value: serde_json::Value = serde_json::from_str(&to_json(&problem.solve().unwrap())).unwrap();
+        assert_eq!(value["status"], "optimal");
+        assert!((value["objective"].as_f64().unwrap() - 11.0).abs() < 1e-9);
+        let x = value["x"].as_object().unwrap();
+        assert_eq!(x.keys().map(String::as_str).collect::<Vec<_>>(), ["s1", "s2", "x", "y"]);
+        for (name, expected) in [("x", 3.0), ("y", 1.0), ("s1", 0.0), ("s2", 0.0)] {
+            assert!((x[name].as_f64().unwrap() - expected).abs() < 1e-9, "{} = {}", name, x[name]);
+        }
+        let unbounded = LpProblem::new().objective(vec![1.0]).add_constraint(vec![-1.0], Relation::Le, 1.0).maximize();
+        assert_eq!(to_json(&unbounded.solve().unwrap()), r#"{"status":"unbounded"}"#);
+    }
+}
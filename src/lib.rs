@@ -0,0 +1,65 @@
+//without the std feature the solvers build on core and alloc, the text formats and the CLI need std
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "std")]
+mod csv;
+mod dual_simplex;
+#[cfg(feature = "exact")]
+mod exact;
+mod expr;
+mod goal;
+mod integer;
+#[cfg(feature = "std")]
+mod lp_format;
+#[cfg(feature = "std")]
+mod mps;
+mod operator;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod presolve;
+mod problem;
+mod revised;
+mod scalar;
+mod scaling;
+mod sensitivity;
+mod simplex;
+mod sparse;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+mod verify;
+mod vertices;
+
+#[cfg(feature = "serde")]
+pub use json::to_json;
+#[cfg(feature = "std")]
+pub use csv::{parse_csv, CsvError};
+pub use dual_simplex::dual_simplex;
+#[cfg(feature = "exact")]
+pub use exact::{solve_exact, ExactResult};
+pub use expr::{var, ExprConstraint, LinExpr};
+pub use goal::{solve_goals, solve_lexicographic, Goal};
+pub use integer::{solve_integer, solve_integer_from, solve_integer_with};
+#[cfg(feature = "std")]
+pub use lp_format::{parse_lp, write_lp, ParseError};
+#[cfg(feature = "std")]
+pub use mps::{parse_mps, MpsError};
+pub use presolve::{presolve, PresolveError, Presolved};
+pub use problem::{dual, solve, solve_range, Constraint, LpProblem};
+pub use operator::{ConstraintOperator, FnOperator};
+#[cfg(feature = "rayon")]
+pub use parallel::solve_many_parallel;
+pub use revised::{revised_simplex, revised_simplex_columns, revised_simplex_operator};
+pub use scalar::Scalar;
+pub use scaling::{simplex_solver_scaled, Scaling};
+pub use sensitivity::{complementary_slackness_report, dual_values, objective_ranges, reduced_costs, rhs_ranges, SlacknessPair, SlacknessReport};
+pub use simplex::{
+    evaluate_objective, find_basis, find_feasible, format_tableau, simplex_solver, simplex_solver_big_m, simplex_solver_bounded, simplex_solver_min, simplex_solver_staged,
+    simplex_solver_two_phase, simplex_solver_warm, solve_many, solve_tableau, Branching, InputLocation, IterationLog, PivotRule, Relation, Rewrite, Sense, Solution, SolveResult, SolverError,
+    SolverOptions, StageResult, TableauSnapshot, TieBreak,
+};
+pub use sparse::SparseMatrix;
+pub use verify::{verify_solution, Violation};
+pub use vertices::{enumerate_optimal_vertices, Vertex, Vertices};
@@ -0,0 +1,581 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::problem::LpProblem;
+use crate::simplex::{Relation, Sense};
+
+//error with the (1-based) line it was found on
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Name(String),
+    Plus,
+    Minus,
+    Colon,
+    Rel(Relation),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Objective,
+    Constraints,
+    Bounds,
+    End,
+}
+
+//parses the objective, constraints and bounds of the CPLEX LP format:
+//
+//  Maximize
+//   obj: 3 x + 2 y + 1
+//  Subject To
+//   c1: x + y <= 4
+//   c2: x + 3 y >= 6
+//   c3: -2 <= x - y <= 2
+//  Bounds
+//   x <= 3
+//   -inf <= y <= 5
+//   z free
+//  End
+//
+//the objective may have a constant term, a constraint with a number and <= in front is a ranged row
+//variables are numbered in order of first appearance, problem.var_names holds that order
+pub fn parse_lp(input: &str) -> Result<LpProblem, ParseError> {
+    let mut problem = LpProblem::new();
+    let mut section = None;
+    let mut objective_tokens = Vec::new();
+    let mut constraint_tokens = Vec::new();
+    let mut bound_tokens = Vec::new();
+
+    for (idx, raw_line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw_line.split('\\').next().unwrap_or("").trim(); //backslash starts a comment
+        if line.is_empty() {
+            continue;
+        }
+
+        let (keyword, rest) = split_keyword(line);
+        let content = match keyword.as_deref() {
+            Some("maximize") | Some("maximum") | Some("max") => {
+                problem = problem.maximize();
+                section = Some(Section::Objective);
+                rest
+            }
+            Some("minimize") | Some("minimum") | Some("min") => {
+                problem = problem.minimize();
+                section = Some(Section::Objective);
+                rest
+            }
+            Some("subject to") | Some("such that") | Some("st") | Some("s.t.") => {
+                section = Some(Section::Constraints);
+                rest
+            }
+            Some("bounds") | Some("bound") => {
+                section = Some(Section::Bounds);
+                rest
+            }
+            Some("end") => {
+                section = Some(Section::End);
+                rest
+            }
+            _ => line,
+        };
+
+        let target = match section {
+            Some(Section::Objective) => &mut objective_tokens,
+            Some(Section::Constraints) => &mut constraint_tokens,
+            Some(Section::Bounds) => &mut bound_tokens,
+            Some(Section::End) if content.is_empty() => continue,
+            Some(Section::End) => return Err(error(line_no, "content after End")),
+            None => return Err(error(line_no, "expected Maximize or Minimize before any content")),
+        };
+        tokenize(content, line_no, target)?;
+    }
+
+    if section.is_none() {
+        return Err(error(1, "missing objective section"));
+    }
+
+    let mut parser = Parser { names: Vec::new(), index: HashMap::new() };
+
+    //objective: optional label, then a linear expression that may include a constant
+    let mut pos = skip_label(&objective_tokens, 0);
+    let (objective, constant, end) = parser.expression(&objective_tokens, pos, true)?;
+    if let Some((token, line_no)) = objective_tokens.get(end) {
+        return Err(error(*line_no, &format!("unexpected {:?} in objective", token)));
+    }
+
+    //constraints: [label:] [lo <=] expression relation rhs, one after another
+    let mut rows = Vec::new();
+    pos = 0;
+    while pos < constraint_tokens.len() {
+        pos = skip_label(&constraint_tokens, pos);
+        let lower = match signed_number(&constraint_tokens, pos) {
+            Some((lo, end)) if matches!(constraint_tokens.get(end), Some((Token::Rel(Relation::Le), _))) => {
+                pos = end + 1;
+                Some(lo)
+            }
+            _ => None,
+        };
+        let (terms, _, end) = parser.expression(&constraint_tokens, pos, false)?;
+        let line_no = constraint_tokens.get(end).or(constraint_tokens.last()).map_or(1, |t| t.1);
+        let relation = match constraint_tokens.get(end) {
+            Some((Token::Rel(rel), _)) => *rel,
+            _ => return Err(error(line_no, "expected <=, >= or = in constraint")),
+        };
+        if lower.is_some() && relation != Relation::Le {
+            return Err(error(line_no, "a ranged constraint needs <= on both sides"));
+        }
+        let (rhs, end) = signed_number(&constraint_tokens, end + 1)
+            .ok_or_else(|| error(line_no, "expected a number on the right-hand side"))?;
+        rows.push((terms, relation, rhs, lower));
+        pos = end;
+    }
+
+    //bounds: `x free`, `x = v`, or [lo <=] x [<= hi] (also with >=), where lo and hi may be +-inf
+    let mut bounds = Vec::new();
+    pos = 0;
+    while pos < bound_tokens.len() {
+        let (start, line_no) = (pos, bound_tokens[pos].1);
+        let mut bound = (None, None);
+        let mut pos_var = pos;
+        if let Some((value, end)) = signed_bound(&bound_tokens, pos) {
+            match bound_tokens.get(end) {
+                Some((Token::Rel(Relation::Le), _)) => bound.0 = Some(value),
+                Some((Token::Rel(Relation::Ge), _)) => bound.1 = Some(value),
+                _ => return Err(error(line_no, "expected <= or >= after the bound")),
+            }
+            pos_var = end + 1;
+        }
+        let var = match bound_tokens.get(pos_var) {
+            Some((Token::Name(name), _)) => parser.var(name),
+            _ => return Err(error(line_no, "expected a variable in bounds")),
+        };
+        pos = pos_var + 1;
+        match bound_tokens.get(pos) {
+            Some((Token::Name(word), _)) if word.eq_ignore_ascii_case("free") => {
+                bound = (Some(f64::NEG_INFINITY), Some(f64::INFINITY));
+                pos += 1;
+            }
+            Some((Token::Rel(rel), _)) => {
+                let (value, end) = signed_bound(&bound_tokens, pos + 1).ok_or_else(|| error(line_no, "expected a number in bounds"))?;
+                match rel {
+                    Relation::Le => bound.1 = Some(value),
+                    Relation::Ge => bound.0 = Some(value),
+                    Relation::Eq => bound = (Some(value), Some(value)),
+                }
+                pos = end;
+            }
+            _ if pos_var > start => {} //just lo <= x
+            _ => return Err(error(line_no, "expected a relation or free after the variable")),
+        }
+        bounds.push((var, bound));
+    }
+
+    let num_vars = parser.names.len();
+    let dense = |terms: &[(usize, f64)]| {
+        let mut coeffs = vec![0.0; num_vars];
+        for &(var, coeff) in terms {
+            coeffs[var] += coeff;
+        }
+        coeffs
+    };
+
+    problem = problem.objective(dense(&objective)).objective_constant(constant);
+    for (terms, relation, rhs, lower) in rows {
+        problem = match lower {
+            Some(lo) => problem.add_range(dense(&terms), lo, rhs),
+            None => problem.add_constraint(dense(&terms), relation, rhs),
+        };
+    }
+    for (var, (lo, hi)) in bounds {
+        let (old_lo, old_hi) = problem.bounds.get(var).copied().unwrap_or((0.0, f64::INFINITY));
+        problem = problem.bound(var, lo.unwrap_or(old_lo), hi.unwrap_or(old_hi));
+    }
+    Ok(problem.var_names(parser.names))
+}
+
+//the problem in the format parse_lp reads, parse_lp(&write_lp(&p)) gives p back for a problem whose coeff vectors all
+//have num_vars entries. every variable shows up in the objective (with 0 if it isn't in it) so they come back in
+//the same order, unnamed ones are written as x1, x2, ... and come back under those names. the format has no soft
+//rows, so a problem with some is written as the hard one solve_soft solves: every soft row cN gets violation vars
+//cN_under and/or cN_over after the others, each costing its penalty in the objective
+pub fn write_lp(problem: &LpProblem) -> String {
+    let num_vars = problem.num_vars();
+    let names: Vec<String> = (0..num_vars).map(|j| problem.var_names.get(j).cloned().unwrap_or_else(|| format!("x{}", j + 1))).collect();
+    if problem.constraints.iter().any(|con| con.penalty.is_some()) {
+        let (mut hard, columns) = problem.with_violation_columns();
+        let violation_names = columns.iter().map(|&(row, _, sign)| format!("c{}_{}", row + 1, if sign > 0.0 { "under" } else { "over" }));
+        hard.var_names = names.into_iter().chain(violation_names).collect();
+        return write_lp(&hard);
+    }
+    let mut out = String::new();
+
+    out.push_str(if problem.sense == Sense::Maximize { "Maximize\n" } else { "Minimize\n" });
+    let objective: Vec<f64> = (0..num_vars).map(|j| problem.objective.get(j).copied().unwrap_or(0.0)).collect();
+    out.push_str(&format!(" obj: {}", linear_expression(&objective, &names, true)));
+    if problem.objective_constant != 0.0 {
+        out.push_str(&format!(" {} {}", if problem.objective_constant < 0.0 { '-' } else { '+' }, problem.objective_constant.abs()));
+    }
+    out.push('\n');
+
+    out.push_str("Subject To\n");
+    for (row, con) in problem.constraints.iter().enumerate() {
+        let relation = match con.relation {
+            Relation::Le => "<=",
+            Relation::Ge => ">=",
+            Relation::Eq => "=",
+        };
+        let lower = con.lower.map_or(String::new(), |lo| format!("{} <= ", lo));
+        out.push_str(&format!(" c{}: {}{} {} {}\n", row + 1, lower, linear_expression(&con.coeffs, &names, false), relation, con.rhs));
+    }
+
+    let bounds: Vec<String> = problem
+        .bounds
+        .iter()
+        .zip(&names)
+        .filter(|(&bound, _)| bound != (0.0, f64::INFINITY))
+        .map(|(&(lo, hi), name)| match (lo, hi) {
+            (f64::NEG_INFINITY, f64::INFINITY) => format!(" {} free\n", name),
+            _ if lo == hi => format!(" {} = {}\n", name, lo),
+            (0.0, _) => format!(" {} <= {}\n", name, bound_value(hi)),
+            (_, f64::INFINITY) => format!(" {} >= {}\n", name, bound_value(lo)),
+            _ => format!(" {} <= {} <= {}\n", bound_value(lo), name, bound_value(hi)),
+        })
+        .collect();
+    if !bounds.is_empty() {
+        out.push_str("Bounds\n");
+        out.extend(bounds);
+    }
+    out.push_str("End\n");
+    out
+}
+
+//`3 x - y + 0.5 z`, zero coeffs are left out unless `all` (a row without any nonzero gets a 0 term)
+fn linear_expression(coeffs: &[f64], names: &[String], all: bool) -> String {
+    let mut terms: Vec<(usize, f64)> = coeffs.iter().copied().enumerate().filter(|&(_, coeff)| all || coeff != 0.0).collect();
+    if terms.is_empty() {
+        terms.push((0, 0.0));
+    }
+    let mut text = String::new();
+    for (k, (j, coeff)) in terms.into_iter().enumerate() {
+        match (k, coeff < 0.0) {
+            (0, true) => text.push_str("- "),
+            (0, false) => {}
+            (_, true) => text.push_str(" - "),
+            (_, false) => text.push_str(" + "),
+        }
+        if coeff.abs() != 1.0 {
+            text.push_str(&format!("{} ", coeff.abs()));
+        }
+        text.push_str(names.get(j).map_or("x1", String::as_str));
+    }
+    text
+}
+
+//infinities as the words parse_lp reads
+fn bound_value(value: f64) -> String {
+    match value {
+        f64::INFINITY => "inf".to_string(),
+        f64::NEG_INFINITY => "-inf".to_string(),
+        _ => format!("{}", value),
+    }
+}
+
+//splits a leading section keyword (lowercased) off the line
+fn split_keyword(line: &str) -> (Option<String>, &str) {
+    let lower = line.to_lowercase();
+    for keyword in ["subject to", "such that"] {
+        if lower.starts_with(keyword) {
+            return (Some(keyword.to_string()), line[keyword.len()..].trim());
+        }
+    }
+    let first = lower.split_whitespace().next().unwrap_or("");
+    match first {
+        "maximize" | "maximum" | "max" | "minimize" | "minimum" | "min" | "st" | "s.t." | "bounds" | "bound" | "end" => {
+            (Some(first.to_string()), line[first.len()..].trim())
+        }
+        _ => (None, line),
+    }
+}
+
+fn tokenize(text: &str, line_no: usize, tokens: &mut Vec<(Token, usize)>) -> Result<(), ParseError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch.is_whitespace() {
+            i += 1;
+        } else if ch.is_ascii_digit() || ch == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            //exponent part, e.g. 1e-5
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let value = literal.parse().map_err(|_| error(line_no, &format!("bad number '{}'", literal)))?;
+            tokens.push((Token::Num(value), line_no));
+        } else if ch.is_alphabetic() || ch == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || "_.[]".contains(chars[i])) {
+                i += 1;
+            }
+            tokens.push((Token::Name(chars[start..i].iter().collect()), line_no));
+        } else {
+            let next = chars.get(i + 1).copied();
+            let (token, len) = match (ch, next) {
+                ('<', Some('=')) | ('=', Some('<')) => (Token::Rel(Relation::Le), 2),
+                ('>', Some('=')) | ('=', Some('>')) => (Token::Rel(Relation::Ge), 2),
+                ('<', _) => (Token::Rel(Relation::Le), 1),
+                ('>', _) => (Token::Rel(Relation::Ge), 1),
+                ('=', _) => (Token::Rel(Relation::Eq), 1),
+                ('+', _) => (Token::Plus, 1),
+                ('-', _) => (Token::Minus, 1),
+                (':', _) => (Token::Colon, 1),
+                _ => return Err(error(line_no, &format!("unexpected character '{}'", ch))),
+            };
+            tokens.push((token, line_no));
+            i += len;
+        }
+    }
+    Ok(())
+}
+
+//skips a `name:` label at pos
+fn skip_label(tokens: &[(Token, usize)], pos: usize) -> usize {
+    match (tokens.get(pos), tokens.get(pos + 1)) {
+        (Some((Token::Name(_), _)), Some((Token::Colon, _))) => pos + 2,
+        _ => pos,
+    }
+}
+
+//signed_number that also takes inf and infinity
+fn signed_bound(tokens: &[(Token, usize)], pos: usize) -> Option<(f64, usize)> {
+    if let Some(found) = signed_number(tokens, pos) {
+        return Some(found);
+    }
+    let mut sign = 1.0;
+    let mut pos = pos;
+    loop {
+        match tokens.get(pos) {
+            Some((Token::Plus, _)) => pos += 1,
+            Some((Token::Minus, _)) => {
+                sign = -sign;
+                pos += 1;
+            }
+            Some((Token::Name(word), _)) if word.eq_ignore_ascii_case("inf") || word.eq_ignore_ascii_case("infinity") => {
+                return Some((sign * f64::INFINITY, pos + 1));
+            }
+            _ => return None,
+        }
+    }
+}
+
+//reads [+|-]* number at pos, returns the value and the position after it
+fn signed_number(tokens: &[(Token, usize)], mut pos: usize) -> Option<(f64, usize)> {
+    let mut sign = 1.0;
+    loop {
+        match tokens.get(pos) {
+            Some((Token::Plus, _)) => pos += 1,
+            Some((Token::Minus, _)) => {
+                sign = -sign;
+                pos += 1;
+            }
+            Some((Token::Num(value), _)) => return Some((sign * value, pos + 1)),
+            _ => return None,
+        }
+    }
+}
+
+//(variable, coeff) pairs of a linear expression
+type Terms = Vec<(usize, f64)>;
+
+struct Parser {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+}
+
+impl Parser {
+    fn var(&mut self, name: &str) -> usize {
+        if let Some(&idx) = self.index.get(name) {
+            return idx;
+        }
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    //reads terms like `- 2 x + y` until something that can't continue the expression, returns the terms, the sum
+    //of the lone numbers and the position after it. lone numbers are only allowed with `constants`, otherwise a
+    //constraint's rhs would run into the expression before it
+    fn expression(&mut self, tokens: &[(Token, usize)], mut pos: usize, constants: bool) -> Result<(Terms, f64, usize), ParseError> {
+        let mut terms = Vec::new();
+        let mut constant = 0.0;
+        let mut first = true;
+        loop {
+            let start = pos;
+            let mut sign = 1.0;
+            while let Some((Token::Plus, _)) | Some((Token::Minus, _)) = tokens.get(pos) {
+                if tokens[pos].0 == Token::Minus {
+                    sign = -sign;
+                }
+                pos += 1;
+            }
+            if pos == start && !first {
+                return Ok((terms, constant, pos)); //terms have to be joined by + or -
+            }
+            first = false;
+
+            let mut coeff = 1.0;
+            if let Some((Token::Num(value), _)) = tokens.get(pos) {
+                coeff = *value;
+                pos += 1;
+            }
+            match tokens.get(pos) {
+                Some((Token::Name(name), _)) => {
+                    let var = self.var(name);
+                    terms.push((var, sign * coeff));
+                    pos += 1;
+                }
+                _ if pos > start && constants && matches!(tokens.get(pos - 1), Some((Token::Num(_), _))) => constant += sign * coeff,
+                _ if pos > start => {
+                    let line_no = tokens.get(pos).or(tokens.get(pos - 1)).map_or(1, |t| t.1);
+                    return Err(error(line_no, "constant terms are only supported in the objective"));
+                }
+                _ => return Ok((terms, constant, pos)),
+            }
+        }
+    }
+}
+
+fn error(line: usize, message: &str) -> ParseError {
+    ParseError { line, message: message.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::SolveResult;
+    use crate::test_support::{assert_objective_close, expect_optimal};
+
+    fn objective(problem: &LpProblem) -> f64 {
+        expect_optimal(problem.solve().unwrap()).objective
+    }
+
+    #[test]
+    fn parses_a_mixed_maximization() {
+        let text = "\\ max 3x + 2y + z, optimum 24 at (4, 6, 0)\nMaximize\n obj: 3 x + 2 y + z\nSubject To\n c1: x + y + z <= 10\n c2: x - y >= -2\n c3: y + z = 6\nEnd\n";
+        let problem = parse_lp(text).unwrap();
+        assert_eq!(problem.var_names, vec!["x", "y", "z"]);
+        assert_eq!(problem.sense, Sense::Maximize);
+        assert_eq!(problem.objective, vec![3.0, 2.0, 1.0]);
+        let rows: Vec<_> = problem.constraints.iter().map(|con| (con.coeffs.clone(), con.relation, con.rhs)).collect();
+        assert_eq!(
+            rows,
+            vec![(vec![1.0, 1.0, 1.0], Relation::Le, 10.0), (vec![1.0, -1.0, 0.0], Relation::Ge, -2.0), (vec![0.0, 1.0, 1.0], Relation::Eq, 6.0)]
+        );
+        let SolveResult::Optimal(solution) = problem.solve().unwrap() else { panic!("the model has an optimum") };
+        assert_objective_close(24.0, solution.objective, 1e-9);
+        assert_eq!(solution.value_of("x"), Some(4.0));
+        assert_eq!(solution.value_of("y"), Some(6.0));
+    }
+
+    #[test]
+    fn parses_bounds_ranges_and_a_constant() {
+        let text = "Minimize\n cost: 2 a + 3 b - c + 5\nst\n r1: a + b >= 2\n r2: -1 <= a - b <= 1\nBounds\n c <= 4\n b free\nEnd";
+        let problem = parse_lp(text).unwrap();
+        assert_eq!(problem.var_names, vec!["a", "b", "c"]);
+        assert_eq!((problem.sense, problem.objective_constant), (Sense::Minimize, 5.0));
+        assert_eq!(problem.constraints[1].lower, Some(-1.0));
+        assert_eq!(problem.bounds[1], (f64::NEG_INFINITY, f64::INFINITY));
+        assert_eq!(problem.bounds[2], (0.0, 4.0));
+        assert_objective_close(5.5, objective(&problem), 1e-9); //(1.5, 0.5, 4)
+    }
+
+    #[test]
+    fn errors_carry_their_line() {
+        let cases = [
+            ("x + y <= 1", 1, "Maximize"),
+            ("Maximize\n x\nSubject To\n c1: x + y 4\nEnd", 4, "<=, >= or ="),
+            ("Maximize\n x\nSubject To\n c1: x <= 4\nEnd\n y", 6, "after End"),
+            ("Maximize\n x\nSubject To\n c1: 1 <= x >= 4\nEnd", 4, "ranged"),
+        ];
+        for (text, line, message) in cases {
+            let err = parse_lp(text).unwrap_err();
+            assert_eq!(err.line, line, "{}: {}", text, err);
+            assert!(err.message.contains(message), "{}", err);
+        }
+    }
+
+    #[test]
+    fn soft_rows_are_written_with_their_penalties() {
+        let problem = LpProblem::new()
+            .objective(vec![1.0, 1.0])
+            .add_constraint(vec![1.0, 1.0], Relation::Le, 4.0)
+            .add_soft_constraint(vec![1.0, 0.0], Relation::Ge, 5.0, 2.0)
+            .add_soft_constraint(vec![0.0, 1.0], Relation::Eq, 1.0, 0.5)
+            .maximize();
+        let text = write_lp(&problem);
+        assert!(text.contains("c2_under") && text.contains("c3_under") && text.contains("c3_over"), "{}", text);
+        assert!(!text.contains("c2_over"), "{}", text);
+        let read = parse_lp(&text).unwrap();
+        assert_eq!(read.num_vars(), 5);
+        assert!(read.constraints.iter().all(|con| con.penalty.is_none()));
+        assert_objective_close(objective(&problem), objective(&read), 1e-9);
+        assert_objective_close(1.5, objective(&read), 1e-9); //(4, 0): 4 - 2 short by 1 - 0.5 short by 1
+    }
+
+    #[test]
+    fn parse_of_write_gives_the_problem_back() {
+        let names = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        let problem = LpProblem::new()
+            .objective(vec![2.0, -3.5, 0.0, 1.0, 0.25])
+            .objective_constant(-7.0)
+            .add_constraint(vec![1.0, 1.0, 0.0, 0.0, 1.0], Relation::Le, 10.0)
+            .add_constraint(vec![1.0, -1.0, 2.0, 0.0, 0.0], Relation::Ge, -2.0)
+            .add_constraint(vec![0.0, 1.0, 1.0, 1.0, 0.0], Relation::Eq, 6.0)
+            .add_range(vec![0.0, 0.0, 1.0, -1.0, 0.5], -1.5, 3.0)
+            .free(0)
+            .bound(1, 0.0, 4.0)
+            .bound(2, -2.0, f64::INFINITY)
+            .bound(3, 1.0, 1.0)
+            .bound(4, -1.0, 2.5)
+            .var_names(names)
+            .minimize();
+        assert_eq!(parse_lp(&write_lp(&problem)).unwrap(), problem);
+        let maximized = problem.maximize();
+        assert_eq!(parse_lp(&write_lp(&maximized)).unwrap(), maximized);
+    }
+
+    #[test]
+    fn unnamed_vars_come_back_under_their_default_names() {
+        let problem = LpProblem::new().objective(vec![3.0, 5.0]).add_constraint(vec![3.0, 2.0], Relation::Le, 18.0).add_constraint(vec![0.0, 2.0], Relation::Le, 12.0).maximize();
+        let read = parse_lp(&write_lp(&problem)).unwrap();
+        assert_eq!(read, problem.var_names(vec!["x1".to_string(), "x2".to_string()]));
+    }
+}
@@ -1,169 +1,248 @@
-use ndarray::prelude::*;
-use ndarray::Array1;
-
-pub fn simplex_solver(
-    c: Array1<f64>,     //objective function coeffs
-    a: &Array2<f64>,    //constraint coeffs
-    b: &Array1<f64>,    //RHS values
-) -> Option<(Array1<f64>, f64)> {
-    let num_constraints = a.nrows();
-    let num_vars = a.ncols();
-    let mut tableau = Array2::<f64>::zeros((num_constraints + 1, num_vars + 1));
-
-    //tableau init
-    tableau.slice_mut(s![..-1, ..num_vars]).assign(a);
-    tableau.slice_mut(s![..-1, -1]).assign(b);
-    tableau.slice_mut(s![-1, ..num_vars]).assign(&(-&c));
-
-    loop {
-        let last_row_index = tableau.nrows() - 1;
-        let last_col_index = tableau.ncols() - 1;
-
-        //check if optimal solution
-        if tableau.slice(s![last_row_index, 0..last_col_index]).iter().all(|&val| val >= 0.0) {
-            break;
-        }
+use std::io::Read;
+use std::process::ExitCode;
 
-        //find the pivot column idx
-        let pivot_col = find_pivot_column(&tableau, last_row_index)?;
+#[cfg(feature = "serde")]
+use lab4::to_json;
+use lab4::{parse_lp, LpProblem, Relation, Solution, SolveResult};
 
-        //find the pivot row
-        if let Some(pivot_row) = find_pivot_row(&tableau, pivot_col, last_row_index) {
-            //pivot
-            pivot_operation(&mut tableau, pivot_row, pivot_col);
-        } else {
-            eprintln!("Problem is unbounded: no valid leaving variable.");
-            return None;
-        }
-    }
+const USAGE: &str = "usage: lab4 [FILE] [--format text|json] [--precision DIGITS]\nreads the problem from FILE, or from stdin without one";
 
-    let solution = extract_solution(&tableau);
-    let objective_value = tableau[[tableau.nrows() - 1, tableau.ncols() - 1]];
+//how far from an integer a printed value may be and still be shown as one
+const SNAP_TOLERANCE: f64 = 1e-9;
 
-    Some((solution, objective_value))
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    #[cfg(feature = "serde")]
+    Json,
 }
 
-fn find_pivot_column(tableau: &Array2<f64>, last_row_index: usize) -> Option<usize> {
-    tableau
-        .slice(s![last_row_index, ..]) //take the last row (objective coeffs)
-        .iter().enumerate() //make it into (index, val) tuples array
-        .filter(|&(_, &val)| val < 0.0)//take only the negative values
-        .min_by(|a, b| a.1.partial_cmp(b.1).unwrap()) //find the lowest value of them (partial cmp because there might only be one negative number)
-        .map(|(idx, _)| idx) //if there is no negative values return None, otherwise return the index of that val (|idx, _| matches the val from min_by into idx) 
+//display only: near-integers print as integers, everything else with `precision` decimals, or in full without one
+fn format_value(val: f64, precision: Option<usize>) -> String {
+    if (val - val.round()).abs() <= SNAP_TOLERANCE {
+        return format!("{}", val.round() + 0.0); //+ 0.0 turns -0 into 0
+    }
+    match precision {
+        Some(digits) => format!("{:.*}", digits, val),
+        None => format!("{}", val),
+    }
 }
 
-fn find_pivot_row(tableau: &Array2<f64>, pivot_col: usize, last_row_index: usize) -> Option<usize> {
-    tableau
-        .slice(s![..last_row_index, pivot_col])//takes all rows except the last one (constraint coeffs) and only take those from the previously found col index
-        .iter().enumerate()//matches them into (idx, val)
-        .filter(|&(_, &val)| val > 0.0)//takes only positive values
-        .map(|(row, &val)| (row, tableau[[row, tableau.ncols() - 1]] / val)) //keep the idx and change the val to the ratio rhs/col val (tableau[[row, tableau.ncols() - 1]] takes the value in the last column of the row)
-        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap()) //take the lowest val of them
-        .map(|(row, _)| row) //return the idx
+fn print_solution(solution: &Solution, precision: Option<usize>) {
+    println!("{}", solution_line(solution, precision));
 }
 
-fn pivot_operation(tableau: &mut Array2<f64>, pivot_row: usize, pivot_col: usize) {
-    let pivot_value = tableau[[pivot_row, pivot_col]];
-    if pivot_value == 0.0 {
-        panic!("Pivot value is zero, cannot divide.");
-    }
-    //divide each val in the pivot row by the found pivot val
-    tableau.row_mut(pivot_row).map_inplace(|x| *x /= pivot_value);
-
-    for i in 0..tableau.nrows() {
-        if i != pivot_row {
-            let row_factor = tableau[[i, pivot_col]];
-            for j in 0..tableau.ncols() {
-                tableau[[i, j]] -= row_factor * tableau[[pivot_row, j]];
-            }
-        }
-    }
-    println!("Tableau after pivot operation:\n{:?}", tableau);
+//structural vars as x1, x2, ... and the slack/surplus ones as s1, s2, ..., split at solution.num_vars
+fn solution_line(solution: &Solution, precision: Option<usize>) -> String {
+    let formatted_x_vals = solution.structural().iter()
+        .enumerate()
+        .map(|(i, &val)| format!("x{}: {}", i + 1, format_value(val, precision)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let formatted_s_vals = solution.slacks().iter()
+        .enumerate()
+        .map(|(i, &val)| format!("s{}: {}", i + 1, format_value(val, precision)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("x vals: [{}] s vals: [{}]", formatted_x_vals, formatted_s_vals)
 }
 
-//extract solution and objective value from the tabeau
-fn extract_solution(tableau: &Array2<f64>) -> Array1<f64> {
-    let mut solution = Array1::zeros(tableau.ncols() - 1);
-    for j in 0..tableau.ncols() - 1 {
-        let mut is_basic = true;
-        let mut basic_row_index = None;
-        for i in 0..tableau.nrows() {
-            if tableau[[i, j]] == 1.0 {
-                if basic_row_index.is_none() {
-                    basic_row_index = Some(i);
-                } else {
-                    is_basic = false;
-                    break;
+//command line settings besides the input file
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Output {
+    format: Format,
+    precision: Option<usize>, //decimals in the text output, None prints values in full
+}
+
+//FILE, --format and --precision, anything else is an error
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<(Option<String>, Output), String> {
+    let mut path = None;
+    let mut format = Format::Text;
+    let mut precision = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    #[cfg(feature = "serde")]
+                    Some("json") => Format::Json,
+                    #[cfg(not(feature = "serde"))]
+                    Some("json") => return Err("json output needs the serde feature".to_string()),
+                    Some(other) => return Err(format!("unknown format '{}'", other)),
+                    None => return Err("--format needs a value".to_string()),
                 }
-            } else if tableau[[i, j]] != 0.0 {
-                is_basic = false;
-                break;
             }
-        }
-        if is_basic {
-            if let Some(row_index) = basic_row_index {
-                solution[j] = tableau[[row_index, tableau.ncols() - 1]];
+            "--precision" => {
+                let digits = args.next().ok_or("--precision needs a value")?;
+                precision = Some(digits.parse::<usize>().map_err(|_| format!("'{}' is not a number of digits", digits))?);
             }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{}'", arg)),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err("only one input file can be given".to_string()),
         }
     }
-    solution
+    Ok((path, Output { format, precision }))
 }
 
-//find basic variables
-fn find_basis(tableau: &Array2<f64>) -> Vec<usize> {
-    let mut basis = Vec::new();
-    for col in 0..tableau.ncols() - 1 {
-        let column = tableau.slice(s![..-1, col]);
-        if column.iter().filter(|&&x| x != 0.0).count() == 1 && column.iter().sum::<f64>() == 1.0 {
-            basis.push(col);
+//the simple format is a "max"/"min" line of objective coeffs, then one row per constraint:
+//
+//  max 3 2
+//  1 1 <= 4
+//  1 3 >= 6
+//
+//anything else is handed to the LP parser, # starts a comment in the simple format
+fn parse_problem(input: &str) -> Result<LpProblem, String> {
+    let lines: Vec<(usize, &str)> = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.split('#').next().unwrap_or("").trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .collect();
+    let is_simple = lines.first().is_some_and(|(_, line)| {
+        let mut tokens = line.split_whitespace();
+        matches!(tokens.next(), Some("max") | Some("min")) && tokens.all(|token| token.parse::<f64>().is_ok())
+    });
+    if !is_simple {
+        return parse_lp(input).map_err(|err| err.to_string());
+    }
+
+    let number = |line_no: usize, token: &str| token.parse::<f64>().map_err(|_| format!("line {}: '{}' is not a number", line_no, token));
+    let mut problem = LpProblem::new();
+    for (k, &(line_no, line)) in lines.iter().enumerate() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if k == 0 {
+            let coeffs = tokens[1..].iter().map(|token| number(line_no, token)).collect::<Result<_, _>>()?;
+            problem = problem.objective(coeffs);
+            problem = if tokens[0] == "max" { problem.maximize() } else { problem.minimize() };
+            continue;
+        }
+        let rel_pos = tokens
+            .iter()
+            .position(|token| matches!(*token, "<=" | ">=" | "="))
+            .ok_or_else(|| format!("line {}: expected <=, >= or =", line_no))?;
+        let relation = match tokens[rel_pos] {
+            "<=" => Relation::Le,
+            ">=" => Relation::Ge,
+            _ => Relation::Eq,
+        };
+        if rel_pos + 2 != tokens.len() {
+            return Err(format!("line {}: expected a single rhs value after the relation", line_no));
         }
+        let coeffs = tokens[..rel_pos].iter().map(|token| number(line_no, token)).collect::<Result<_, _>>()?;
+        problem = problem.add_constraint(coeffs, relation, number(line_no, tokens[rel_pos + 1])?);
     }
-    basis
+    Ok(problem)
 }
 
-fn print_solution(solution: &Array1<f64>) {
-    // Assuming the solution has at least 6 variables
-    let x_vals = &solution.slice(s![..solution.len() - 3]); // All variables except the last 3
-    let s_vals = &solution.slice(s![solution.len() - 3..]); // Last 3 variables (slack variables)
+fn print_result(result: &SolveResult, output: Output) {
+    match output.format {
+        Format::Text => print_text(result, output.precision),
+        #[cfg(feature = "serde")]
+        Format::Json => println!("{}", to_json(result)),
+    }
+}
 
-    let formatted_x_vals = x_vals.iter()
-        .enumerate()
-        .map(|(i, &val)| format!("x{}: {}", i + 1, val))
-        .collect::<Vec<String>>()
-        .join(", ");
+fn print_text(result: &SolveResult, precision: Option<usize>) {
+    match result {
+        SolveResult::Optimal(solution) => {
+            print_solution(solution, precision);
+            println!(
+                "Optimal objective value: {} ({} iterations in {:?})",
+                format_value(solution.objective, precision),
+                solution.iterations,
+                solution.solve_time
+            );
+            println!("Base (indices of basic variables): {:?}", solution.basis);
+        }
+        SolveResult::Unbounded => println!("The problem is unbounded."),
+        SolveResult::Infeasible => println!("The problem is infeasible."),
+        SolveResult::IterationLimit(_) => println!("Iteration limit reached."),
+    }
+}
 
-    let formatted_s_vals = s_vals.iter()
-        .enumerate()
-        .map(|(i, &val)| format!("s{}: {}", i + 1, val))
-        .collect::<Vec<String>>()
-        .join(", ");
+fn main() -> ExitCode {
+    let (path, output) = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut input = String::new();
+    let read = match &path {
+        Some(path) => std::fs::read_to_string(path).map(|text| input = text),
+        None => std::io::stdin().read_to_string(&mut input).map(|_| ()),
+    };
+    if let Err(err) = read {
+        eprintln!("couldn't read {}: {}", path.as_deref().unwrap_or("stdin"), err);
+        return ExitCode::FAILURE;
+    }
 
-    println!("x vals: [{}] s vals: [{}]", formatted_x_vals, formatted_s_vals);
+    let problem = match parse_problem(&input) {
+        Ok(problem) => problem,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    match problem.solve() {
+        Ok(result) => {
+            print_result(&result, output);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Solver error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
 }
 
-fn main() {
-    let c = array![2.0, -3.0, 0.0, -5.0, 0.0, 0.0, 0.0];
-    let a = array![
-        [-1.0, 1.0, -1.0, -1.0, 1.0, 0.0, 0.0],
-        [2.0, 4.0, 0.0, 0.0, 0.0, 1.0, 0.0],
-        [0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0],
-    ];
-    let b = array![8.0, 10.0, 3.0];
-
-    if let Some((solution, objective_value)) = simplex_solver(c.clone(), &a, &b) {
-        print_solution(&solution);
-        println!("Optimal objective value: {}", objective_value);
-        println!("Base (indices of basic variables): {:?}", find_basis(&a));
-    } else {
-        println!("The problem is unbounded or infeasible.");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lab4::test_support::expect_optimal;
+
+    #[test]
+    fn labels_follow_the_problem_size() {
+        //max x1 + x2 st x1 <= 1, x2 <= 2
+        let two = LpProblem::new().objective(vec![1.0, 1.0]).add_constraint(vec![1.0, 0.0], Relation::Le, 1.0).add_constraint(vec![0.0, 1.0], Relation::Le, 2.0).maximize();
+        assert_eq!(solution_line(&expect_optimal(two.solve().unwrap()), None), "x vals: [x1: 1, x2: 2] s vals: [s1: 0, s2: 0]");
+
+        //three vars under five rows, one of them an = row without a slack
+        let five = LpProblem::new()
+            .objective(vec![1.0, 1.0, 1.0])
+            .add_constraint(vec![1.0, 0.0, 0.0], Relation::Le, 1.0)
+            .add_constraint(vec![0.0, 1.0, 0.0], Relation::Le, 2.0)
+            .add_constraint(vec![0.0, 0.0, 1.0], Relation::Le, 3.0)
+            .add_constraint(vec![1.0, 1.0, 0.0], Relation::Eq, 3.0)
+            .add_constraint(vec![1.0, 1.0, 1.0], Relation::Le, 10.0)
+            .maximize();
+        assert_eq!(solution_line(&expect_optimal(five.solve().unwrap()), None), "x vals: [x1: 1, x2: 2, x3: 3] s vals: [s1: 0, s2: 0, s3: 0, s4: 4]");
+    }
+
+    #[test]
+    fn fewer_columns_than_structural_vars_do_not_panic() {
+        let mut solution = expect_optimal(LpProblem::new().objective(vec![1.0]).add_constraint(vec![1.0], Relation::Le, 0.5).maximize().solve().unwrap());
+        assert_eq!(solution_line(&solution, Some(2)), "x vals: [x1: 0.50] s vals: [s1: 0]");
+        solution.num_vars = 5;
+        assert_eq!(solution_line(&solution, Some(2)), "x vals: [x1: 0.50, x2: 0] s vals: []");
     }
 
-    let n = array![8.0, 0.0, 3.0];
-    if let Some((solution, objective_value)) = simplex_solver(c, &a, &n) {
-        print_solution(&solution);
-        println!("Optimal objective value: {}", objective_value);
-        println!("Base (indices of basic variables): {:?}", find_basis(&a));
-    } else {
-        println!("The problem is unbounded or infeasible.");
+    #[test]
+    fn values_print_with_the_chosen_precision() {
+        assert_eq!(format_value(3.9999999999998, None), "4");
+        assert_eq!(format_value(-1e-13, Some(3)), "0");
+        assert_eq!(format_value(2.0 / 3.0, Some(3)), "0.667");
+        assert_eq!(format_value(2.0 / 3.0, Some(0)), "1");
+        assert_eq!(format_value(1.25, None), "1.25");
+        //the stored values stay as they are
+        let mut solution = expect_optimal(LpProblem::new().objective(vec![1.0]).add_constraint(vec![3.0], Relation::Le, 2.0).maximize().solve().unwrap());
+        solution.x[1] = 3.9999999999998;
+        assert_eq!(solution_line(&solution, Some(2)), "x vals: [x1: 0.67] s vals: [s1: 4]");
+        assert_eq!(solution.x[1], 3.9999999999998);
     }
 }
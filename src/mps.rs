@@ -0,0 +1,310 @@
+//free-format MPS: every line is split on whitespace instead of being read by the fixed column positions of the
+//original format, so names can't contain spaces but fields don't have to line up
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::problem::LpProblem;
+use crate::simplex::Relation;
+
+#[derive(Debug)]
+pub enum MpsError {
+    Io(io::Error),
+    Parse { line: usize, message: String }, //1-based line number
+}
+
+impl fmt::Display for MpsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MpsError::Io(err) => write!(f, "read error: {}", err),
+            MpsError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for MpsError {}
+
+impl From<io::Error> for MpsError {
+    fn from(err: io::Error) -> Self {
+        MpsError::Io(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Name,
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Bounds,
+    End,
+}
+
+//reads an MPS file (NAME, ROWS, COLUMNS, RHS, BOUNDS, ENDATA and the optional OBJSENSE)
+//MPS problems minimize unless OBJSENSE says MAX, variables keep the order of the COLUMNS section
+//BOUNDS become variable bounds on the problem, not extra rows
+pub fn parse_mps(reader: impl BufRead) -> Result<LpProblem, MpsError> {
+    let mut section = None;
+    let mut maximize = false;
+    let mut objective_row: Option<String> = None;
+    let mut ignored_rows: Vec<String> = Vec::new(); //free rows after the first one
+    let mut rows: Vec<(String, Relation)> = Vec::new();
+    let mut row_index: HashMap<String, usize> = HashMap::new();
+    let mut var_names: Vec<String> = Vec::new();
+    let mut var_index: HashMap<String, usize> = HashMap::new();
+    let mut entries: Vec<(usize, usize, f64)> = Vec::new(); //row, var, coeff
+    let mut objective: Vec<(usize, f64)> = Vec::new();
+    let mut rhs: HashMap<usize, f64> = HashMap::new();
+    let mut bounds: Vec<(f64, f64)> = Vec::new(); //filled once COLUMNS is done
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_no = idx + 1;
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        //section headers start in the first column
+        if !line.starts_with(char::is_whitespace) {
+            section = Some(match fields[0] {
+                "NAME" => Section::Name,
+                "OBJSENSE" => {
+                    //the sense may share the header line in free MPS
+                    if let Some(&sense) = fields.get(1) {
+                        maximize = parse_sense(sense, line_no)?;
+                    }
+                    Section::ObjSense
+                }
+                "ROWS" => Section::Rows,
+                "COLUMNS" => Section::Columns,
+                "RHS" => Section::Rhs,
+                "BOUNDS" => Section::Bounds,
+                "ENDATA" => Section::End,
+                other => return Err(error(line_no, &format!("unsupported section {}", other))),
+            });
+            continue;
+        }
+
+        match section {
+            Some(Section::ObjSense) => maximize = parse_sense(fields[0], line_no)?,
+            Some(Section::Rows) => {
+                let [kind, name] = fields[..] else {
+                    return Err(error(line_no, "expected row type and name"));
+                };
+                let relation = match kind {
+                    "N" => {
+                        //only the first free row is the objective, the rest are ignored
+                        match objective_row {
+                            None => objective_row = Some(name.to_string()),
+                            Some(_) => ignored_rows.push(name.to_string()),
+                        }
+                        continue;
+                    }
+                    "L" => Relation::Le,
+                    "G" => Relation::Ge,
+                    "E" => Relation::Eq,
+                    other => return Err(error(line_no, &format!("unknown row type {}", other))),
+                };
+                row_index.insert(name.to_string(), rows.len());
+                rows.push((name.to_string(), relation));
+            }
+            Some(Section::Columns) => {
+                if fields.contains(&"'MARKER'") {
+                    continue;
+                }
+                if fields.len() < 3 || fields.len().is_multiple_of(2) {
+                    return Err(error(line_no, "expected a column name followed by row/value pairs"));
+                }
+                let var = *var_index.entry(fields[0].to_string()).or_insert_with(|| {
+                    var_names.push(fields[0].to_string());
+                    var_names.len() - 1
+                });
+                for pair in fields[1..].chunks(2) {
+                    let value = parse_number(pair[1], line_no)?;
+                    if objective_row.as_deref() == Some(pair[0]) {
+                        objective.push((var, value));
+                    } else if let Some(&row) = row_index.get(pair[0]) {
+                        entries.push((row, var, value));
+                    } else if !ignored_rows.iter().any(|row| row == pair[0]) {
+                        return Err(error(line_no, &format!("unknown row {}", pair[0])));
+                    }
+                }
+            }
+            Some(Section::Rhs) => {
+                //the rhs set name is optional, pairs always come last
+                let pairs = if fields.len() % 2 == 1 { &fields[1..] } else { &fields[..] };
+                for pair in pairs.chunks(2) {
+                    let value = parse_number(pair[1], line_no)?;
+                    if let Some(&row) = row_index.get(pair[0]) {
+                        rhs.insert(row, value);
+                    } else if objective_row.as_deref() != Some(pair[0]) && !ignored_rows.iter().any(|row| row == pair[0]) {
+                        return Err(error(line_no, &format!("unknown row {}", pair[0])));
+                    }
+                }
+            }
+            Some(Section::Bounds) => {
+                let (kind, column, value) = match fields[..] {
+                    [kind, _, column, value] => (kind, column, Some(value)),
+                    [kind, _, column] => (kind, column, None),
+                    _ => return Err(error(line_no, "expected bound type, set name, column and value")),
+                };
+                let var = *var_index
+                    .get(column)
+                    .ok_or_else(|| error(line_no, &format!("unknown column {}", column)))?;
+                let value = value.map(|v| parse_number(v, line_no)).transpose()?;
+                let need_value = || value.ok_or_else(|| error(line_no, "bound needs a value"));
+                if bounds.is_empty() {
+                    bounds = vec![(0.0, f64::INFINITY); var_names.len()];
+                }
+                match kind {
+                    "UP" => {
+                        //a negative upper bound on a var still at the default lower bound makes it unbounded below
+                        let hi = need_value()?;
+                        if hi < 0.0 && bounds[var].0 == 0.0 {
+                            bounds[var].0 = f64::NEG_INFINITY;
+                        }
+                        bounds[var].1 = hi;
+                    }
+                    "LO" => bounds[var].0 = need_value()?,
+                    "FX" => bounds[var] = (need_value()?, need_value()?),
+                    "PL" => bounds[var].1 = f64::INFINITY,
+                    "MI" => bounds[var].0 = f64::NEG_INFINITY,
+                    "FR" => bounds[var] = (f64::NEG_INFINITY, f64::INFINITY),
+                    other => return Err(error(line_no, &format!("unsupported bound type {}", other))),
+                }
+            }
+            Some(Section::Name) | Some(Section::End) => {}
+            None => return Err(error(line_no, "data before any section")),
+        }
+    }
+
+    let num_vars = var_names.len();
+    let mut c = vec![0.0; num_vars];
+    for (var, value) in objective {
+        c[var] += value;
+    }
+    let mut coeffs = vec![vec![0.0; num_vars]; rows.len()];
+    for (row, var, value) in entries {
+        coeffs[row][var] += value;
+    }
+
+    let mut problem = LpProblem::new().objective(c);
+    problem = if maximize { problem.maximize() } else { problem.minimize() };
+    for (row, ((_, relation), row_coeffs)) in rows.into_iter().zip(coeffs).enumerate() {
+        problem = problem.add_constraint(row_coeffs, relation, rhs.get(&row).copied().unwrap_or(0.0));
+    }
+    for (var, (lo, hi)) in bounds.into_iter().enumerate() {
+        problem = problem.bound(var, lo, hi);
+    }
+    Ok(problem.var_names(var_names))
+}
+
+fn parse_sense(field: &str, line_no: usize) -> Result<bool, MpsError> {
+    match field {
+        "MAX" | "MAXIMIZE" => Ok(true),
+        "MIN" | "MINIMIZE" => Ok(false),
+        other => Err(error(line_no, &format!("unknown objective sense {}", other))),
+    }
+}
+
+fn parse_number(field: &str, line_no: usize) -> Result<f64, MpsError> {
+    field.parse().map_err(|_| error(line_no, &format!("bad number '{}'", field)))
+}
+
+fn error(line: usize, message: &str) -> MpsError {
+    MpsError::Parse { line, message: message.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::SolveResult;
+    use crate::test_support::{assert_objective_close, assert_solution_close};
+
+    //max x + 2y - z st x + y + z <= 10, x - y >= -2, z = 1 with x in [1, 4], y <= 5 and z free
+    const FIXTURE: &str = "\
+NAME          BOUNDED
+OBJSENSE
+    MAX
+ROWS
+ N  COST
+ L  CAP
+ G  DIFF
+ E  FIX
+COLUMNS
+    X         COST      1.0        CAP       1.0
+    X         DIFF      1.0
+    Y         COST      2.0        CAP       1.0
+    Y         DIFF      -1.0
+    Z         COST      -1.0       CAP       1.0
+    Z         FIX       1.0
+RHS
+    RHS       CAP       10.0       DIFF      -2.0
+    RHS       FIX       1.0
+BOUNDS
+ LO BND       X         1.0
+ UP BND       X         4.0
+ UP BND       Y         5.0
+ FR BND       Z
+ENDATA
+";
+
+    #[test]
+    fn reads_the_fixture_with_its_bounds() {
+        let problem = parse_mps(FIXTURE.as_bytes()).unwrap();
+        assert_eq!(problem.var_names, ["X", "Y", "Z"]);
+        assert_eq!(problem.objective, [1.0, 2.0, -1.0]);
+        assert_eq!(problem.bounds, [(1.0, 4.0), (0.0, 5.0), (f64::NEG_INFINITY, f64::INFINITY)]);
+        let relations: Vec<Relation> = problem.constraints.iter().map(|con| con.relation).collect();
+        assert_eq!(relations, [Relation::Le, Relation::Ge, Relation::Eq]);
+        assert_eq!(problem.constraints[1].rhs, -2.0);
+
+        let SolveResult::Optimal(solution) = problem.solve().unwrap() else {
+            panic!("the fixture has an optimum");
+        };
+        //y stops at its bound of 5, x at its upper bound of 4 with 0 to spare in CAP
+        assert_solution_close(&[4.0, 5.0, 1.0], &solution.structural().to_owned(), 1e-9);
+        assert_objective_close(13.0, solution.objective, 1e-9);
+    }
+
+    #[test]
+    fn negative_up_bound_frees_the_lower_end() {
+        let input = FIXTURE.replace(" UP BND       Y         5.0", " UP BND       Y         -1.0");
+        let problem = parse_mps(input.as_bytes()).unwrap();
+        assert_eq!(problem.bounds[1], (f64::NEG_INFINITY, -1.0));
+        //an explicit lower bound is kept
+        let input = FIXTURE.replace(" UP BND       X         4.0", " UP BND       X         -4.0");
+        let crossed = parse_mps(input.as_bytes()).unwrap();
+        assert_eq!(crossed.bounds[0], (1.0, -4.0));
+        //and nothing fits between them
+        assert_eq!(crossed.solve().unwrap(), SolveResult::Infeasible);
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_field() {
+        let input = FIXTURE.replace("DIFF      -2.0", "DIFF      minus");
+        match parse_mps(input.as_bytes()) {
+            Err(MpsError::Parse { line, .. }) => assert_eq!(line, 17),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn fixture_round_trips_through_the_builder_and_lp_text() {
+        let built = LpProblem::new()
+            .objective(vec![1.0, 2.0, -1.0])
+            .add_constraint(vec![1.0, 1.0, 1.0], Relation::Le, 10.0)
+            .add_constraint(vec![1.0, -1.0, 0.0], Relation::Ge, -2.0)
+            .add_constraint(vec![0.0, 0.0, 1.0], Relation::Eq, 1.0)
+            .bound(0, 1.0, 4.0)
+            .bound(1, 0.0, 5.0)
+            .free(2)
+            .var_names(["X", "Y", "Z"].map(String::from).to_vec())
+            .maximize();
+        let problem = parse_mps(FIXTURE.as_bytes()).unwrap();
+        assert_eq!(problem, built);
+        assert_eq!(crate::lp_format::parse_lp(&crate::lp_format::write_lp(&problem)).unwrap(), problem);
+    }
+}
@@ -0,0 +1,116 @@
+use ndarray::prelude::*;
+
+use crate::scalar::Scalar;
+use crate::simplex::dot;
+
+//constraint matrix given only by its products, for A too big or too implicit to store as an Array2
+pub trait ConstraintOperator<T: Scalar> {
+    fn nrows(&self) -> usize;
+    fn ncols(&self) -> usize;
+    fn apply(&self, x: ArrayView1<T>) -> Array1<T>; //A·x
+    fn apply_transpose(&self, y: ArrayView1<T>) -> Array1<T>; //Aᵀ·y
+
+    //column j of A, A·e_j unless the implementor can do better
+    fn column(&self, j: usize) -> Array1<T> {
+        let mut unit = Array1::<T>::zeros(self.ncols());
+        unit[j] = T::one();
+        self.apply(unit.view())
+    }
+}
+
+impl<T: Scalar> ConstraintOperator<T> for Array2<T> {
+    fn nrows(&self) -> usize {
+        self.nrows()
+    }
+
+    fn ncols(&self) -> usize {
+        self.ncols()
+    }
+
+    fn apply(&self, x: ArrayView1<T>) -> Array1<T> {
+        self.rows().into_iter().map(|row| dot(row, x)).collect()
+    }
+
+    //row by row so a row-major matrix is read contiguously
+    fn apply_transpose(&self, y: ArrayView1<T>) -> Array1<T> {
+        let mut product = Array1::<T>::zeros(self.ncols());
+        for (row, weight) in self.rows().into_iter().zip(y.iter()).filter(|(_, weight)| !weight.is_zero()) {
+            product.zip_mut_with(&row, |sum, val| *sum += weight.clone() * val.clone());
+        }
+        product
+    }
+
+    fn column(&self, j: usize) -> Array1<T> {
+        self.column(j).to_owned()
+    }
+}
+
+//operator made of two closures, e.g. for a matrix generated on the fly
+pub struct FnOperator<F, G> {
+    nrows: usize,
+    ncols: usize,
+    apply: F,
+    apply_transpose: G,
+}
+
+impl<F, G> FnOperator<F, G> {
+    pub fn new(nrows: usize, ncols: usize, apply: F, apply_transpose: G) -> Self {
+        FnOperator { nrows, ncols, apply, apply_transpose }
+    }
+}
+
+impl<T, F, G> ConstraintOperator<T> for FnOperator<F, G>
+where
+    T: Scalar,
+    F: Fn(ArrayView1<T>) -> Array1<T>,
+    G: Fn(ArrayView1<T>) -> Array1<T>,
+{
+    fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    fn apply(&self, x: ArrayView1<T>) -> Array1<T> {
+        (self.apply)(x)
+    }
+
+    fn apply_transpose(&self, y: ArrayView1<T>) -> Array1<T> {
+        (self.apply_transpose)(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::revised::revised_simplex_operator;
+    use crate::simplex::{Relation, SolveResult, SolverOptions};
+
+    #[test]
+    fn row_wise_transpose_matches_the_columns() {
+        let a = array![[1.0, 0.0, 2.5], [0.0, -2.0, 1.0], [3.0, 2.0, 0.0], [0.0, 0.0, 0.0]];
+        let y = array![0.5, 0.0, -1.0, 4.0];
+        let by_columns: Array1<f64> = a.columns().into_iter().map(|col| col.dot(&y)).collect();
+        assert_eq!(ConstraintOperator::apply_transpose(&a, y.view()), by_columns);
+        assert_eq!(ConstraintOperator::apply(&a, array![1.0, 1.0, 1.0].view()), array![3.5, -1.0, 5.0, 0.0]);
+        assert_eq!(ConstraintOperator::column(&a, 2), array![2.5, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn closures_solve_like_the_matrix() {
+        let a = array![[1.0, 0.0], [0.0, 2.0], [3.0, 2.0]];
+        let operator = FnOperator::new(3, 2, |x: ArrayView1<f64>| a.dot(&x), |y: ArrayView1<f64>| a.t().dot(&y));
+        let (c, b, relations, options) = (array![3.0, 5.0], array![4.0, 12.0, 18.0], [Relation::Le; 3], SolverOptions::default());
+        let from_closures = revised_simplex_operator(c.clone(), &operator, &b, &relations, &options).unwrap();
+        let from_matrix = revised_simplex_operator(c, &a, &b, &relations, &options).unwrap();
+        let (SolveResult::Optimal(from_closures), SolveResult::Optimal(from_matrix)) = (from_closures, from_matrix) else {
+            panic!("wyndor has an optimum");
+        };
+        assert_eq!(from_closures.x, from_matrix.x);
+        assert_eq!(from_closures.objective, 36.0);
+        //the default column is A·e_j
+        assert_eq!(operator.column(1), array![0.0, 2.0, 2.0]);
+    }
+}
@@ -0,0 +1,50 @@
+use ndarray::prelude::*;
+use rayon::prelude::*;
+
+use crate::scalar::Scalar;
+use crate::simplex::{solve_many, Relation, SolveResult, SolverError, SolverOptions};
+
+//solve_many with the objectives split into one contiguous chunk per rayon thread, each chunk warm-starts on its own
+//results come back in the order of `objectives`, the first error of any chunk is returned
+pub fn solve_many_parallel<T: Scalar + Send + Sync>(
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    objectives: &[Array1<T>],
+    options: &SolverOptions,
+) -> Result<Vec<SolveResult<T>>, SolverError> {
+    let chunk_size = objectives.len().div_ceil(rayon::current_num_threads()).max(1);
+    let chunks: Vec<Vec<SolveResult<T>>> = objectives
+        .par_chunks(chunk_size)
+        .map(|chunk| solve_many(a, b, relations, chunk, options))
+        .collect::<Result<_, _>>()?;
+    Ok(chunks.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::random_feasible_lp;
+
+    #[test]
+    fn parallel_results_equal_the_sequential_ones() {
+        let options = SolverOptions::default();
+        for seed in 0..5 {
+            let (c, a, b, relations) = random_feasible_lp(seed, 8, 6).0.to_arrays();
+            //more objectives than threads so chunks hold several and warm-start inside, the x can then be another
+            //optimal vertex than the sequential one but the objective can't
+            let objectives: Vec<Array1<f64>> = (0..3 * rayon::current_num_threads() + 1).map(|k| c.mapv(|coeff| coeff + (k % 4) as f64 * 0.5)).collect();
+            let sequential = solve_many(&a, &b, &relations, &objectives, &options).unwrap();
+            let parallel = solve_many_parallel(&a, &b, &relations, &objectives, &options).unwrap();
+            assert_eq!(parallel.len(), objectives.len());
+            for (k, (par, seq)) in parallel.iter().zip(&sequential).enumerate() {
+                match (par, seq) {
+                    (SolveResult::Optimal(par), SolveResult::Optimal(seq)) => {
+                        crate::test_support::assert_objective_close(seq.objective, par.objective, 1e-9);
+                    }
+                    _ => assert_eq!(par, seq, "seed {} objective {}", seed, k),
+                }
+            }
+        }
+    }
+}
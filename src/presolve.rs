@@ -0,0 +1,269 @@
+use ndarray::Array1;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::problem::{Constraint, LpProblem};
+use crate::simplex::{Relation, SolveResult, SolverError, SolverOptions};
+
+//reduced problem plus what is needed to map its solution back onto the original rows and variables
+#[derive(Debug, Clone, PartialEq)]
+pub struct Presolved {
+    pub problem: LpProblem,
+    pub row_map: Vec<Option<usize>>, //reduced row every original row ended up in, None for dropped empty and singleton rows
+    pub fixed: Vec<(usize, f64)>,    //original variables with lo == hi, taken out of the problem at that value
+    pub tightened: Vec<(usize, (f64, f64))>, //original variables whose bounds singleton rows tightened, with the new (lo, hi)
+    pub original: LpProblem,
+}
+
+//presolve found a row nothing can satisfy
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresolveError {
+    Infeasible { row: usize }, //empty row with the wrong rhs sign, an = row clashing with an identical one, or a singleton row emptying its var's bounds
+}
+
+impl fmt::Display for PresolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresolveError::Infeasible { row } => write!(f, "constraint {} can never hold", row),
+        }
+    }
+}
+
+impl core::error::Error for PresolveError {}
+
+//turns hard singleton rows (one var left once the fixed ones are substituted) into bounds on that var, until no
+//new singleton shows up, then substitutes fixed variables (lo == hi) into the rows and the objective constant,
+//drops the singleton and all-zero rows and merges rows with the same coeffs and relation. duplicate <= and >= rows
+//keep the tighter rhs (and lower end for ranged rows), duplicate = rows have to agree
+pub fn presolve(problem: &LpProblem) -> Result<Presolved, PresolveError> {
+    let num_vars = problem.num_vars();
+    let (bounds, singleton) = tighten_bounds(problem)?;
+    let bound = |var: usize| bounds[var];
+    let original_bound = |var: usize| problem.bounds.get(var).copied().unwrap_or((0.0, f64::INFINITY));
+    let tightened = (0..num_vars).filter(|&var| bound(var) != original_bound(var)).map(|var| (var, bound(var))).collect();
+    let fixed: Vec<(usize, f64)> = (0..num_vars).filter(|&var| bound(var).0 == bound(var).1).map(|var| (var, bound(var).0)).collect();
+    let kept: Vec<usize> = (0..num_vars).filter(|var| fixed.iter().all(|(fixed_var, _)| fixed_var != var)).collect();
+    let pick = |values: &[f64]| kept.iter().map(|&var| values.get(var).copied().unwrap_or(0.0)).collect::<Vec<f64>>();
+    let fixed_part = |values: &[f64]| fixed.iter().map(|&(var, value)| values.get(var).copied().unwrap_or(0.0) * value).sum::<f64>();
+
+    let mut reduced = LpProblem {
+        objective: pick(&problem.objective),
+        objective_constant: problem.objective_constant + fixed_part(&problem.objective),
+        constraints: Vec::new(),
+        var_names: kept.iter().filter_map(|&var| problem.var_names.get(var).cloned()).collect(),
+        bounds: kept.iter().map(|&var| bound(var)).collect(),
+        start: None, //a basis of the full problem has the wrong columns
+        last_basis: Default::default(),
+        ..problem.clone()
+    };
+    let mut row_map = Vec::with_capacity(problem.constraints.len());
+
+    for (row, con) in problem.constraints.iter().enumerate() {
+        if singleton[row] {
+            row_map.push(None); //the var's bounds hold it now
+            continue;
+        }
+        let shift = fixed_part(&con.coeffs);
+        let con = &Constraint { coeffs: pick(&con.coeffs), relation: con.relation, rhs: con.rhs - shift, lower: con.lower.map(|lo| lo - shift), penalty: con.penalty };
+        //a soft row is kept as it is, it may be violated at a cost and can't be merged with another
+        if con.penalty.is_none() && con.coeffs.iter().all(|&coeff| coeff == 0.0) {
+            if !con.holds_at_zero() {
+                return Err(PresolveError::Infeasible { row });
+            }
+            row_map.push(None);
+            continue;
+        }
+
+        let coeffs = con.coeffs.clone();
+        let duplicate = reduced
+            .constraints
+            .iter()
+            .position(|kept| con.penalty.is_none() && kept.penalty.is_none() && kept.relation == con.relation && kept.coeffs == coeffs);
+        match duplicate {
+            Some(kept_row) => {
+                let kept = &mut reduced.constraints[kept_row];
+                match con.relation {
+                    Relation::Le => {
+                        kept.rhs = kept.rhs.min(con.rhs);
+                        //ranged rows keep the tighter lower end too, a plain <= row has none
+                        kept.lower = match (kept.lower, con.lower) {
+                            (Some(lo1), Some(lo2)) => Some(lo1.max(lo2)),
+                            (lo1, lo2) => lo1.or(lo2),
+                        };
+                        if kept.lower.is_some_and(|lo| lo > kept.rhs) {
+                            return Err(PresolveError::Infeasible { row });
+                        }
+                    }
+                    Relation::Ge => kept.rhs = kept.rhs.max(con.rhs),
+                    Relation::Eq if kept.rhs != con.rhs => return Err(PresolveError::Infeasible { row }),
+                    Relation::Eq => {}
+                }
+                row_map.push(Some(kept_row));
+            }
+            None => {
+                row_map.push(Some(reduced.constraints.len()));
+                reduced.constraints.push(Constraint { coeffs, relation: con.relation, rhs: con.rhs, lower: con.lower, penalty: con.penalty });
+            }
+        }
+    }
+
+    Ok(Presolved { problem: reduced, row_map, fixed, tightened, original: problem.clone() })
+}
+
+//the bounds of every var after intersecting them with what the singleton rows imply, and which rows those were.
+//a var whose bounds meet counts as fixed, so the rows it is in can turn into singletons on the next pass
+type Tightened = (Vec<(f64, f64)>, Vec<bool>);
+
+fn tighten_bounds(problem: &LpProblem) -> Result<Tightened, PresolveError> {
+    let num_vars = problem.num_vars();
+    let mut bounds: Vec<(f64, f64)> = (0..num_vars).map(|var| problem.bounds.get(var).copied().unwrap_or((0.0, f64::INFINITY))).collect();
+    let mut singleton = vec![false; problem.constraints.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (row, con) in problem.constraints.iter().enumerate() {
+            if singleton[row] || con.penalty.is_some() {
+                continue;
+            }
+            let mut free = con.coeffs.iter().enumerate().filter(|&(var, &coeff)| coeff != 0.0 && bounds[var].0 != bounds[var].1);
+            let (Some((var, &coeff)), None) = (free.next(), free.next()) else {
+                continue;
+            };
+            let shift: f64 = con.coeffs.iter().enumerate().filter(|&(other, _)| other != var && bounds[other].0 == bounds[other].1).map(|(other, coeff)| coeff * bounds[other].0).sum();
+            //lo_row <= coeff·x_var <= hi_row, then divided through by coeff
+            let (lo_row, hi_row) = match con.relation {
+                Relation::Le => (con.lower.map_or(f64::NEG_INFINITY, |lo| lo - shift), con.rhs - shift),
+                Relation::Ge => (con.rhs - shift, f64::INFINITY),
+                Relation::Eq => (con.rhs - shift, con.rhs - shift),
+            };
+            let (lo, hi) = if coeff > 0.0 { (lo_row / coeff, hi_row / coeff) } else { (hi_row / coeff, lo_row / coeff) };
+            let (old_lo, old_hi) = bounds[var];
+            let (lo, hi) = (old_lo.max(lo), old_hi.min(hi));
+            if lo > hi {
+                return Err(PresolveError::Infeasible { row });
+            }
+            bounds[var] = (lo, hi);
+            singleton[row] = true;
+            changed = true;
+        }
+    }
+    Ok((bounds, singleton))
+}
+
+impl Presolved {
+    //x of the reduced problem in the layout of the original one: structural vars with the fixed ones put back,
+    //then a slack/surplus per <= and >= row
+    pub fn lift(&self, x: &Array1<f64>) -> Array1<f64> {
+        let num_vars = self.original.num_vars();
+        let mut reduced_vars = x.iter().copied();
+        let structural: Vec<f64> = (0..num_vars)
+            .map(|var| match self.fixed.iter().find(|(fixed_var, _)| *fixed_var == var) {
+                Some(&(_, value)) => value,
+                None => reduced_vars.next().unwrap_or(0.0),
+            })
+            .collect();
+        let slacks: Vec<f64> = self.original.constraints.iter().filter(|con| con.relation != Relation::Eq).map(|con| {
+            let lhs: f64 = con.coeffs.iter().zip(&structural).map(|(coeff, val)| coeff * val).sum();
+            match con.relation {
+                Relation::Ge => lhs - con.rhs,
+                _ => con.rhs - lhs,
+            }
+        }).collect();
+        structural.into_iter().chain(slacks).collect()
+    }
+
+    //a result of the reduced problem in terms of the original one, the objective already holds the fixed vars' part
+    //through the reduced problem's objective constant. the tableau and the per-column fields stay the reduced ones,
+    //the binding rows are found again on the original rows, within the default epsilon. a Rewrite of the reduced
+    //solve is dropped, its columns are the reduced problem's
+    pub fn lift_result(&self, result: SolveResult) -> SolveResult {
+        match result {
+            SolveResult::Optimal(mut solution) => {
+                solution.x = self.lift(&solution.x);
+                let num_vars = self.original.num_vars();
+                solution.binding_constraints = self.original.binding_rows(&solution.x.as_slice().unwrap()[..num_vars], SolverOptions::default().epsilon);
+                if !solution.soft_violations.is_empty() {
+                    solution.soft_violations = self.row_map.iter().map(|kept_row| kept_row.map_or(0.0, |k| solution.soft_violations[k])).collect();
+                }
+                solution.num_vars = self.original.num_vars();
+                solution.var_names = self.original.var_names.clone();
+                solution.rewrite = None;
+                SolveResult::Optimal(solution)
+            }
+            other => other,
+        }
+    }
+
+    //solves the reduced problem and lifts the result back
+    pub fn solve_with(&self, options: &SolverOptions) -> Result<SolveResult, SolverError> {
+        Ok(self.lift_result(self.problem.solve_with(options)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{assert_objective_close, assert_solution_close, expect_optimal};
+
+    //wyndor with a third var fixed at 2: max 3x1 + 5x2 + 4x3 st x1 + x2 + x3 <= 10, 2x2 <= 12, 3x1 + 2x2 <= 18,
+    //x3 = 2, optimum 36 + 8 at (2, 6, 2)
+    fn with_fixed_var() -> LpProblem {
+        LpProblem::new()
+            .objective(vec![3.0, 5.0, 4.0])
+            .add_constraint(vec![1.0, 1.0, 1.0], Relation::Le, 10.0)
+            .add_constraint(vec![0.0, 2.0, 0.0], Relation::Le, 12.0)
+            .add_constraint(vec![3.0, 2.0, 0.0], Relation::Le, 18.0)
+            .bound(2, 2.0, 2.0)
+            .maximize()
+    }
+
+    #[test]
+    fn fixed_var_is_taken_out_and_put_back() {
+        let problem = with_fixed_var();
+        let presolved = presolve(&problem).unwrap();
+        assert_eq!(presolved.fixed, vec![(2, 2.0)]);
+        assert_eq!(presolved.problem.num_vars(), 2);
+        let first = presolved.row_map[0].unwrap();
+        assert_eq!((presolved.problem.constraints[first].coeffs.clone(), presolved.problem.constraints[first].rhs), (vec![1.0, 1.0], 8.0));
+        assert_eq!(presolved.problem.objective_constant, 8.0);
+
+        let lifted = expect_optimal(presolved.solve_with(&SolverOptions::default()).unwrap());
+        assert_objective_close(44.0, lifted.objective, 1e-9);
+        //the original layout: the three structurals, the fixed one at its value, then a slack per row
+        assert_eq!(lifted.x.len(), 3 + 3);
+        assert_solution_close(&[2.0, 6.0, 2.0], &lifted.structural().to_owned(), 1e-9);
+        let direct = expect_optimal(problem.solve().unwrap());
+        assert_solution_close(&direct.x.to_vec(), &lifted.x, 1e-9);
+    }
+
+    #[test]
+    fn singleton_rows_tighten_bounds_until_a_var_is_fixed() {
+        //x1 <= 2 and 2x1 >= 4 fix x1 at 2, which leaves x1 + x2 <= 5 a singleton on x2: x2 <= 3. only
+        //x2 + x3 <= 4 is left as a row. max x1 + x2 + x3 is 2 + 4
+        let problem = LpProblem::new()
+            .objective(vec![1.0, 1.0, 1.0])
+            .add_constraint(vec![1.0, 0.0, 0.0], Relation::Le, 2.0)
+            .add_constraint(vec![2.0, 0.0, 0.0], Relation::Ge, 4.0)
+            .add_constraint(vec![1.0, 1.0, 0.0], Relation::Le, 5.0)
+            .add_constraint(vec![0.0, 1.0, 1.0], Relation::Le, 4.0)
+            .maximize();
+        let presolved = presolve(&problem).unwrap();
+        assert_eq!(presolved.fixed, vec![(0, 2.0)]);
+        assert_eq!(presolved.tightened, vec![(0, (2.0, 2.0)), (1, (0.0, 3.0))]);
+        assert_eq!((presolved.problem.num_vars(), presolved.problem.constraints.len()), (2, 1));
+        assert_eq!(presolved.row_map, vec![None, None, None, Some(0)]);
+
+        let lifted = expect_optimal(presolved.solve_with(&SolverOptions::default()).unwrap());
+        assert_objective_close(6.0, lifted.objective, 1e-9);
+        assert_eq!(lifted.x.len(), 3 + 4);
+        assert_objective_close(expect_optimal(problem.solve().unwrap()).objective, lifted.objective, 1e-9);
+        assert!(crate::verify::verify_solution(&problem, &lifted.x, 1e-9).is_ok(), "{:?}", lifted.x);
+    }
+
+    #[test]
+    fn tightening_past_the_other_bound_is_infeasible() {
+        let problem = LpProblem::new().objective(vec![1.0]).add_constraint(vec![1.0], Relation::Le, 2.0).add_constraint(vec![1.0], Relation::Ge, 3.0);
+        assert_eq!(presolve(&problem), Err(PresolveError::Infeasible { row: 1 }));
+    }
+}
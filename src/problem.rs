@@ -0,0 +1,898 @@
+use ndarray::{Array1, Array2};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use crate::simplex::{basis_without_row, bounded_from, check_dimensions, evaluate_objective, flip_objective, Relation, Sense, SolveResult, SolverError, SolverOptions};
+
+//one row of the problem, coeffs only cover the structural variables
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    pub coeffs: Vec<f64>,
+    pub relation: Relation,
+    pub rhs: f64,
+    pub lower: Option<f64>, //lo of a ranged row lo <= coeffs·x <= rhs, always with Relation::Le
+    pub penalty: Option<f64>, //cost per unit the row is violated by, None for a hard row
+}
+
+impl Constraint {
+    //whether 0 (rel) rhs holds, all an all-zero row can ask for
+    pub(crate) fn holds_at_zero(&self) -> bool {
+        match self.relation {
+            Relation::Le => self.rhs >= 0.0 && self.lower.is_none_or(|lo| lo <= 0.0),
+            Relation::Ge => self.rhs <= 0.0,
+            Relation::Eq => self.rhs == 0.0,
+        }
+    }
+}
+
+//LP built up piece by piece, slack/surplus/artificial columns get added when it is solved
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LpProblem {
+    pub objective: Vec<f64>,
+    pub objective_constant: f64, //added to the reported objective value, doesn't change which x is optimal
+    pub constraints: Vec<Constraint>,
+    pub sense: Sense,
+    pub var_names: Vec<String>, //names of the structural variables in column order, empty if unnamed
+    pub bounds: Vec<(f64, f64)>, //(lo, hi) per structural variable, missing ones are (0, inf)
+    pub start: Option<Vec<usize>>, //basis the next solve tries first, see warm_start
+    pub(crate) last_basis: RefCell<Option<Vec<usize>>>, //basis of the latest optimal solve, the coeff setters make it the start
+}
+
+impl LpProblem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn objective(mut self, coeffs: Vec<f64>) -> Self {
+        self.objective = coeffs;
+        self
+    }
+
+    //objective·x + c0
+    pub fn objective_constant(mut self, c0: f64) -> Self {
+        self.objective_constant = c0;
+        self
+    }
+
+    pub fn add_constraint(mut self, coeffs: Vec<f64>, relation: Relation, rhs: f64) -> Self {
+        self.constraints.push(Constraint { coeffs, relation, rhs, lower: None, penalty: None });
+        self
+    }
+
+    //a row that may be violated, each unit it's off by costs penalty in the objective
+    pub fn add_soft_constraint(mut self, coeffs: Vec<f64>, relation: Relation, rhs: f64, penalty: f64) -> Self {
+        self.constraints.push(Constraint { coeffs, relation, rhs, lower: None, penalty: Some(penalty) });
+        self
+    }
+
+    //lo <= coeffs·x <= hi as a single row, solved as coeffs·x + r = hi with a bounded slack 0 <= r <= hi - lo
+    //it takes the place of a <= row in x, so its slack is hi - coeffs·x like any other
+    pub fn add_range(mut self, coeffs: Vec<f64>, lo: f64, hi: f64) -> Self {
+        self.constraints.push(Constraint { coeffs, relation: Relation::Le, rhs: hi, lower: Some(lo), penalty: None });
+        self
+    }
+
+    //lo <= x_var <= hi, either side may be infinite. lo > hi makes the problem infeasible
+    pub fn bound(mut self, var: usize, lo: f64, hi: f64) -> Self {
+        if self.bounds.len() <= var {
+            self.bounds.resize(var + 1, (0.0, f64::INFINITY));
+        }
+        self.bounds[var] = (lo, hi);
+        self
+    }
+
+    //x_var is held at 0 without deleting its column: lo = hi = 0, and a column with no room to move never enters the basis
+    pub fn forbid(self, var: usize) -> Self {
+        self.bound(var, 0.0, 0.0)
+    }
+
+    //x_var can take any sign
+    pub fn free(self, var: usize) -> Self {
+        self.bound(var, f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    pub fn var_names(mut self, names: Vec<String>) -> Self {
+        self.var_names = names;
+        self
+    }
+
+    //c_var = value, the rest of the problem stays as it is. the next solve starts from the basis of the last one
+    pub fn set_objective_coeff(mut self, var: usize, value: f64) -> Self {
+        self.mark_dirty();
+        if self.objective.len() <= var {
+            self.objective.resize(var + 1, 0.0);
+        }
+        self.objective[var] = value;
+        self
+    }
+
+    //a[row][var] = value for an existing row, like set_objective_coeff. an unknown row leaves the problem as it is
+    pub fn set_constraint_coeff(mut self, row: usize, var: usize, value: f64) -> Self {
+        if row >= self.constraints.len() {
+            return self;
+        }
+        self.mark_dirty();
+        let coeffs = &mut self.constraints[row].coeffs;
+        if coeffs.len() <= var {
+            coeffs.resize(var + 1, 0.0);
+        }
+        coeffs[var] = value;
+        self
+    }
+
+    //a changed coeff keeps the last optimal basis as a warm start, the next solve skips phase one if it still fits
+    fn mark_dirty(&mut self) {
+        if let Some(basis) = self.last_basis.get_mut().take() {
+            self.start = Some(basis);
+        }
+    }
+
+    //deletes row `index`. a warm start basis is carried over, with the row's slack pivoted out of the way (see
+    //basis_without_row), so the next solve resumes the primal simplex near the old optimum. dropping a binding row
+    //can only improve the objective, one it can't carry over is lost and the next solve starts from scratch. an index
+    //past the last row leaves the problem as it is
+    pub fn remove_constraint(mut self, index: usize) -> Self {
+        if index >= self.constraints.len() {
+            return self;
+        }
+        if let Some(start) = self.start.take() {
+            let (_, a, b, relations) = self.to_arrays();
+            let lower: Array1<f64> = (0..a.ncols()).map(|var| self.bounds.get(var).map_or(0.0, |&(lo, _)| lo)).collect();
+            if lower.iter().all(|lo| lo.is_finite()) {
+                self.start = basis_without_row(&a, &(&b - &a.dot(&lower)), &relations, &start, index, SolverOptions::default().epsilon);
+            }
+        }
+        self.constraints.remove(index);
+        self
+    }
+
+    //solve tries `basis` (Solution::basis of an earlier solve) before phase one, which it skips when the basis is
+    //still feasible, e.g. after set_objective_coeff, which picks the last basis up by itself. one that no longer fits
+    //just means solving from scratch. problems with soft or ranged rows or free vars are rewritten before solving, the
+    //basis is one of the rewritten problem then, which is what their Solution::basis holds
+    pub fn warm_start(mut self, basis: Vec<usize>) -> Self {
+        self.start = Some(basis);
+        self
+    }
+
+    pub fn maximize(mut self) -> Self {
+        self.sense = Sense::Maximize;
+        self
+    }
+
+    pub fn minimize(mut self) -> Self {
+        self.sense = Sense::Minimize;
+        self
+    }
+
+    //objective·x + objective_constant, what solve reports as the objective when x is the optimum
+    pub fn evaluate_objective(&self, x: &Array1<f64>) -> f64 {
+        evaluate_objective(&Array1::from(self.objective.clone()), x) + self.objective_constant
+    }
+
+    //structural variable count, the longest of the objective, the constraint rows and the bounds
+    pub fn num_vars(&self) -> usize {
+        self.constraints.iter().map(|con| con.coeffs.len()).fold(self.objective.len().max(self.bounds.len()), usize::max)
+    }
+
+    //same problem with every nondefault bound written out as a constraint row. a var with a negative lower bound
+    //is left free, with a >= row unless the bound is -inf, the others go back to the default x >= 0
+    pub fn bounds_as_constraints(&self) -> LpProblem {
+        let num_vars = self.num_vars();
+        let mut problem = self.clone();
+        problem.bounds.clear();
+        for (var, &(lo, hi)) in self.bounds.iter().enumerate() {
+            let mut unit = vec![0.0; num_vars];
+            unit[var] = 1.0;
+            if lo < 0.0 {
+                problem = problem.free(var);
+            }
+            if lo != 0.0 && lo.is_finite() {
+                problem = problem.add_constraint(unit.clone(), Relation::Ge, lo);
+            }
+            if hi.is_finite() {
+                problem = problem.add_constraint(unit, Relation::Le, hi);
+            }
+        }
+        problem
+    }
+
+    //same problem with every ranged row written out as a <= row followed by a >= row, a soft one as two soft rows
+    //with its penalty (only one side can be violated at a time)
+    pub fn ranges_as_constraints(&self) -> LpProblem {
+        let mut problem = LpProblem { constraints: Vec::new(), ..self.clone() };
+        for con in &self.constraints {
+            problem.constraints.push(Constraint { lower: None, ..con.clone() });
+            if let Some(lo) = con.lower {
+                problem.constraints.push(Constraint { relation: Relation::Ge, rhs: lo, lower: None, ..con.clone() });
+            }
+        }
+        problem
+    }
+
+    //c, a, b and the relations zero-padded to num_vars columns, ranged rows only keep their <= side
+    pub fn to_arrays(&self) -> (Array1<f64>, Array2<f64>, Array1<f64>, Vec<Relation>) {
+        let num_vars = self.num_vars();
+        let mut c = Array1::zeros(num_vars);
+        for (j, &coeff) in self.objective.iter().enumerate() {
+            c[j] = coeff;
+        }
+        let mut a = Array2::zeros((self.constraints.len(), num_vars));
+        for (i, con) in self.constraints.iter().enumerate() {
+            for (j, &coeff) in con.coeffs.iter().enumerate() {
+                a[[i, j]] = coeff;
+            }
+        }
+        let b = self.constraints.iter().map(|con| con.rhs).collect();
+        let relations = self.constraints.iter().map(|con| con.relation).collect();
+        (c, a, b, relations)
+    }
+
+    //rows that x meets with equality, ranged rows at either end, within eps relative to the rhs
+    pub(crate) fn binding_rows(&self, x: &[f64], eps: f64) -> Vec<usize> {
+        let tight = |lhs: f64, rhs: f64| (lhs - rhs).abs() <= eps * (1.0 + rhs.abs());
+        (0..self.constraints.len())
+            .filter(|&row| {
+                let con = &self.constraints[row];
+                let lhs: f64 = con.coeffs.iter().zip(x).map(|(coeff, val)| coeff * val).sum();
+                (con.relation == Relation::Eq && con.penalty.is_none()) || tight(lhs, con.rhs) || con.lower.is_some_and(|lo| tight(lhs, lo))
+            })
+            .collect()
+    }
+
+    pub fn solve(&self) -> Result<SolveResult, SolverError> {
+        self.solve_with(&SolverOptions::default())
+    }
+
+    //x holds the structural vars followed by a slack/surplus var for every <= and >= row
+    pub fn solve_with(&self, options: &SolverOptions) -> Result<SolveResult, SolverError> {
+        //a hard row with all-zero coeffs can only hold or not, one that can't makes the problem infeasible before any
+        //pivot. the ones that hold stay in the tableau, where their slack covers them, so the row and column fields of
+        //the solution keep the problem's layout. presolve is what drops them
+        let zero_row = |con: &Constraint| con.penalty.is_none() && con.coeffs.iter().all(|&coeff| coeff == 0.0);
+        if self.constraints.iter().any(|con| zero_row(con) && !con.holds_at_zero()) {
+            return Ok(SolveResult::Infeasible);
+        }
+        if self.constraints.iter().any(|con| con.penalty.is_some()) {
+            return self.solve_soft(options);
+        }
+        if self.constraints.iter().any(|con| con.lower.is_some()) {
+            return self.solve_ranged(options);
+        }
+        let split: Vec<usize> = (0..self.bounds.len()).filter(|&var| self.bounds[var].0 == f64::NEG_INFINITY).collect();
+        if !split.is_empty() {
+            return self.solve_split(&split, options);
+        }
+        let (c, a, b, relations) = self.to_arrays();
+        let result = match self.sense {
+            Sense::Maximize => bounded_from(c, &a, &b, &relations, &self.bounds, self.start.as_deref(), options)?,
+            Sense::Minimize => flip_objective(bounded_from(-c, &a, &b, &relations, &self.bounds, self.start.as_deref(), options)?),
+        };
+        match result {
+            SolveResult::Optimal(mut solution) => {
+                *self.last_basis.borrow_mut() = Some(solution.basis.clone());
+                solution.var_names = self.var_names.clone();
+                solution.objective += self.objective_constant;
+                Ok(SolveResult::Optimal(solution))
+            }
+            other => Ok(other),
+        }
+    }
+
+    //the problem solve_soft solves: every soft row made hard with its violation columns, and the (row, column of its
+    //violation var, its coeff in the row) of each of them
+    pub(crate) fn with_violation_columns(&self) -> (LpProblem, Vec<(usize, usize, f64)>) {
+        let num_vars = self.num_vars();
+        let mut problem = LpProblem { constraints: Vec::new(), ..self.clone() };
+        problem.objective.resize(num_vars, 0.0);
+        let mut columns = Vec::new(); //(row, column of its violation var, its coeff in the row)
+        for (row, con) in self.constraints.iter().enumerate() {
+            let mut con = Constraint { penalty: None, ..con.clone() };
+            con.coeffs.resize(num_vars, 0.0);
+            if let Some(penalty) = self.constraints[row].penalty {
+                let cost = match self.sense {
+                    Sense::Maximize => -penalty,
+                    Sense::Minimize => penalty,
+                };
+                let (under, over) = match (con.relation, con.lower) {
+                    (Relation::Le, None) => (false, true),
+                    (Relation::Ge, _) => (true, false),
+                    _ => (true, true),
+                };
+                for (sign, used) in [(1.0, under), (-1.0, over)] {
+                    if used {
+                        columns.push((row, num_vars + columns.len(), sign));
+                        problem.objective.push(cost);
+                        con.coeffs.resize(num_vars + columns.len(), 0.0);
+                        con.coeffs[num_vars + columns.len() - 1] = sign;
+                    }
+                }
+            }
+            problem.constraints.push(con);
+        }
+        for con in &mut problem.constraints {
+            con.coeffs.resize(num_vars + columns.len(), 0.0);
+        }
+        (problem, columns)
+    }
+
+    //every soft row gets violation columns after the structural ones, an over one for <=, an under one for >= and
+    //both for = and ranged rows, each costing its penalty. x and binding_constraints are put back in terms of this
+    //problem, so a violated row's slack goes negative, with the violations in soft_violations. the objective includes
+    //the penalties paid. the tableau and the per-column fields stay those of the rewritten problem, see Rewrite
+    fn solve_soft(&self, options: &SolverOptions) -> Result<SolveResult, SolverError> {
+        let num_vars = self.num_vars();
+        let (problem, violation_columns) = self.with_violation_columns();
+
+        match problem.solve_with(options)? {
+            SolveResult::Optimal(solution) => {
+                let mut columns: Vec<Vec<(usize, f64)>> = (0..num_vars).map(|j| vec![(j, 1.0)]).collect();
+                let mut violations = vec![Vec::new(); self.constraints.len()];
+                for (row, con) in self.constraints.iter().enumerate() {
+                    let own = violation_columns.iter().filter(|&&(of, _, _)| of == row);
+                    violations[row] = own.clone().map(|&(_, col, _)| (col, 1.0)).collect();
+                    if con.relation != Relation::Eq {
+                        //the slack of the row as given is the rewritten row's slack, which sits past the violation
+                        //columns, plus what the violation vars covered
+                        let slack = columns.len() + violation_columns.len();
+                        let side = if con.relation == Relation::Le { 1.0 } else { -1.0 };
+                        columns.push(core::iter::once((slack, 1.0)).chain(own.map(|&(_, col, sign)| (col, side * sign))).collect());
+                    }
+                }
+                let mut solution = solution.rewritten_as(num_vars, columns, violations);
+                solution.binding_constraints = self.binding_rows(&solution.x.as_slice().unwrap()[..num_vars], options.epsilon);
+                Ok(SolveResult::Optimal(Box::new(solution)))
+            }
+            other => Ok(other),
+        }
+    }
+
+    //every ranged row becomes coeffs·x + r = hi with r in [0, hi - lo], the r columns go after the structural ones
+    //and their values are put back where the row's slack would be. the tableau and the per-column fields of the
+    //solution stay those of the rewritten problem, see Rewrite
+    fn solve_ranged(&self, options: &SolverOptions) -> Result<SolveResult, SolverError> {
+        if self.constraints.iter().any(|con| con.lower.is_some_and(|lo| lo > con.rhs)) {
+            return Ok(SolveResult::Infeasible);
+        }
+        let num_vars = self.num_vars();
+        let ranged: Vec<usize> = (0..self.constraints.len()).filter(|&row| self.constraints[row].lower.is_some()).collect();
+        let mut problem = LpProblem { constraints: Vec::new(), ..self.clone() };
+        for (row, con) in self.constraints.iter().enumerate() {
+            let mut coeffs = con.coeffs.clone();
+            coeffs.resize(num_vars + ranged.len(), 0.0);
+            match con.lower {
+                Some(lo) => {
+                    let k = ranged.iter().position(|&r| r == row).unwrap();
+                    coeffs[num_vars + k] = 1.0;
+                    problem = problem.add_constraint(coeffs, Relation::Eq, con.rhs).bound(num_vars + k, 0.0, con.rhs - lo);
+                }
+                None => problem = problem.add_constraint(coeffs, con.relation, con.rhs),
+            }
+        }
+
+        match problem.solve_with(options)? {
+            SolveResult::Optimal(solution) => {
+                let mut columns: Vec<Vec<(usize, f64)>> = (0..num_vars).map(|j| vec![(j, 1.0)]).collect();
+                let mut slacks = num_vars + ranged.len()..;
+                for (row, con) in self.constraints.iter().enumerate() {
+                    match con.lower {
+                        Some(_) => columns.push(vec![(num_vars + ranged.iter().position(|&r| r == row).unwrap(), 1.0)]),
+                        None if con.relation != Relation::Eq => columns.push(vec![(slacks.next().unwrap(), 1.0)]),
+                        None => {}
+                    }
+                }
+                let mut solution = solution.rewritten_as(num_vars, columns, Vec::new());
+                solution.binding_constraints = self.binding_rows(&solution.x.as_slice().unwrap()[..num_vars], options.epsilon);
+                Ok(SolveResult::Optimal(Box::new(solution)))
+            }
+            other => Ok(other),
+        }
+    }
+
+    //every x_j without a lower bound is solved as x⁺ - x⁻ with x⁺ in [0, max(hi, 0)] and x⁻ in [max(-hi, 0), inf)
+    //the x⁻ columns go after the other structural ones and are folded back into x, the tableau and the
+    //per-column fields of the solution stay those of the split problem, see Rewrite
+    fn solve_split(&self, split: &[usize], options: &SolverOptions) -> Result<SolveResult, SolverError> {
+        let num_vars = self.num_vars();
+        match self.split_free(split).solve_with(options)? {
+            SolveResult::Optimal(solution) => {
+                let columns = (0..solution.x.len() - split.len())
+                    .map(|j| match split.iter().position(|&var| var == j) {
+                        Some(k) => vec![(j, 1.0), (num_vars + k, -1.0)],
+                        None if j < num_vars => vec![(j, 1.0)],
+                        None => vec![(j + split.len(), 1.0)],
+                    })
+                    .collect();
+                Ok(SolveResult::Optimal(Box::new(solution.rewritten_as(num_vars, columns, Vec::new()))))
+            }
+            other => Ok(other),
+        }
+    }
+
+    //the problem solve_split solves, with an x⁻ column appended for every var in split
+    pub(crate) fn split_free(&self, split: &[usize]) -> LpProblem {
+        let num_vars = self.num_vars();
+        let mut problem = self.clone();
+        problem.objective.resize(num_vars, 0.0);
+        problem.bounds.resize(num_vars, (0.0, f64::INFINITY));
+        for con in &mut problem.constraints {
+            con.coeffs.resize(num_vars, 0.0);
+        }
+        for &var in split {
+            let hi = self.bounds[var].1;
+            problem.bounds[var] = (0.0, hi.max(0.0));
+            problem.bounds.push(((-hi).max(0.0), f64::INFINITY));
+            problem.objective.push(-problem.objective[var]);
+            for con in &mut problem.constraints {
+                con.coeffs.push(-con.coeffs[var]);
+            }
+        }
+        problem
+    }
+}
+
+//everything in one call: c·x is maximized for Sense::Maximize and minimized for Sense::Minimize, the reported
+//objective is c·x at the optimum either way (never -c·x), and lo <= x_j <= hi for every entry of bounds
+//(missing ones are (0, inf), lo may be -inf). rows with a negative rhs, >= and = rows all go through phase one,
+//x holds the structural vars followed by a slack/surplus var for every <= and >= row. an a without rows is fine,
+//then the bounds alone decide: each x_j goes to whichever bound its cost favors, Unbounded if that one is infinite
+pub fn solve(
+    c: &Array1<f64>,
+    a: &Array2<f64>,
+    b: &Array1<f64>,
+    relations: &[Relation],
+    bounds: &[(f64, f64)],
+    sense: Sense,
+    options: &SolverOptions,
+) -> Result<SolveResult, SolverError> {
+    check_dimensions(c, a, b, Some(relations))?;
+    if bounds.len() > a.ncols() {
+        return Err(SolverError::DimensionMismatch { expected: a.ncols(), found: bounds.len() });
+    }
+    let mut problem = LpProblem { objective: c.to_vec(), sense, ..LpProblem::new() };
+    for (row, (&relation, &rhs)) in a.rows().into_iter().zip(relations.iter().zip(b)) {
+        problem = problem.add_constraint(row.to_vec(), relation, rhs);
+    }
+    for (var, &(lo, hi)) in bounds.iter().enumerate() {
+        problem = problem.bound(var, lo, hi);
+    }
+    problem.solve_with(options)
+}
+
+//the minimum and the maximum of the problem's objective over its constraints, in that order, whatever its sense.
+//the max is solved first and the min starts from its basis, which is feasible for both, so phase one runs once. soft,
+//ranged and free problems get rewritten the same way for both, so their basis carries over too. an infeasible problem
+//is infeasible both ways without a second solve
+pub fn solve_range(problem: &LpProblem, options: &SolverOptions) -> Result<(SolveResult, SolveResult), SolverError> {
+    let max = problem.clone().maximize().solve_with(options)?;
+    let min = problem.clone().minimize();
+    let min = match &max {
+        SolveResult::Optimal(solution) => min.warm_start(solution.basis.clone()).solve_with(options)?,
+        SolveResult::Infeasible => SolveResult::Infeasible,
+        _ => min.solve_with(options)?,
+    };
+    Ok((min, max))
+}
+
+//dual LP: a max problem turns into min b·y with Aᵀy >= c, a min problem into max b·y with Aᵀy <= c
+//y_i is >= 0 for rows pointing the "right" way (<= in a max, >= in a min), <= 0 for the others and free for = rows
+//since every variable here is >= 0, a <= 0 y_i is stored negated and a free one as two columns y_i⁺, y_i⁻ (in that order)
+//a ranged row counts as its <= and >= rows, bounds count as extra primal rows and get their own dual columns after the constraint ones.
+//a free primal var (or one with a negative lower bound, which gets left free) gives an = dual row, soft rows are
+//made hard with their violation columns first, which become primal vars like the others
+pub fn dual(problem: &LpProblem) -> LpProblem {
+    let problem = &problem.with_violation_columns().0.ranges_as_constraints().bounds_as_constraints();
+    let (c, a, b, relations) = problem.to_arrays();
+    let (dual_sense, dual_relation, natural) = match problem.sense {
+        Sense::Maximize => (Sense::Minimize, Relation::Ge, Relation::Le),
+        Sense::Minimize => (Sense::Maximize, Relation::Le, Relation::Ge),
+    };
+
+    //each primal row becomes one or two dual columns with a sign
+    let mut columns = Vec::new();
+    for (i, &rel) in relations.iter().enumerate() {
+        match rel {
+            Relation::Eq => {
+                columns.push((i, 1.0));
+                columns.push((i, -1.0));
+            }
+            rel if rel == natural => columns.push((i, 1.0)),
+            _ => columns.push((i, -1.0)),
+        }
+    }
+
+    let objective = columns.iter().map(|&(i, sign)| sign * b[i]).collect();
+    //strong duality: both optima are the same value, so the constant carries over
+    let mut dual_problem = LpProblem::new().objective(objective).objective_constant(problem.objective_constant);
+    dual_problem.sense = dual_sense;
+    for j in 0..c.len() {
+        let coeffs = columns.iter().map(|&(i, sign)| sign * a[[i, j]]).collect();
+        let free = problem.bounds.get(j).is_some_and(|&(lo, _)| lo == f64::NEG_INFINITY);
+        dual_problem = dual_problem.add_constraint(coeffs, if free { Relation::Eq } else { dual_relation }, c[j]);
+    }
+    dual_problem
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensitivity::{complementary_slackness_report, dual_values, reduced_costs};
+    use crate::simplex::Solution;
+    use crate::test_support::{assert_objective_close, assert_solution_close, expect_optimal, wyndor};
+
+    #[test]
+    fn satisfiable_zero_rows_keep_the_optimum_and_the_layout() {
+        let mut problem = wyndor();
+        problem.constraints.insert(0, Constraint { coeffs: vec![0.0, 0.0], relation: Relation::Le, rhs: 5.0, lower: None, penalty: None });
+        let problem = problem
+            .add_constraint(vec![0.0, 0.0], Relation::Ge, -3.0)
+            .add_constraint(vec![0.0, 0.0], Relation::Eq, 0.0)
+            .add_constraint(vec![0.0, 0.0], Relation::Ge, 0.0);
+        let solution = expect_optimal(problem.solve().unwrap());
+        assert_objective_close(36.0, solution.objective, 1e-9);
+        assert_eq!(solution.x.len(), 2 + 6); //a slack for every <= and >= row, zero ones included
+        assert_solution_close(&[2.0, 6.0], &solution.structural().to_owned(), 1e-9);
+        assert_eq!(solution.basis.len(), problem.constraints.len());
+
+        let duals = dual_values(&solution);
+        assert_eq!(duals.len(), problem.constraints.len());
+        assert_solution_close(&[0.0, 0.0, 1.5, 1.0, 0.0, 0.0, 0.0], &duals, 1e-9);
+        let reduced = reduced_costs(&solution);
+        assert_eq!(reduced.len(), solution.x.len());
+        assert!(reduced.iter().all(|&d| d.abs() < 1e3), "{:?}", reduced); //no rhs entry read as a reduced cost
+        let report = complementary_slackness_report(&SolveResult::Optimal(Box::new(solution.clone())), 1e-9).unwrap();
+        assert!(report.is_satisfied(), "{:?}", report);
+        assert!(solution.gomory_cut().is_none()); //integer vertex, and no panic on the zero rows' columns
+    }
+
+    #[test]
+    fn unsatisfiable_zero_rows_are_infeasible() {
+        for (relation, rhs) in [(Relation::Le, -3.0), (Relation::Ge, 2.0), (Relation::Eq, 1.0)] {
+            let problem = wyndor().add_constraint(vec![0.0, 0.0], relation, rhs);
+            assert_eq!(problem.solve().unwrap(), SolveResult::Infeasible, "0 {:?} {}", relation, rhs);
+        }
+        assert_eq!(wyndor().add_range(vec![0.0, 0.0], 1.0, 2.0).solve().unwrap(), SolveResult::Infeasible);
+        //a soft zero row only costs its penalty
+        let soft = expect_optimal(wyndor().add_soft_constraint(vec![0.0, 0.0], Relation::Ge, 2.0, 1.0).solve().unwrap());
+        assert_objective_close(34.0, soft.objective, 1e-9);
+    }
+
+    //min 2x1 + x2 st x1 + x2 >= 1, x2 - x1 <= 3 with x1 free, optimum 0 at (-1, 2)
+    fn free_min() -> LpProblem {
+        LpProblem::new()
+            .objective(vec![2.0, 1.0])
+            .add_constraint(vec![1.0, 1.0], Relation::Ge, 1.0)
+            .add_constraint(vec![-1.0, 1.0], Relation::Le, 3.0)
+            .free(0)
+            .minimize()
+    }
+
+    #[test]
+    fn free_var_ends_negative() {
+        let solution = expect_optimal(free_min().solve().unwrap());
+        assert_solution_close(&[-1.0, 2.0], &solution.structural().to_owned(), 1e-9);
+        assert_objective_close(0.0, solution.objective, 1e-9);
+    }
+
+    #[test]
+    fn dual_of_a_free_var_is_an_equality_row() {
+        let dual_problem = dual(&free_min());
+        assert!(dual_problem.objective.iter().all(|coeff| coeff.is_finite()), "{:?}", dual_problem.objective);
+        assert_eq!(dual_problem.constraints[0].relation, Relation::Eq);
+        assert_eq!(dual_problem.constraints[1].relation, Relation::Le);
+        let dual_solution = expect_optimal(dual_problem.solve().unwrap());
+        assert_objective_close(0.0, dual_solution.objective, 1e-9);
+
+        //a negative lower bound leaves the var free with a >= row in the dual's primal
+        let bounded = free_min().bound(0, -0.5, f64::INFINITY);
+        let written = bounded.bounds_as_constraints();
+        assert_eq!(written.bounds[0], (f64::NEG_INFINITY, f64::INFINITY));
+        assert_eq!(written.constraints.len(), 3);
+        let primal = expect_optimal(bounded.solve().unwrap());
+        assert_objective_close(0.5, primal.objective, 1e-9);
+        assert_objective_close(primal.objective, expect_optimal(written.solve().unwrap()).objective, 1e-9);
+        assert_objective_close(primal.objective, expect_optimal(dual(&bounded).solve().unwrap()).objective, 1e-9);
+    }
+
+    #[test]
+    fn dual_keeps_soft_penalties() {
+        let problem = wyndor().add_soft_constraint(vec![1.0, 1.0], Relation::Ge, 9.0, 2.0).add_range(vec![1.0, -1.0], -3.0, 1.0);
+        let primal = expect_optimal(problem.solve().unwrap());
+        assert!(primal.soft_violations[3] > 0.5);
+        assert_objective_close(primal.objective, expect_optimal(dual(&problem).solve().unwrap()).objective, 1e-9);
+        let split = problem.ranges_as_constraints();
+        assert_eq!(split.constraints.len(), 6);
+        assert_objective_close(primal.objective, expect_optimal(split.solve().unwrap()).objective, 1e-9);
+    }
+
+    #[test]
+    fn dual_transposes_and_flips() {
+        let dual_problem = dual(&wyndor());
+        assert_eq!(dual_problem.sense, Sense::Minimize);
+        assert_eq!(dual_problem.objective, vec![4.0, 12.0, 18.0]);
+        let rows: Vec<_> = dual_problem.constraints.iter().map(|con| (con.coeffs.clone(), con.relation, con.rhs)).collect();
+        assert_eq!(rows, vec![(vec![1.0, 0.0, 3.0], Relation::Ge, 3.0), (vec![0.0, 2.0, 2.0], Relation::Ge, 5.0)]);
+        let y = expect_optimal(dual_problem.solve().unwrap());
+        assert_solution_close(&[0.0, 1.5, 1.0], &y.structural().to_owned(), 1e-9);
+    }
+
+    #[test]
+    fn strong_duality_on_random_problems() {
+        for seed in 0..40 {
+            let (problem, _) = crate::test_support::random_feasible_lp(seed, 6, 5);
+            let problem = if seed % 3 == 0 { LpProblem { objective: problem.objective.iter().map(|c| -c).collect(), ..problem }.minimize() } else { problem };
+            let primal = expect_optimal(problem.solve().unwrap());
+            let dual_solution = expect_optimal(dual(&problem).solve().unwrap());
+            assert_objective_close(primal.objective, dual_solution.objective, 1e-7);
+        }
+    }
+
+    #[test]
+    fn solve_takes_le_rows() {
+        let solution = expect_optimal(
+            solve(&ndarray::array![3.0, 5.0], &ndarray::array![[1.0, 0.0], [0.0, 2.0], [3.0, 2.0]], &ndarray::array![4.0, 12.0, 18.0], &[Relation::Le; 3], &[], Sense::Maximize, &SolverOptions::default())
+                .unwrap(),
+        );
+        assert_objective_close(36.0, solution.objective, 1e-9);
+        //two structurals then a slack per row
+        assert_eq!(solution.x.len(), 5);
+        assert_solution_close(&[2.0, 6.0], &solution.structural().to_owned(), 1e-9);
+    }
+
+    #[test]
+    fn solve_takes_ge_rows_and_reports_the_minimum_itself() {
+        //min 2x1 + 3x2 st x1 + x2 >= 4, x1 + 3x2 >= 6, optimum 9 at (3, 1)
+        let solution = expect_optimal(
+            solve(&ndarray::array![2.0, 3.0], &ndarray::array![[1.0, 1.0], [1.0, 3.0]], &ndarray::array![4.0, 6.0], &[Relation::Ge; 2], &[], Sense::Minimize, &SolverOptions::default()).unwrap(),
+        );
+        assert_objective_close(9.0, solution.objective, 1e-9);
+        assert_solution_close(&[3.0, 1.0], &solution.structural().to_owned(), 1e-9);
+    }
+
+    #[test]
+    fn solve_takes_eq_rows_and_bounds() {
+        //max x1 + 2x2 st x1 + x2 = 5, x2 <= 3 as a bound, optimum 8 at (2, 3)
+        let solution = expect_optimal(
+            solve(&ndarray::array![1.0, 2.0], &ndarray::array![[1.0, 1.0]], &ndarray::array![5.0], &[Relation::Eq], &[(0.0, f64::INFINITY), (0.0, 3.0)], Sense::Maximize, &SolverOptions::default())
+                .unwrap(),
+        );
+        assert_objective_close(8.0, solution.objective, 1e-9);
+        assert_solution_close(&[2.0, 3.0], &solution.structural().to_owned(), 1e-9);
+    }
+
+    #[test]
+    fn solve_without_rows_goes_to_the_favored_bounds() {
+        let a = Array2::zeros((0, 2));
+        let b = Array1::zeros(0);
+        let solution = expect_optimal(solve(&ndarray::array![1.0, -1.0], &a, &b, &[], &[(-1.0, 2.0), (-3.0, 4.0)], Sense::Maximize, &SolverOptions::default()).unwrap());
+        assert_objective_close(5.0, solution.objective, 1e-9);
+        assert_solution_close(&[2.0, -3.0], &solution.structural().to_owned(), 1e-9);
+        let result = solve(&ndarray::array![1.0, -1.0], &a, &b, &[], &[(-1.0, f64::INFINITY)], Sense::Maximize, &SolverOptions::default()).unwrap();
+        assert!(matches!(result, SolveResult::Unbounded), "{:?}", result);
+    }
+
+    #[test]
+    fn crossed_bounds_are_infeasible() {
+        let crossed = LpProblem::new().objective(vec![1.0]).add_constraint(vec![1.0], Relation::Le, 10.0).bound(0, 3.0, 1.0).maximize();
+        assert_eq!(crossed.solve().unwrap(), SolveResult::Infeasible);
+        assert_eq!(crossed.clone().minimize().solve().unwrap(), SolveResult::Infeasible);
+        //lo = hi still fixes the var
+        let fixed = expect_optimal(crossed.bound(0, 3.0, 3.0).solve().unwrap());
+        assert_objective_close(3.0, fixed.objective, 1e-9);
+    }
+
+    #[test]
+    fn solve_rejects_more_bounds_than_vars() {
+        let result = solve(&ndarray::array![1.0], &ndarray::array![[1.0]], &ndarray::array![1.0], &[Relation::Le], &[(0.0, 1.0), (0.0, 1.0)], Sense::Maximize, &SolverOptions::default());
+        assert!(matches!(result, Err(SolverError::DimensionMismatch { expected: 1, found: 2 })), "{:?}", result);
+    }
+
+    #[test]
+    fn objective_constant_shifts_the_value_not_the_point() {
+        let plain = expect_optimal(wyndor().solve().unwrap());
+        let shifted = expect_optimal(wyndor().objective_constant(10.0).solve().unwrap());
+        assert_objective_close(46.0, shifted.objective, 1e-9);
+        assert_eq!(plain.x, shifted.x);
+        //a minimization reports it the same way, c·x + c0
+        let min = expect_optimal(wyndor().minimize().add_constraint(vec![1.0, 1.0], Relation::Ge, 2.0).objective_constant(-4.0).solve().unwrap());
+        assert_objective_close(6.0 - 4.0, min.objective, 1e-9);
+        assert_solution_close(&[2.0, 0.0], &min.structural().to_owned(), 1e-9);
+    }
+
+    #[test]
+    fn range_active_at_its_upper_end() {
+        //1 <= x1 + x2 <= 7 cuts (2, 6) off, the optimum moves to (1, 6) with the row at 7
+        let ranged = expect_optimal(wyndor().add_range(vec![1.0, 1.0], 1.0, 7.0).solve().unwrap());
+        assert_objective_close(33.0, ranged.objective, 1e-9);
+        assert_solution_close(&[1.0, 6.0], &ranged.structural().to_owned(), 1e-9);
+        //one slack column per row, the range's one is 0 at the upper end
+        assert_eq!(ranged.x.len(), 2 + 4);
+        assert!(ranged.x[5].abs() < 1e-9);
+        let two_rows = wyndor().add_constraint(vec![1.0, 1.0], Relation::Le, 7.0).add_constraint(vec![1.0, 1.0], Relation::Ge, 1.0);
+        assert_objective_close(expect_optimal(two_rows.solve().unwrap()).objective, ranged.objective, 1e-9);
+    }
+
+    #[test]
+    fn range_active_at_its_lower_end() {
+        let ranged = expect_optimal(wyndor().minimize().add_range(vec![1.0, 1.0], 1.0, 7.0).solve().unwrap());
+        assert_objective_close(3.0, ranged.objective, 1e-9);
+        assert_solution_close(&[1.0, 0.0], &ranged.structural().to_owned(), 1e-9);
+        assert!((ranged.x[5] - 6.0).abs() < 1e-9, "the slack is hi - lo at the lower end, {:?}", ranged.x);
+    }
+
+    #[test]
+    fn evaluate_objective_matches_the_reported_optimum() {
+        for problem in [wyndor(), wyndor().objective_constant(-2.5), wyndor().minimize().add_constraint(vec![1.0, 1.0], Relation::Ge, 2.0)] {
+            let solution = expect_optimal(problem.solve().unwrap());
+            //x with its slacks or without them, the slacks don't count
+            assert_objective_close(solution.objective, problem.evaluate_objective(&solution.x), 1e-9);
+            assert_objective_close(solution.objective, problem.evaluate_objective(&solution.structural().to_owned()), 1e-9);
+        }
+        //any point, not only the optimum
+        assert_eq!(wyndor().objective_constant(1.0).evaluate_objective(&ndarray::array![1.0, 1.0]), 9.0);
+        assert_eq!(crate::simplex::evaluate_objective(&ndarray::array![3.0, 5.0], &ndarray::array![1.0, 1.0, 7.0]), 8.0);
+    }
+
+    #[test]
+    fn forbidden_var_stays_out_of_the_basis() {
+        //x2 is the one Dantzig would enter first, forbidden the optimum falls back to 12 at (4, 0)
+        let options = SolverOptions { record_log: true, ..SolverOptions::default() };
+        let solution = expect_optimal(wyndor().forbid(1).solve_with(&options).unwrap());
+        assert_objective_close(12.0, solution.objective, 1e-9);
+        assert_eq!(solution.x.len(), 2 + 3);
+        assert_solution_close(&[4.0, 0.0], &solution.structural().to_owned(), 1e-9);
+        assert!(!solution.log.is_empty() && solution.log.iter().all(|entry| entry.entering != 1), "{:?}", solution.log);
+    }
+
+    #[test]
+    fn a_free_var_can_make_the_objective_unbounded() {
+        //min x1 + x2 st x1 + x2 <= 5 with x1 free: x1 goes to -inf and the <= row never stops it
+        let problem = LpProblem::new().objective(vec![1.0, 1.0]).add_constraint(vec![1.0, 1.0], Relation::Le, 5.0).free(0).minimize();
+        assert_eq!(problem.solve().unwrap(), SolveResult::Unbounded);
+        //an upper bound alone doesn't stop it going down either, a >= row does
+        assert_eq!(problem.clone().bound(0, f64::NEG_INFINITY, 2.0).solve().unwrap(), SolveResult::Unbounded);
+        let held = expect_optimal(problem.add_constraint(vec![1.0, 0.0], Relation::Ge, -3.0).solve().unwrap());
+        assert_objective_close(-3.0, held.objective, 1e-9);
+        assert_solution_close(&[-3.0, 0.0], &held.structural().to_owned(), 1e-9);
+    }
+
+    #[test]
+    fn a_tweaked_coeff_reoptimizes_from_the_old_basis() {
+        let first = expect_optimal(wyndor().solve().unwrap());
+        //c1 = 8 makes (4, 3) the better vertex, one pivot away from (2, 6)
+        let tweaked = wyndor().warm_start(first.basis.clone()).set_objective_coeff(0, 8.0);
+        let warm = expect_optimal(tweaked.solve().unwrap());
+        //a solved problem remembers its basis, the setter hands it to the next solve
+        let problem = wyndor();
+        problem.solve().unwrap();
+        let resolved = expect_optimal(problem.set_objective_coeff(0, 8.0).solve().unwrap());
+        assert_eq!(resolved, Solution { solve_time: resolved.solve_time, ..warm.clone() });
+        let cold = expect_optimal(wyndor().set_objective_coeff(0, 8.0).solve().unwrap());
+        assert_objective_close(47.0, warm.objective, 1e-9);
+        assert_solution_close(&[4.0, 3.0], &warm.structural().to_owned(), 1e-9);
+        assert_eq!(warm.iterations, 1);
+        assert!(warm.iterations < cold.iterations);
+        assert!(resolved.iterations < cold.iterations, "{} resolved against {} cold", resolved.iterations, cold.iterations);
+
+        //x1 + 2x2 <= 18 in place of 3x1 + 2x2 <= 18 leaves the old basis infeasible, that's a cold solve then: 42 at (4, 6)
+        let reshaped = expect_optimal(wyndor().warm_start(first.basis).set_constraint_coeff(2, 0, 1.0).solve().unwrap());
+        assert_objective_close(42.0, reshaped.objective, 1e-9);
+        assert_solution_close(&[4.0, 6.0], &reshaped.structural().to_owned(), 1e-9);
+        //an unknown row changes nothing
+        assert_eq!(wyndor().set_constraint_coeff(9, 0, 1.0), wyndor());
+    }
+
+    #[test]
+    fn solve_range_brackets_the_objective() {
+        for seed in 0..20 {
+            let (problem, point) = crate::test_support::random_feasible_lp(seed, 6, 5);
+            let (min, max) = solve_range(&problem, &SolverOptions::default()).unwrap();
+            let (min, max) = (expect_optimal(min), expect_optimal(max));
+            assert!(min.objective <= max.objective + 1e-9, "seed {}: {} > {}", seed, min.objective, max.objective);
+            for solution in [&min, &max] {
+                assert!(crate::verify::verify_solution(&problem, &solution.x, 1e-7).is_ok(), "seed {}: {:?}", seed, solution.x);
+            }
+            //the generating point is feasible, so it lies between the two
+            let at_point = problem.evaluate_objective(&point);
+            assert!(min.objective <= at_point + 1e-7 && at_point <= max.objective + 1e-7, "seed {}", seed);
+            assert_objective_close(expect_optimal(problem.clone().minimize().solve().unwrap()).objective, min.objective, 1e-7);
+        }
+        let infeasible = wyndor().add_constraint(vec![1.0, 1.0], Relation::Ge, 20.0);
+        assert_eq!(solve_range(&infeasible, &SolverOptions::default()).unwrap(), (SolveResult::Infeasible, SolveResult::Infeasible));
+
+        //a >= row needs phase one: the cold min pivots its artificial out, the min of solve_range starts feasible
+        //and never does, whether the problem gets rewritten or not
+        let logged = SolverOptions { record_log: true, ..SolverOptions::default() };
+        let artificial_pivots = |solution: &Solution| {
+            let first_artificial = solution.rewrite.as_ref().map_or(solution.x.len(), |rewrite| rewrite.x.len());
+            solution.log.iter().filter(|step| step.leaving.is_some_and(|col| col >= first_artificial)).count()
+        };
+        let base = wyndor().add_constraint(vec![3.0, 2.0], Relation::Ge, 2.0);
+        let cases = [
+            ("plain", base.clone()),
+            ("ranged", base.clone().add_range(vec![1.0, 1.0], 1.0, 9.0)),
+            ("soft", base.clone().add_soft_constraint(vec![1.0, 1.0], Relation::Ge, 20.0, 0.5)),
+            ("free", base.clone().free(0).add_constraint(vec![-1.0, 0.0], Relation::Le, 3.0)),
+        ];
+        for (name, problem) in cases {
+            let (min, _) = solve_range(&problem, &logged).unwrap();
+            let (min, cold) = (expect_optimal(min), expect_optimal(problem.clone().minimize().solve_with(&logged).unwrap()));
+            assert_objective_close(cold.objective, min.objective, 1e-9);
+            assert!(artificial_pivots(&cold) > 0, "{}: {:?}", name, cold.log);
+            assert_eq!(artificial_pivots(&min), 0, "{}: {:?}", name, min.log);
+        }
+    }
+
+    #[test]
+    fn structural_and_slack_parts_follow_the_problem_layout() {
+        //3 vars under a <=, a >= and an = row: 3 structurals, a slack and a surplus
+        let problem = LpProblem::new()
+            .objective(vec![1.0, 2.0, 1.0])
+            .add_constraint(vec![1.0, 1.0, 1.0], Relation::Le, 6.0)
+            .add_constraint(vec![1.0, 0.0, 0.0], Relation::Ge, 1.0)
+            .add_constraint(vec![0.0, 1.0, -1.0], Relation::Eq, 1.0)
+            .maximize();
+        let solution = expect_optimal(problem.solve().unwrap());
+        assert_eq!(solution.structural().len(), problem.num_vars());
+        assert_eq!(solution.slacks().len(), problem.constraints.iter().filter(|con| con.relation != Relation::Eq).count());
+        let (x1, x2, x3) = (solution.structural()[0], solution.structural()[1], solution.structural()[2]);
+        assert_solution_close(&[6.0 - x1 - x2 - x3, x1 - 1.0], &solution.slacks().to_owned(), 1e-9);
+        let joined: Vec<f64> = solution.structural().iter().chain(solution.slacks().iter()).copied().collect();
+        assert_eq!(joined, solution.x.to_vec());
+        //a free var is still one structural column, its x⁺ - x⁻ split is folded back
+        let free = expect_optimal(problem.free(2).solve().unwrap());
+        assert_eq!((free.structural().len(), free.slacks().len()), (3, 2));
+    }
+
+    #[test]
+    fn removing_a_binding_row_improves_the_optimum() {
+        let first = expect_optimal(wyndor().solve().unwrap());
+        //without 3x1 + 2x2 <= 18 the optimum moves to (4, 6) for 42, resuming from the old vertex
+        let relaxed = wyndor().warm_start(first.basis.clone()).remove_constraint(2);
+        assert_eq!(relaxed.constraints.len(), 2);
+        let warm = expect_optimal(relaxed.solve().unwrap());
+        let cold = expect_optimal(wyndor().remove_constraint(2).solve().unwrap());
+        assert_objective_close(42.0, warm.objective, 1e-9);
+        assert_solution_close(&[4.0, 6.0], &warm.structural().to_owned(), 1e-9);
+        assert_eq!(warm.x.len(), 2 + 2);
+        assert!(warm.iterations < cold.iterations, "{} warm against {} cold", warm.iterations, cold.iterations);
+        //x1 <= 4 has slack at (2, 6), taking it away changes nothing and needs no pivot
+        let slack_row = expect_optimal(wyndor().warm_start(first.basis).remove_constraint(0).solve().unwrap());
+        assert_objective_close(36.0, slack_row.objective, 1e-9);
+        assert_eq!(slack_row.iterations, 0);
+    }
+
+    #[test]
+    fn removing_an_unknown_row_changes_nothing() {
+        let first = expect_optimal(wyndor().solve().unwrap());
+        assert_eq!(wyndor().remove_constraint(9), wyndor());
+        let warm = wyndor().warm_start(first.basis.clone());
+        assert_eq!(warm.clone().remove_constraint(3), warm);
+    }
+
+    #[test]
+    fn soft_rows_are_traded_off_by_their_penalties() {
+        //x1 >= 6 and x2 >= 6 can't both hold under x1 + x2 <= 10, max x1 + x2 falls short on the cheaper one
+        let problem = |first: f64, second: f64| {
+            LpProblem::new()
+                .objective(vec![1.0, 1.0])
+                .add_constraint(vec![1.0, 1.0], Relation::Le, 10.0)
+                .add_soft_constraint(vec![1.0, 0.0], Relation::Ge, 6.0, first)
+                .add_soft_constraint(vec![0.0, 1.0], Relation::Ge, 6.0, second)
+                .maximize()
+        };
+        let solution = expect_optimal(problem(3.0, 1.0).solve().unwrap());
+        assert_solution_close(&[6.0, 4.0], &solution.structural().to_owned(), 1e-9);
+        assert_solution_close(&[0.0, 0.0, 2.0], &Array1::from(solution.soft_violations.clone()), 1e-9);
+        assert_objective_close(10.0 - 2.0, solution.objective, 1e-9);
+        let swapped = expect_optimal(problem(1.0, 3.0).solve().unwrap());
+        assert_solution_close(&[4.0, 6.0], &swapped.structural().to_owned(), 1e-9);
+        assert_solution_close(&[0.0, 2.0, 0.0], &Array1::from(swapped.soft_violations.clone()), 1e-9);
+        //a hard x1 >= 6 in place of the soft one is what the heavy penalty buys
+        let hard = expect_optimal(problem(3.0, 1.0).add_constraint(vec![1.0, 0.0], Relation::Ge, 6.0).solve().unwrap());
+        assert_objective_close(solution.objective, hard.objective, 1e-9);
+    }
+}
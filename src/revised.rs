@@ -0,0 +1,615 @@
+use ndarray::prelude::*;
+use core::cmp::Ordering;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::operator::ConstraintOperator;
+use crate::scalar::Scalar;
+use crate::simplex::{
+    binding_rows, check_dimensions, choose_entering, degenerate_columns, dot, epsilon, find_pivot_row, row_signs, standard_tableau, update_devex_weights,
+    zero_reduced_costs, ColumnBounds, LoopExit, PivotRule, Relation, Selection, Sense, Solution, SolveResult, SolverError,
+    SolverOptions, Step, Timer, Trace,
+};
+
+//constraint part of the standard tableau, the columns never change, only B⁻¹ does
+//the structural columns are A's (with the negated rows flipped back) and any generated ones, the slack/surplus/artificial ones are stored
+struct StandardForm<'a, T, A: ?Sized> {
+    operator: &'a A,
+    signs: Array1<T>,           //-1 for rows negated to get a nonnegative rhs
+    generated: Vec<Array1<T>>,  //columns added by the pricing callback, signs already applied, they follow A's columns
+    extra: Array2<T>, //row k is column num_structural + k of [structural | slack/surplus | artificial], so pricing reads contiguous memory
+    rhs: Array1<T>,
+}
+
+impl<T: Scalar, A: ConstraintOperator<T> + ?Sized> StandardForm<'_, T, A> {
+    fn num_structural(&self) -> usize {
+        self.operator.ncols() + self.generated.len()
+    }
+
+    fn num_cols(&self) -> usize {
+        self.num_structural() + self.extra.nrows()
+    }
+
+    fn column(&self, j: usize) -> Array1<T> {
+        let num_vars = self.operator.ncols();
+        match (j.checked_sub(num_vars), j.checked_sub(self.num_structural())) {
+            (None, _) => self.operator.column(j) * &self.signs,
+            (Some(k), None) => self.generated[k].clone(),
+            (_, Some(k)) => self.extra.row(k).to_owned(),
+        }
+    }
+
+    //y·A_j of every column, the ones of A with a single Aᵀ product
+    fn price(&self, y: ArrayView1<T>) -> Array1<T> {
+        let structural = self.operator.apply_transpose((&y * &self.signs).view());
+        let generated = self.generated.iter().map(|col| dot(col.view(), y));
+        structural.into_iter().chain(generated).chain(self.extra.rows().into_iter().map(|col| dot(col, y))).collect()
+    }
+}
+
+//revised simplex for the same problems as simplex_solver_two_phase, only B⁻¹ (m×m) is updated per pivot
+//reduced costs and the entering column come from products with the original columns, which never get rewritten
+//the tableau in the returned solution is built once at the end so the sensitivity functions work on it, there is no history
+pub fn revised_simplex<T: Scalar>(
+    c: Array1<T>,
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    options: &SolverOptions,
+) -> Result<SolveResult<T>, SolverError> {
+    check_dimensions(&c, a, b, Some(relations))?;
+    revised_simplex_operator(c, a, b, relations, options)
+}
+
+//revised_simplex with A only available through A·x and Aᵀ·y
+pub fn revised_simplex_operator<T: Scalar, A: ConstraintOperator<T> + ?Sized>(
+    c: Array1<T>,
+    a: &A,
+    b: &Array1<T>,
+    relations: &[Relation],
+    options: &SolverOptions,
+) -> Result<SolveResult<T>, SolverError> {
+    revised_core(c, a, b, relations, options, None)
+}
+
+//column generation: every time the restricted problem is optimal, `pricing` gets the row duals (as dual_values
+//reports them) and may return a new column a_j with its cost c_j. it enters when c_j - y·a_j > 0, i.e. it has a
+//negative reduced cost, otherwise or on None the solve stops. generated columns go after a's in x
+pub fn revised_simplex_columns<T: Scalar>(
+    c: Array1<T>,
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    options: &SolverOptions,
+    mut pricing: impl FnMut(ArrayView1<T>) -> Option<(Array1<T>, T)>,
+) -> Result<SolveResult<T>, SolverError> {
+    check_dimensions(&c, a, b, Some(relations))?;
+    revised_core(c, a, b, relations, options, Some(&mut pricing))
+}
+
+type Pricing<'p, T> = &'p mut dyn FnMut(ArrayView1<T>) -> Option<(Array1<T>, T)>;
+
+fn revised_core<T: Scalar, A: ConstraintOperator<T> + ?Sized>(
+    mut c: Array1<T>,
+    a: &A,
+    b: &Array1<T>,
+    relations: &[Relation],
+    options: &SolverOptions,
+    mut pricing: Option<Pricing<'_, T>>,
+) -> Result<SolveResult<T>, SolverError> {
+    let timer = Timer::start();
+    let lengths = [(a.ncols(), c.len()), (a.nrows(), b.len()), (a.nrows(), relations.len())];
+    if let Some(&(expected, found)) = lengths.iter().find(|(expected, found)| expected != found) {
+        return Err(SolverError::DimensionMismatch { expected, found });
+    }
+    let eps = epsilon::<T>(options);
+    let num_rows = a.nrows();
+    let num_vars = a.ncols();
+
+    //the standard tableau of a problem with no structural columns is exactly the slack/artificial part
+    let (standard, basis, first_extra_artificial) = standard_tableau(&Array2::<T>::zeros((num_rows, 0)), b, relations);
+    let num_extra = standard.ncols() - 1;
+    let mut form = StandardForm {
+        operator: a,
+        signs: Array1::from(row_signs(b)),
+        generated: Vec::new(),
+        extra: standard.slice(s![..num_rows, ..num_extra]).t().as_standard_layout().into_owned(),
+        rhs: standard.slice(s![..num_rows, num_extra]).to_owned(),
+    };
+    let mut basis: Vec<usize> = basis.iter().map(|col| col + num_vars).collect();
+    let mut first_artificial = num_vars + first_extra_artificial;
+    let num_cols = form.num_cols();
+    let mut unit_columns = basis.clone();
+    let mut inverse = BasisInverse::identity(num_rows); //the starting basis is the slack/artificial identity
+    let mut trace = Trace::new();
+
+    //phase one: maximize -(sum of artificials)
+    let mut phase_one_costs = Array1::<T>::zeros(num_cols);
+    phase_one_costs.slice_mut(s![first_artificial..]).fill(-T::one());
+    let exit = revised_loop(&form, &phase_one_costs, &mut inverse, &mut basis, num_cols, options, &mut trace)?;
+    if let Some(result) = revised_stopped(exit, &form, &phase_one_costs, &inverse, &basis) {
+        return Ok(result);
+    }
+    let values = inverse.ftran(form.rhs.view());
+    let artificial_sum = basis.iter().zip(&values).filter(|(&col, _)| col >= first_artificial).fold(T::zero(), |sum, (_, val)| sum + val.clone());
+    if artificial_sum > eps {
+        return Ok(SolveResult::Infeasible);
+    }
+
+    //artificials basic at zero get swapped for a real column with a nonzero entry in their row
+    for (row, basic) in basis.iter_mut().enumerate() {
+        if *basic < first_artificial {
+            continue;
+        }
+        let tableau_row = form.price(inverse.row(row).view());
+        if let Some(col) = tableau_row.iter().take(first_artificial).position(|val| val.abs() > eps) {
+            let entering = inverse.ftran(form.column(col).view());
+            inverse.update(&entering, row);
+            *basic = col;
+        }
+    }
+
+    //phase two: real objective, artificials can't enter anymore
+    let mut costs = Array1::<T>::zeros(num_cols);
+    costs.slice_mut(s![..c.len()]).assign(&c);
+    loop {
+        let exit = revised_loop(&form, &costs, &mut inverse, &mut basis, first_artificial, options, &mut trace)?;
+        if let Some(result) = revised_stopped(exit, &form, &costs, &inverse, &basis) {
+            return Ok(result);
+        }
+        let Some(pricing) = pricing.as_mut() else {
+            break;
+        };
+        let duals = basis_duals(&costs, &inverse, &basis);
+        let Some((column, cost)) = pricing((&duals * &form.signs).view()) else {
+            break;
+        };
+        if column.len() != num_rows {
+            return Err(SolverError::DimensionMismatch { expected: num_rows, found: column.len() });
+        }
+        let column = column * &form.signs;
+        if dot(duals.view(), column.view()) - cost.clone() >= -eps.clone() {
+            break; //the column wouldn't improve anything, so looping on it would never end
+        }
+
+        //the new column goes in right after the structural ones, everything past it moves over by one
+        let at = form.num_structural();
+        let shift = |col: &mut usize| {
+            if *col >= at {
+                *col += 1;
+            }
+        };
+        basis.iter_mut().for_each(shift);
+        unit_columns.iter_mut().for_each(shift);
+        first_artificial += 1;
+        form.generated.push(column);
+        let mut extended = costs.to_vec();
+        extended.insert(at, cost.clone());
+        costs = Array1::from(extended);
+        c = c.iter().cloned().chain(core::iter::once(cost)).collect();
+    }
+    let num_cols = form.num_cols();
+
+    let tableau = full_tableau(&form, &costs, &inverse, &basis);
+    let mut x = Array1::<T>::zeros(first_artificial);
+    for (row, &col) in basis.iter().enumerate() {
+        if col < first_artificial {
+            x[col] = tableau[[row, num_cols]].clone();
+        }
+    }
+    let objective = dot(c.view(), x.slice(s![..c.len()]));
+    let alternate_optima = zero_reduced_costs(&tableau, &basis, first_artificial, &eps);
+    let degenerate = degenerate_columns(&tableau, &basis, first_artificial, &eps);
+    let binding_constraints = binding_rows(&tableau, &unit_columns, c.len(), &x, &eps);
+
+    Ok(SolveResult::Optimal(Box::new(Solution {
+        x,
+        objective,
+        iterations: trace.iterations,
+        nodes: 0,
+        solve_time: timer.elapsed(),
+        basis,
+        num_vars: c.len(),
+        var_names: Vec::new(),
+        sense: Sense::Maximize,
+        tableau,
+        costs,
+        unit_columns,
+        row_signs: row_signs(b),
+        at_upper: vec![false; first_artificial],
+        upper_bounds: vec![None; first_artificial],
+        lower_bounds: vec![T::zero(); first_artificial],
+        alternate_optima,
+        degenerate,
+        binding_constraints,
+        soft_violations: Vec::new(),
+        rewrite: None,
+        history: Vec::new(),
+        log: trace.log,
+        pivot_sizes: trace.pivot_sizes,
+    })))
+}
+
+//pricing, ratio test and B⁻¹ update until no column among the first `num_eligible` has a negative reduced cost
+fn revised_loop<T: Scalar, A: ConstraintOperator<T> + ?Sized>(
+    form: &StandardForm<T, A>,
+    costs: &Array1<T>,
+    inverse: &mut BasisInverse<T>,
+    basis: &mut [usize],
+    num_eligible: usize,
+    options: &SolverOptions,
+    trace: &mut Trace<T>,
+) -> Result<LoopExit, SolverError> {
+    let eps = epsilon::<T>(options);
+    let num_rows = form.rhs.len();
+    let num_cols = form.num_cols();
+    let max_iterations = options.max_iterations.unwrap_or(50 * (num_rows + num_cols));
+    let no_bounds = ColumnBounds::unbounded(num_cols);
+    let mut devex_weights = vec![T::one(); num_cols];
+    let mut values = inverse.ftran(form.rhs.view()); //B⁻¹·b, moved along with every pivot instead of recomputed
+
+    loop {
+        //y = c_B·B⁻¹, then d_j = y·A_j - c_j, the same numbers the objective row of the dense tableau holds
+        let duals = basis_duals(costs, inverse, basis);
+        let mut is_basic = vec![false; num_cols];
+        for &col in basis.iter() {
+            is_basic[col] = true;
+        }
+        let priced = form.price(duals.view());
+        let mut reduced = Array1::<T>::zeros(num_cols);
+        for j in (0..num_eligible).filter(|&j| !is_basic[j]) {
+            reduced[j] = priced[j].clone() - costs[j].clone();
+        }
+        let edge_weight = |col: usize| match options.pivot_rule {
+            PivotRule::Devex => devex_weights[col].clone(),
+            _ => inverse.ftran(form.column(col).view()).iter().fold(T::one(), |sum, val| sum + val.clone() * val.clone()),
+        };
+        let selection = Selection::new(options, trace.iterations);
+        let pivot_col = match choose_entering(reduced.view(), 0..num_eligible, selection, &eps, edge_weight) {
+            Some(col) => col,
+            None => return Ok(LoopExit::Optimal),
+        };
+
+        //the ratio test only needs the entering column and the current values, laid out as a two-column tableau
+        //lexicographic ties also compare the rows of B⁻¹, which start out as the identity so every row is lexicographically positive
+        let entering = inverse.ftran(form.column(pivot_col).view());
+        let extra = if options.pivot_rule == PivotRule::Lexicographic { num_rows } else { 0 };
+        let mut ratio_input = Array2::<T>::zeros((num_rows, extra + 2));
+        if extra > 0 {
+            ratio_input.slice_mut(s![.., ..extra]).assign(&inverse.to_dense());
+        }
+        ratio_input.column_mut(extra).assign(&entering);
+        ratio_input.column_mut(extra + 1).assign(&values);
+        let pivot_row = match find_pivot_row(&ratio_input, extra, num_rows, basis, &no_bounds, selection, &eps) {
+            Some(Step::Pivot(row)) => row,
+            _ => return Ok(LoopExit::Unbounded),
+        };
+        if trace.iterations >= max_iterations {
+            return Ok(LoopExit::IterationLimit);
+        }
+        if entering[pivot_row].is_zero() {
+            return Err(SolverError::ZeroPivot { row: pivot_row, col: pivot_col });
+        }
+
+        if options.pivot_rule == PivotRule::Devex {
+            //the devex update needs row pivot_row of B⁻¹·A, which the revised method doesn't keep around
+            let tableau_row = form.price(inverse.row(pivot_row).view());
+            update_devex_weights(&mut devex_weights, tableau_row.view(), pivot_col, basis[pivot_row]);
+        }
+        trace.note_pivot(&entering[pivot_row]);
+        inverse.update(&entering, pivot_row);
+        let step = values[pivot_row].clone() / entering[pivot_row].clone();
+        values.zip_mut_with(&entering, |val, coeff| *val -= step.clone() * coeff.clone());
+        values[pivot_row] = step.clone();
+        let leaving = basis[pivot_row];
+        basis[pivot_row] = pivot_col;
+        trace.iterations += 1;
+        if options.record_log {
+            let objective = basis.iter().zip(values.iter()).fold(T::zero(), |sum, (&col, val)| sum + costs[col].clone() * val.clone());
+            trace.log(options, pivot_col, Some((pivot_row, leaving)), step, objective);
+        }
+        if options.refactor_frequency.is_some_and(|frequency| inverse.updates >= frequency) {
+            let mut basis_matrix = Array2::<T>::zeros((num_rows, num_rows));
+            for (i, &col) in basis.iter().enumerate() {
+                basis_matrix.column_mut(i).assign(&form.column(col));
+            }
+            if inverse.refactor(basis_matrix, &eps) {
+                values = inverse.ftran(form.rhs.view());
+            }
+        }
+        #[cfg(feature = "std")]
+        if options.verbose {
+            eprintln!("iteration {}: pivot on row {}, column {}", trace.iterations, pivot_row, pivot_col);
+        }
+    }
+}
+
+//y = c_B·B⁻¹ in the rows of the standard form
+fn basis_duals<T: Scalar>(costs: &Array1<T>, inverse: &BasisInverse<T>, basis: &[usize]) -> Array1<T> {
+    let basic_costs: Array1<T> = basis.iter().map(|&col| costs[col].clone()).collect();
+    inverse.btran(basic_costs.view())
+}
+
+//B⁻¹ in product form, E_k ⋯ E_1 · base with one eta matrix per pivot, base starts out as the slack/artificial identity
+//E_i is the identity with column p replaced by eta, so applying one costs O(m) instead of an O(m²) rewrite of B⁻¹.
+//once there are as many etas as rows they get multiplied into base so ftran/btran don't keep getting longer
+struct BasisInverse<T> {
+    size: usize,
+    base: Option<Array2<T>>, //None while it's still the identity
+    etas: Vec<(usize, Array1<T>)>,
+    updates: usize, //pivots since B⁻¹ was last computed from the basis columns
+}
+
+impl<T: Scalar> BasisInverse<T> {
+    fn identity(size: usize) -> Self {
+        BasisInverse { size, base: None, etas: Vec::new(), updates: 0 }
+    }
+
+    //B⁻¹·v, base first and then the etas oldest first
+    fn ftran(&self, v: ArrayView1<T>) -> Array1<T> {
+        let mut w = match &self.base {
+            Some(base) => base.rows().into_iter().map(|row| dot(row, v)).collect(),
+            None => v.to_owned(),
+        };
+        for (p, eta) in &self.etas {
+            let wp = w[*p].clone();
+            if wp.is_zero() {
+                continue;
+            }
+            for (i, val) in w.iter_mut().enumerate() {
+                if i == *p {
+                    *val = eta[i].clone() * wp.clone();
+                } else {
+                    *val += eta[i].clone() * wp.clone();
+                }
+            }
+        }
+        w
+    }
+
+    //yᵀ·B⁻¹, the etas newest first (each one only changes entry p) and then base
+    fn btran(&self, y: ArrayView1<T>) -> Array1<T> {
+        let mut w = y.to_owned();
+        for (p, eta) in self.etas.iter().rev() {
+            w[*p] = dot(w.view(), eta.view());
+        }
+        match &self.base {
+            Some(base) => base.columns().into_iter().map(|col| dot(col, w.view())).collect(),
+            None => w,
+        }
+    }
+
+    //row i of B⁻¹
+    fn row(&self, i: usize) -> Array1<T> {
+        let mut unit = Array1::<T>::zeros(self.size);
+        unit[i] = T::one();
+        self.btran(unit.view())
+    }
+
+    fn to_dense(&self) -> Array2<T> {
+        let mut dense = Array2::<T>::zeros((self.size, self.size));
+        for i in 0..self.size {
+            dense.row_mut(i).assign(&self.row(i));
+        }
+        dense
+    }
+
+    //`entering` is B⁻¹·a_q of the column replacing the basic one of pivot_row
+    fn update(&mut self, entering: &Array1<T>, pivot_row: usize) {
+        let pivot_value = entering[pivot_row].clone();
+        let eta = entering
+            .iter()
+            .enumerate()
+            .map(|(i, val)| if i == pivot_row { T::one() / pivot_value.clone() } else { -val.clone() / pivot_value.clone() })
+            .collect();
+        self.etas.push((pivot_row, eta));
+        self.updates += 1;
+        if self.etas.len() >= self.size {
+            let mut base = Array2::<T>::zeros((self.size, self.size));
+            for j in 0..self.size {
+                let mut unit = Array1::<T>::zeros(self.size);
+                unit[j] = T::one();
+                base.column_mut(j).assign(&self.ftran(unit.view()));
+            }
+            self.base = Some(base);
+            self.etas.clear();
+        }
+    }
+
+    //inverts the basis matrix from scratch (Gauss-Jordan with partial pivoting), which throws away the rounding
+    //the etas piled up. keeps the current factors and returns false if the matrix looks singular
+    fn refactor(&mut self, mut basis_matrix: Array2<T>, eps: &T) -> bool {
+        let size = self.size;
+        let mut inverse = Array2::<T>::eye(size);
+        for col in 0..size {
+            let pivot_row = (col..size)
+                .max_by(|&i, &j| basis_matrix[[i, col]].abs().partial_cmp(&basis_matrix[[j, col]].abs()).unwrap_or(Ordering::Equal))
+                .unwrap_or(col);
+            let pivot_value = basis_matrix[[pivot_row, col]].clone();
+            if pivot_value.abs() <= *eps {
+                return false;
+            }
+            for m in [&mut basis_matrix, &mut inverse] {
+                if pivot_row != col {
+                    let (mut upper, mut lower) = m.multi_slice_mut((s![col, ..], s![pivot_row, ..]));
+                    ndarray::Zip::from(&mut upper).and(&mut lower).for_each(core::mem::swap);
+                }
+                m.row_mut(col).map_inplace(|val| *val /= pivot_value.clone());
+            }
+            for row in (0..size).filter(|&row| row != col) {
+                let factor = basis_matrix[[row, col]].clone();
+                if factor.is_zero() {
+                    continue;
+                }
+                for m in [&mut basis_matrix, &mut inverse] {
+                    let pivot = m.row(col).to_owned();
+                    m.row_mut(row).zip_mut_with(&pivot, |val, p| *val -= factor.clone() * p.clone());
+                }
+            }
+        }
+        self.base = Some(inverse);
+        self.etas.clear();
+        self.updates = 0;
+        true
+    }
+}
+
+//B⁻¹·[A | b] with the objective row below it, the same tableau the dense solver would end up with
+fn full_tableau<T: Scalar, A: ConstraintOperator<T> + ?Sized>(
+    form: &StandardForm<T, A>,
+    costs: &Array1<T>,
+    inverse: &BasisInverse<T>,
+    basis: &[usize],
+) -> Array2<T> {
+    let num_rows = form.rhs.len();
+    let num_cols = form.num_cols();
+    let mut tableau = Array2::<T>::zeros((num_rows + 1, num_cols + 1));
+    for j in 0..num_cols {
+        tableau.slice_mut(s![..num_rows, j]).assign(&inverse.ftran(form.column(j).view()));
+    }
+    tableau.slice_mut(s![..num_rows, num_cols]).assign(&inverse.ftran(form.rhs.view()));
+    for j in 0..=num_cols {
+        let priced = basis.iter().enumerate().fold(T::zero(), |sum, (i, &col)| sum + costs[col].clone() * tableau[[i, j]].clone());
+        tableau[[num_rows, j]] = if j < num_cols { priced - costs[j].clone() } else { priced };
+    }
+    tableau
+}
+
+//result to return early when the loop didn't reach an optimum
+fn revised_stopped<T: Scalar, A: ConstraintOperator<T> + ?Sized>(
+    exit: LoopExit,
+    form: &StandardForm<T, A>,
+    costs: &Array1<T>,
+    inverse: &BasisInverse<T>,
+    basis: &[usize],
+) -> Option<SolveResult<T>> {
+    match exit {
+        LoopExit::Optimal => None,
+        LoopExit::Unbounded => Some(SolveResult::Unbounded),
+        LoopExit::IterationLimit => Some(SolveResult::IterationLimit(full_tableau(form, costs, inverse, basis))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::simplex_solver_two_phase;
+    use crate::test_support::{assert_objective_close, assert_solution_close, expect_optimal, random_feasible_lp};
+
+    //Some(1) rebuilds B⁻¹ from the basis columns after every pivot, None only ever multiplies etas into it,
+    //both have to walk the dense tableau's pivots to its optimum
+    #[test]
+    fn eta_updates_agree_with_recomputing_the_inverse() {
+        for seed in 0..20 {
+            let (problem, _) = random_feasible_lp(seed, 15, 30);
+            let (c, a, b, relations) = problem.to_arrays();
+            let dense = expect_optimal(simplex_solver_two_phase(c.clone(), &a, &b, &relations, &SolverOptions::default()).unwrap());
+            for refactor_frequency in [None, Some(1), Some(7)] {
+                let options = SolverOptions { refactor_frequency, ..SolverOptions::default() };
+                let revised = expect_optimal(revised_simplex(c.clone(), &a, &b, &relations, &options).unwrap());
+                assert_objective_close(dense.objective, revised.objective, 1e-9);
+                assert_solution_close(&dense.x.to_vec(), &revised.x, 1e-7);
+                assert_eq!(dense.iterations, revised.iterations, "seed {} refactoring every {:?}", seed, refactor_frequency);
+            }
+        }
+    }
+
+    #[test]
+    fn revised_reports_the_dense_basis() {
+        let (problem, _) = random_feasible_lp(4, 8, 12);
+        let (c, a, b, relations) = problem.to_arrays();
+        let dense = expect_optimal(simplex_solver_two_phase(c.clone(), &a, &b, &relations, &SolverOptions::default()).unwrap());
+        let revised = expect_optimal(revised_simplex(c, &a, &b, &relations, &SolverOptions::default()).unwrap());
+        assert_eq!(dense.binding_constraints, revised.binding_constraints);
+        let (rows, cols) = dense.tableau.dim();
+        assert_eq!(revised.tableau.dim(), (rows, cols));
+        for j in 0..cols {
+            assert!((dense.tableau[[rows - 1, j]] - revised.tableau[[rows - 1, j]]).abs() < 1e-7, "reduced cost of column {}", j);
+        }
+    }
+
+    //cutting stock: rolls of width 10 cut into pieces of width 3, 4 and 5, 5, 4 and 3 of them needed. a pattern is a
+    //column of how many of each piece one roll gives, the lp maximizes -(rolls used) subject to pattern·x >= demand
+    const WIDTHS: [usize; 3] = [3, 4, 5];
+    const ROLL: usize = 10;
+
+    //every pattern fitting in a roll, brute force is enough at this size
+    fn patterns() -> Vec<[usize; 3]> {
+        let mut all = Vec::new();
+        for a in 0..=ROLL / WIDTHS[0] {
+            for b in 0..=ROLL / WIDTHS[1] {
+                for c in 0..=ROLL / WIDTHS[2] {
+                    if a * WIDTHS[0] + b * WIDTHS[1] + c * WIDTHS[2] <= ROLL && a + b + c > 0 {
+                        all.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        all
+    }
+
+    #[test]
+    fn column_generation_solves_cutting_stock() {
+        let demand = array![5.0, 4.0, 3.0];
+        let relations = [Relation::Ge; 3];
+        //one pattern per width to start from, as many pieces of it as fit
+        let a = array![[3.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 2.0]];
+        let mut generated = 0;
+        let result = revised_simplex_columns(array![-1.0, -1.0, -1.0], &a, &demand, &relations, &SolverOptions::default(), |y| {
+            //the pattern with the most dual value, it enters once -y·a is above its cost of one roll
+            let value = |pattern: &[usize; 3]| pattern.iter().zip(y.iter()).map(|(&count, &dual)| -(count as f64) * dual).sum::<f64>();
+            let best = patterns().into_iter().max_by(|p, q| value(p).partial_cmp(&value(q)).unwrap())?;
+            generated += 1;
+            Some((best.iter().map(|&count| count as f64).collect(), -1.0))
+        });
+        let solution = expect_optimal(result.unwrap());
+        assert!(generated > 1);
+        assert!(solution.x.len() > 3 + 3, "no column was added");
+
+        //the lp over every pattern at once has the same optimum, better than the starting patterns alone
+        let all = patterns();
+        let full = Array2::from_shape_fn((3, all.len()), |(i, j)| all[j][i] as f64);
+        let everything = expect_optimal(revised_simplex(Array1::from(vec![-1.0; all.len()]), &full, &demand, &relations, &SolverOptions::default()).unwrap());
+        assert_objective_close(everything.objective, solution.objective, 1e-9);
+        let start = expect_optimal(revised_simplex(array![-1.0, -1.0, -1.0], &a, &demand, &relations, &SolverOptions::default()).unwrap());
+        assert!(everything.objective > start.objective + 1e-9);
+    }
+
+    //largest |row·x - b| in the standard form the solver works in, a slack per <= and >= row after the structurals
+    fn residual(a: &Array2<f64>, b: &Array1<f64>, relations: &[Relation], x: &Array1<f64>) -> f64 {
+        let structural = x.slice(ndarray::s![..a.ncols()]);
+        let mut slack = a.ncols();
+        let mut worst: f64 = 0.0;
+        for ((row, &relation), &rhs) in a.rows().into_iter().zip(relations).zip(b) {
+            let mut lhs = row.dot(&structural);
+            match relation {
+                Relation::Le => lhs += x[slack],
+                Relation::Ge => lhs -= x[slack],
+                Relation::Eq => {}
+            }
+            if relation != Relation::Eq {
+                slack += 1;
+            }
+            worst = worst.max((lhs - rhs).abs());
+        }
+        worst
+    }
+
+    #[test]
+    fn refactoring_keeps_the_eta_file_from_drifting() {
+        //a hundred-odd eta updates on a 40x60 problem leave errors around 1e-12 in B⁻¹·b, recomputing B⁻¹ every
+        //10 pivots brings them down by a few times
+        let (mut drifted, mut refactored) = (0.0, 0.0);
+        for seed in 0..5 {
+            let (c, a, b, relations) = random_feasible_lp(seed, 40, 60).0.to_arrays();
+            let never = expect_optimal(revised_simplex(c.clone(), &a, &b, &relations, &SolverOptions { refactor_frequency: None, ..SolverOptions::default() }).unwrap());
+            let often = expect_optimal(revised_simplex(c, &a, &b, &relations, &SolverOptions { refactor_frequency: Some(10), ..SolverOptions::default() }).unwrap());
+            let (never, often) = (residual(&a, &b, &relations, &never.x), residual(&a, &b, &relations, &often.x));
+            assert!(often < never, "seed {}: {:e} refactoring against {:e} without", seed, often, never);
+            drifted += never;
+            refactored += often;
+        }
+        assert!(2.0 * refactored < drifted, "{:e} refactoring against {:e} without", refactored, drifted);
+    }
+}
@@ -0,0 +1,8 @@
+use num_traits::{FromPrimitive, NumAssign, Signed};
+use core::fmt::Debug;
+
+//number type the solver runs on, f64 by default but f32 or an exact type like num_rational::BigRational work too
+//SolverOptions::epsilon gets converted with from_f64, exact types can set it to 0
+pub trait Scalar: Signed + NumAssign + FromPrimitive + PartialOrd + Clone + Debug {}
+
+impl<T> Scalar for T where T: Signed + NumAssign + FromPrimitive + PartialOrd + Clone + Debug {}
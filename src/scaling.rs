@@ -0,0 +1,133 @@
+use ndarray::{Array1, Array2};
+
+use crate::simplex::{check_dimensions, simplex_solver_two_phase, Relation, SolveResult, SolverError, SolverOptions};
+
+//how many row/column sweeps geometric scaling does
+const SCALING_PASSES: usize = 4;
+
+//row factors r and column factors s, the scaled problem is R·A·S x' (rel) R·b with objective (S·c)·x' and x = S·x'
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scaling {
+    pub row_scale: Array1<f64>,
+    pub col_scale: Array1<f64>,
+}
+
+impl Scaling {
+    //geometric-mean equilibration, every sweep divides a row (then a column) by sqrt(min |a_ij| · max |a_ij|) over its nonzeros
+    pub fn geometric(a: &Array2<f64>) -> Self {
+        let mut row_scale = Array1::<f64>::ones(a.nrows());
+        let mut col_scale = Array1::<f64>::ones(a.ncols());
+        let mut scaled = a.clone();
+
+        for _ in 0..SCALING_PASSES {
+            for (i, mut row) in scaled.rows_mut().into_iter().enumerate() {
+                if let Some(factor) = geometric_factor(row.iter()) {
+                    row.mapv_inplace(|val| val * factor);
+                    row_scale[i] *= factor;
+                }
+            }
+            for (j, mut col) in scaled.columns_mut().into_iter().enumerate() {
+                if let Some(factor) = geometric_factor(col.iter()) {
+                    col.mapv_inplace(|val| val * factor);
+                    col_scale[j] *= factor;
+                }
+            }
+        }
+        Scaling { row_scale, col_scale }
+    }
+
+    pub fn scale(&self, c: &Array1<f64>, a: &Array2<f64>, b: &Array1<f64>) -> (Array1<f64>, Array2<f64>, Array1<f64>) {
+        let mut a = a.clone();
+        for ((i, j), val) in a.indexed_iter_mut() {
+            *val *= self.row_scale[i] * self.col_scale[j];
+        }
+        (c * &self.col_scale, a, b * &self.row_scale)
+    }
+
+    //maps x of the scaled two-phase solve back, slack/surplus columns follow the rows that have one
+    //the objective doesn't change under scaling, the tableau and the sensitivity fields stay in scaled units
+    pub fn unscale(&self, result: SolveResult, relations: &[Relation]) -> SolveResult {
+        let SolveResult::Optimal(mut solution) = result else {
+            return result;
+        };
+        let num_vars = self.col_scale.len();
+        let slack_rows = relations.iter().enumerate().filter(|(_, &rel)| rel != Relation::Eq).map(|(i, _)| i);
+        for (j, &factor) in self.col_scale.iter().enumerate() {
+            solution.x[j] *= factor;
+        }
+        for (k, i) in slack_rows.enumerate() {
+            solution.x[num_vars + k] /= self.row_scale[i];
+        }
+        SolveResult::Optimal(solution)
+    }
+}
+
+//1 / sqrt(min · max) of the nonzero magnitudes, None for an empty line or one that is already balanced
+fn geometric_factor<'a>(values: impl Iterator<Item = &'a f64>) -> Option<f64> {
+    let (min, max) = values
+        .map(|val| val.abs())
+        .filter(|&val| val > 0.0)
+        .fold((f64::INFINITY, 0.0_f64), |(min, max), val| (min.min(val), max.max(val)));
+    if max == 0.0 || min * max == 1.0 {
+        return None;
+    }
+    Some(1.0 / num_traits::Float::sqrt(min * max))
+}
+
+//two-phase solve on the geometrically scaled problem, x comes back in the original units
+pub fn simplex_solver_scaled(
+    c: Array1<f64>,
+    a: &Array2<f64>,
+    b: &Array1<f64>,
+    relations: &[Relation],
+    options: &SolverOptions,
+) -> Result<SolveResult, SolverError> {
+    check_dimensions(&c, a, b, Some(relations))?;
+    let scaling = Scaling::geometric(a);
+    let (c, a, b) = scaling.scale(&c, a, b);
+    Ok(scaling.unscale(simplex_solver_two_phase(c, &a, &b, relations, options)?, relations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn geometric_scaling_balances_the_magnitudes() {
+        let a = array![[1e-6, 2e-6], [3e5, 1e6]];
+        let scaling = Scaling::geometric(&a);
+        let (_, scaled, _) = scaling.scale(&Array1::zeros(2), &a, &Array1::zeros(2));
+        let largest = scaled.iter().fold(0.0_f64, |max, val| max.max(val.abs()));
+        let smallest = scaled.iter().fold(f64::INFINITY, |min, val| min.min(val.abs()));
+        assert!(largest / smallest < 10.0, "{:?}", scaled);
+    }
+
+    #[test]
+    fn scaled_solve_is_right_where_the_unscaled_one_is_not() {
+        //x1 <= 1 written as 1e-10 x1 <= 1e-10, the tolerance takes the coeff for a zero without scaling
+        let (c, a, b) = (array![1.0, 1.0], array![[1e-10, 0.0], [0.0, 1e8]], array![1e-10, 1e8]);
+        let relations = [Relation::Le; 2];
+        let options = SolverOptions::default();
+        let unscaled = simplex_solver_two_phase(c.clone(), &a, &b, &relations, &options).unwrap();
+        let scaled = simplex_solver_scaled(c, &a, &b, &relations, &options).unwrap();
+        let SolveResult::Optimal(solution) = scaled else { panic!("the scaled solve has an optimum, got {:?}", scaled) };
+        assert!((solution.objective - 2.0).abs() < 1e-9);
+        assert!((solution.x[0] - 1.0).abs() < 1e-9 && (solution.x[1] - 1.0).abs() < 1e-9, "{:?}", solution.x);
+        let wrong = match unscaled {
+            SolveResult::Optimal(unscaled) => (unscaled.objective - 2.0).abs() > 1e-6,
+            _ => true,
+        };
+        assert!(wrong);
+    }
+
+    #[test]
+    fn unscale_maps_the_slacks_back() {
+        //wyndor with its rows multiplied by 100, 0.01 and 1000, the slacks at (2, 6) are 200, 0 and 0 in those units
+        let (c, a, b) = (array![3.0, 5.0], array![[100.0, 0.0], [0.0, 0.02], [3000.0, 2000.0]], array![400.0, 0.12, 18000.0]);
+        let result = simplex_solver_scaled(c, &a, &b, &[Relation::Le; 3], &SolverOptions::default()).unwrap();
+        let SolveResult::Optimal(solution) = result else { panic!("wyndor has an optimum") };
+        let expected = [2.0, 6.0, 200.0, 0.0, 0.0];
+        assert!(solution.x.iter().zip(expected).all(|(x, e)| (x - e).abs() < 1e-7), "{:?}", solution.x);
+    }
+}
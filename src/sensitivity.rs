@@ -0,0 +1,396 @@
+use ndarray::{s, Array1};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::simplex::{slack_column, Sense, Solution, SolveResult};
+
+//shadow price of every constraint, in the units of the original objective
+//the objective row under row i's starting identity column holds y_i - c_k, for a <= row that's just the entry under its slack
+pub fn dual_values(solution: &Solution) -> Array1<f64> {
+    let last_row_index = solution.tableau.nrows() - 1;
+    let sense = if solution.sense == Sense::Minimize { -1.0 } else { 1.0 };
+    solution
+        .unit_columns
+        .iter()
+        .zip(&solution.row_signs)
+        .map(|(&col, &sign)| sense * sign * (solution.tableau[[last_row_index, col]] + solution.costs[col]))
+        .collect()
+}
+
+//reduced cost of every column of x, read off the final objective row and zero for the basic ones
+//nonnegative at an optimum, also for minimized problems since those were solved as max -c·x
+//except for variables sitting at their upper bound, there the column is u_j - x_j so the sign is turned back and it is <= 0.
+//a column of a rewritten solution gets the one of the first tableau column it is made of (x⁺ of a split var, whose
+//x⁻ always has the opposite one)
+pub fn reduced_costs(solution: &Solution) -> Array1<f64> {
+    if let (Some(rewritten), Some(rewrite)) = (solution.rewritten(), &solution.rewrite) {
+        let costs = reduced_costs(&rewritten);
+        return rewrite.columns.iter().map(|terms| terms.first().map_or(0.0, |&(col, coeff)| costs[col] / coeff)).collect();
+    }
+    let last_row_index = solution.tableau.nrows() - 1;
+    let mut costs = solution.tableau.slice(s![last_row_index, ..solution.x.len()]).to_owned();
+    for (cost, &at_upper) in costs.iter_mut().zip(&solution.at_upper) {
+        if at_upper {
+            *cost = -*cost;
+        }
+    }
+    for &col in &solution.basis {
+        if col < costs.len() {
+            costs[col] = 0.0;
+        }
+    }
+    costs
+}
+
+//how far each objective coeff of x can go down and up while the final basis stays optimal, as (decrease, increase)
+//a basic c_j moves the whole objective row by its row of the tableau, a nonbasic one only its own reduced cost.
+//a column of a rewritten solution made of several tableau columns moves all of their costs: with one of them basic
+//that one decides, the others follow it at no cost, otherwise each one limits the range on its own
+pub fn objective_ranges(solution: &Solution) -> Vec<(f64, f64)> {
+    let Some((rewritten, rewrite)) = solution.rewritten().zip(solution.rewrite.as_ref()) else {
+        return (0..solution.x.len()).map(|j| column_range(solution, j, &[])).collect();
+    };
+    rewrite
+        .columns
+        .iter()
+        .map(|terms| {
+            let cols: Vec<usize> = terms.iter().map(|&(col, _)| col).collect();
+            let oriented = |&(col, coeff): &(usize, f64), skip: &[usize]| {
+                let (decrease, increase) = column_range(&rewritten, col, skip);
+                if coeff < 0.0 { (increase, decrease) } else { (decrease, increase) }
+            };
+            match terms.iter().find(|(col, _)| rewritten.basis.contains(col)) {
+                Some(basic) => oriented(basic, &cols),
+                None => terms.iter().map(|term| oriented(term, &[])).fold((f64::INFINITY, f64::INFINITY), |(dec, inc), (d, i)| (dec.min(d), inc.min(i))),
+            }
+        })
+        .collect()
+}
+
+//objective_ranges of one tableau column, the columns in skip move along with it and don't limit it
+fn column_range(solution: &Solution, j: usize, skip: &[usize]) -> (f64, f64) {
+    let last_row_index = solution.tableau.nrows() - 1;
+    let objective_row = solution.tableau.row(last_row_index);
+    let nonbasic = (0..solution.x.len()).filter(|col| !solution.basis.contains(col) && !skip.contains(col));
+    let (decrease, increase) = match solution.basis.iter().position(|&col| col == j) {
+        //+δ on c_j adds δ·T[r, k] to every nonbasic reduced cost, which all have to stay >= 0
+        Some(row) => nonbasic.fold((f64::INFINITY, f64::INFINITY), |(dec, inc), k| {
+            let entry = solution.tableau[[row, k]];
+            if entry > 0.0 {
+                (dec.min(objective_row[k] / entry), inc)
+            } else if entry < 0.0 {
+                (dec, inc.min(objective_row[k] / -entry))
+            } else {
+                (dec, inc)
+            }
+        }),
+        None => (f64::INFINITY, objective_row[j]),
+    };
+    //a complemented column carries -c_j, and a minimized problem was solved with -c
+    let flipped = solution.at_upper.get(j).copied().unwrap_or(false);
+    if flipped != (solution.sense == Sense::Minimize) {
+        (increase, decrease)
+    } else {
+        (decrease, increase)
+    }
+}
+
+//how far each b_i can go down and up while the final basis stays feasible, as (decrease, increase)
+//changing b_i by δ moves the rhs column by δ times row i's column of B⁻¹, each basic var has to stay within its bounds
+pub fn rhs_ranges(solution: &Solution) -> Vec<(f64, f64)> {
+    let rhs_col = solution.tableau.ncols() - 1;
+    solution
+        .unit_columns
+        .iter()
+        .zip(&solution.row_signs)
+        .map(|(&unit_col, &sign)| {
+            solution.basis.iter().enumerate().fold((f64::INFINITY, f64::INFINITY), |(dec, inc), (row, &col)| {
+                let rate = sign * solution.tableau[[row, unit_col]];
+                let value = solution.tableau[[row, rhs_col]];
+                let room = solution.upper_bounds.get(col).copied().flatten().map_or(f64::INFINITY, |upper| upper - value);
+                if rate > 0.0 {
+                    (dec.min(value / rate), inc.min(room / rate))
+                } else if rate < 0.0 {
+                    (dec.min(room / -rate), inc.min(value / -rate))
+                } else {
+                    (dec, inc)
+                }
+            })
+        })
+        .collect()
+}
+
+//one complementary pair: a row's slack with its dual value, or a variable's value with its reduced cost
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlacknessPair {
+    pub index: usize, //row or column of x
+    pub primal: f64,
+    pub dual: f64,
+    pub violated: bool, //both sides are nonzero beyond the tolerance
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlacknessReport {
+    pub constraints: Vec<SlacknessPair>, //slack of every row against its dual value, = rows always have slack 0
+    pub variables: Vec<SlacknessPair>,   //every column of x against its reduced cost
+}
+
+impl SlacknessReport {
+    pub fn violations(&self) -> impl Iterator<Item = &SlacknessPair> {
+        self.constraints.iter().chain(&self.variables).filter(|pair| pair.violated)
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.violations().next().is_none()
+    }
+}
+
+//pairs every row's slack with its dual value and every column's value with its reduced cost, at an optimum one side of
+//each pair is zero. a column is paired by how far it is from its lower bound, and one sitting at its upper bound is
+//exempt, its reduced cost is what holds it there. a rewritten solution is checked on the problem solve_with solved,
+//the rows then pair that problem's slacks and a column of x is violated if one of the columns it is made of is.
+//None unless the result is optimal
+pub fn complementary_slackness_report(result: &SolveResult, eps: f64) -> Option<SlacknessReport> {
+    let SolveResult::Optimal(solution) = result else {
+        return None;
+    };
+    if let (Some(rewritten), Some(rewrite)) = (solution.rewritten(), &solution.rewrite) {
+        let report = complementary_slackness_report(&SolveResult::Optimal(Box::new(rewritten)), eps)?;
+        let costs = reduced_costs(solution);
+        let variables = (rewrite.columns.iter().enumerate())
+            .map(|(j, terms)| SlacknessPair { index: j, primal: solution.x[j], dual: costs[j], violated: terms.iter().any(|&(col, _)| report.variables[col].violated) })
+            .collect();
+        return Some(SlacknessReport { constraints: report.constraints, variables });
+    }
+    let pair = |index: usize, primal: f64, dual: f64| SlacknessPair { index, primal, dual, violated: primal.abs() > eps && dual.abs() > eps };
+
+    let duals = dual_values(solution);
+    let constraints = (0..solution.unit_columns.len())
+        .map(|row| {
+            let slack_col = slack_column(&solution.tableau, &solution.unit_columns, solution.num_vars, solution.x.len(), row, &eps);
+            let slack = slack_col.map_or(0.0, |col| solution.x[col]);
+            pair(row, slack, duals[row])
+        })
+        .collect();
+    let costs = reduced_costs(solution);
+    let variables = (0..solution.x.len())
+        .map(|col| {
+            let at_upper = solution.at_upper.get(col).copied().unwrap_or(false);
+            let off_lower = solution.x[col] - solution.lower_bounds.get(col).copied().unwrap_or(0.0);
+            let violated = off_lower.abs() > eps && costs[col].abs() > eps && !at_upper;
+            SlacknessPair { violated, ..pair(col, solution.x[col], costs[col]) }
+        })
+        .collect();
+    Some(SlacknessReport { constraints, variables })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem::LpProblem;
+    use crate::simplex::Relation;
+    use crate::test_support::{self, expect_optimal};
+
+    //wyndor with an x3 worth 0.5 in the third row, 3x1 + 2x2 + x3 <= 18: still 36 at (2, 6, 0), with x3 nonbasic
+    fn wyndor_x3() -> LpProblem {
+        test_support::wyndor().set_objective_coeff(2, 0.5).set_constraint_coeff(2, 2, 1.0)
+    }
+
+    //min 2x1 + x2 + 3x3 st x1 + x2 + x3 >= 1, x2 - x1 <= 3 with x1 free, optimum 0 at (-1, 2, 0)
+    fn free_var() -> LpProblem {
+        LpProblem::new()
+            .objective(vec![2.0, 1.0, 3.0])
+            .add_constraint(vec![1.0, 1.0, 1.0], Relation::Ge, 1.0)
+            .add_constraint(vec![-1.0, 1.0, 0.0], Relation::Le, 3.0)
+            .free(0)
+            .minimize()
+    }
+
+    //wyndor with -3 <= x1 - x2 <= 1, which ends at its lower end: 34.2 at (2.4, 5.4, 0)
+    fn ranged() -> LpProblem {
+        wyndor_x3().add_range(vec![1.0, -1.0, 0.0], -3.0, 1.0)
+    }
+
+    //wyndor with x1 + x2 >= 9 at 2 per unit short, 1 short at (2, 6, 0) for 34
+    fn soft() -> LpProblem {
+        wyndor_x3().add_soft_constraint(vec![1.0, 1.0, 0.0], Relation::Ge, 9.0, 2.0)
+    }
+
+    fn fixtures() -> [(&'static str, LpProblem); 4] {
+        [("plain", wyndor_x3()), ("free", free_var()), ("ranged", ranged()), ("soft", soft())]
+    }
+
+    #[test]
+    fn complementary_slackness_holds_at_every_optimum() {
+        for (name, problem) in fixtures() {
+            let result = problem.solve().unwrap();
+            let report = complementary_slackness_report(&result, 1e-9).unwrap();
+            assert!(report.is_satisfied(), "{}: {:?}", name, report.violations().collect::<Vec<_>>());
+            assert_eq!(report.constraints.len(), problem.constraints.len(), "{}", name);
+            assert_eq!(report.variables.len(), expect_optimal(problem.solve().unwrap()).x.len(), "{}", name);
+        }
+        //a var held at a nonzero lower bound by its reduced cost is at its bound, not off it
+        let raised = wyndor_x3().bound(2, 1.0, f64::INFINITY);
+        assert!(complementary_slackness_report(&raised.solve().unwrap(), 1e-9).unwrap().is_satisfied());
+        assert_eq!(complementary_slackness_report(&SolveResult::Infeasible, 1e-9), None);
+    }
+
+    #[test]
+    fn dual_values_are_the_objective_change_per_unit_of_rhs() {
+        let delta = 1e-4;
+        for (name, problem) in fixtures() {
+            let solution = expect_optimal(problem.solve().unwrap());
+            let duals = dual_values(&solution);
+            assert_eq!(duals.len(), problem.constraints.len(), "{}", name);
+            for (row, &dual) in duals.iter().enumerate() {
+                let mut moved = problem.clone();
+                moved.constraints[row].rhs += delta;
+                moved.constraints[row].lower = moved.constraints[row].lower.map(|lo| lo + delta);
+                let slope = (expect_optimal(moved.solve().unwrap()).objective - solution.objective) / delta;
+                assert!((slope - dual).abs() < 1e-6, "{} row {}: dual {} but the objective moves {} per unit", name, row, dual, slope);
+            }
+        }
+    }
+
+    #[test]
+    fn reduced_costs_line_up_with_x() {
+        for (name, problem) in fixtures() {
+            let solution = expect_optimal(problem.solve().unwrap());
+            let costs = reduced_costs(&solution);
+            assert_eq!(costs.len(), solution.x.len(), "{}", name);
+            //x1 and x2 are basic everywhere, x3 is held at 0
+            assert!(costs[0].abs() < 1e-9 && costs[1].abs() < 1e-9, "{}: {:?}", name, costs);
+            assert!(costs[2] > 1e-9, "{}: {:?}", name, costs);
+        }
+        //x3 costs 3 against the 1.5 the first row prices it at in the free problem
+        assert!((reduced_costs(&expect_optimal(free_var().solve().unwrap()))[2] - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn objective_ranges_keep_the_optimum() {
+        for (name, problem) in fixtures() {
+            let solution = expect_optimal(problem.solve().unwrap());
+            let ranges = objective_ranges(&solution);
+            assert_eq!(ranges.len(), solution.x.len(), "{}", name);
+            for (var, &(decrease, increase)) in ranges.iter().enumerate().take(problem.num_vars()) {
+                assert!(decrease >= 0.0 && increase >= 0.0, "{} x{}: {:?}", name, var + 1, ranges[var]);
+                for change in [-0.9 * decrease.min(10.0), 0.9 * increase.min(10.0)] {
+                    let moved = problem.clone().set_objective_coeff(var, problem.objective[var] + change);
+                    let x = expect_optimal(moved.solve().unwrap()).x;
+                    let same = x.iter().zip(&solution.x).take(problem.num_vars()).all(|(a, b)| (a - b).abs() < 1e-7);
+                    assert!(same, "{} x{} moved by {}: {:?} instead of {:?}", name, var + 1, change, x, solution.x);
+                }
+            }
+            //the nonbasic x3 can get cheaper forever but only dearer by its reduced cost
+            let d = reduced_costs(&solution)[2];
+            let expected = if problem.sense == Sense::Maximize { (f64::INFINITY, d) } else { (d, f64::INFINITY) };
+            assert_eq!(ranges[2].0.is_infinite(), expected.0.is_infinite(), "{}: {:?}", name, ranges[2]);
+            assert!((ranges[2].0.min(1e9) - expected.0.min(1e9)).abs() < 1e-9 && (ranges[2].1.min(1e9) - expected.1.min(1e9)).abs() < 1e-9, "{}: {:?}", name, ranges[2]);
+        }
+    }
+
+    #[test]
+    fn textbook_shadow_prices() {
+        //wyndor's are 0, 3/2 and 1, x3 doesn't change them
+        assert_eq!(dual_values(&expect_optimal(wyndor_x3().solve().unwrap())).to_vec(), vec![0.0, 1.5, 1.0]);
+        //min 2x1 + 3x2 st x1 + x2 >= 4, x1 + 3x2 >= 6: y solves y1 + y2 = 2, y1 + 3y2 = 3 at (3, 1)
+        let covering = LpProblem::new()
+            .objective(vec![2.0, 3.0])
+            .add_constraint(vec![1.0, 1.0], Relation::Ge, 4.0)
+            .add_constraint(vec![1.0, 3.0], Relation::Ge, 6.0)
+            .minimize();
+        let duals = dual_values(&expect_optimal(covering.solve().unwrap()));
+        assert!((duals[0] - 1.5).abs() < 1e-9 && (duals[1] - 0.5).abs() < 1e-9, "{:?}", duals);
+        //strong duality, b·y is the objective
+        assert!((4.0 * duals[0] + 6.0 * duals[1] - 9.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reduced_costs_are_nonnegative_at_an_optimum() {
+        for seed in 0..30 {
+            let (problem, _) = crate::test_support::random_feasible_lp(seed, 5, 6);
+            let problem = if seed % 2 == 1 { LpProblem { objective: problem.objective.iter().map(|c| -c).collect(), ..problem }.minimize() } else { problem };
+            let solution = expect_optimal(problem.solve().unwrap());
+            let costs = reduced_costs(&solution);
+            assert!(costs.iter().all(|&cost| cost >= -1e-9), "seed {}: {:?}", seed, costs);
+            assert!(solution.basis.iter().filter(|&&col| col < costs.len()).all(|&col| costs[col] == 0.0));
+        }
+        //x2 <= 5 as a bound holds x2 at its upper end, whose reduced cost is turned back to <= 0
+        let capped = expect_optimal(wyndor_x3().bound(1, 0.0, 5.0).solve().unwrap());
+        assert!(capped.at_upper[1]);
+        assert!(reduced_costs(&capped)[1] < -1e-9);
+    }
+
+    #[test]
+    fn hand_computed_objective_ranges() {
+        //wyndor's c1 = 3 can go from 0 to 7.5 and c2 = 5 from 2 up, before another vertex takes over
+        let plain = LpProblem::new()
+            .objective(vec![3.0, 5.0])
+            .add_constraint(vec![1.0, 0.0], Relation::Le, 4.0)
+            .add_constraint(vec![0.0, 2.0], Relation::Le, 12.0)
+            .add_constraint(vec![3.0, 2.0], Relation::Le, 18.0)
+            .maximize();
+        let ranges = objective_ranges(&expect_optimal(plain.solve().unwrap()));
+        assert!((ranges[0].0 - 3.0).abs() < 1e-9 && (ranges[0].1 - 4.5).abs() < 1e-9, "{:?}", ranges);
+        assert!((ranges[1].0 - 3.0).abs() < 1e-9 && ranges[1].1.is_infinite(), "{:?}", ranges);
+        //and just past an end the optimum moves
+        let moved = expect_optimal(plain.clone().set_objective_coeff(0, 7.6).solve().unwrap());
+        assert!((moved.x[0] - 4.0).abs() < 1e-9, "{:?}", moved.x);
+    }
+
+    #[test]
+    fn rhs_ranges_keep_the_basis_inside_and_lose_it_outside() {
+        //b1 = 4 can drop by its slack of 2, b2 = 12 and b3 = 18 can both move by 6
+        let solution = expect_optimal(wyndor_x3().solve().unwrap());
+        let ranges = rhs_ranges(&solution);
+        let expected = [(2.0, f64::INFINITY), (6.0, 6.0), (6.0, 6.0)];
+        for (row, (&(decrease, increase), (dec, inc))) in ranges.iter().zip(expected).enumerate() {
+            assert!((decrease - dec).abs() < 1e-9 && (increase.min(1e9) - inc.min(1e9)).abs() < 1e-9, "row {}: {:?}", row, ranges[row]);
+        }
+        let basis_at = |row: usize, delta: f64| {
+            let mut moved = wyndor_x3();
+            moved.constraints[row].rhs += delta;
+            let mut basis = expect_optimal(moved.solve().unwrap()).basis;
+            basis.sort_unstable();
+            basis
+        };
+        let mut basis = solution.basis.clone();
+        basis.sort_unstable();
+        for (row, &(decrease, increase)) in ranges.iter().enumerate() {
+            assert_eq!(basis_at(row, -0.9 * decrease), basis, "row {}", row);
+            assert_eq!(basis_at(row, 0.9 * increase.min(100.0)), basis, "row {}", row);
+            assert_ne!(basis_at(row, -1.1 * decrease), basis, "row {}", row);
+            if increase.is_finite() {
+                assert_ne!(basis_at(row, 1.1 * increase), basis, "row {}", row);
+            }
+        }
+    }
+
+    #[test]
+    fn a_corrupted_solution_breaks_complementary_slackness() {
+        //wyndor's second row has dual 1.5, claiming slack on it breaks the pair
+        let mut solution = expect_optimal(wyndor_x3().solve().unwrap());
+        solution.x[4] = 1.0; //its slack, after x1, x2, x3 and s1
+        let report = complementary_slackness_report(&SolveResult::Optimal(Box::new(solution)), 1e-9).unwrap();
+        let violated: Vec<usize> = report.constraints.iter().filter(|pair| pair.violated).map(|pair| pair.index).collect();
+        assert_eq!(violated, vec![1]);
+        //and as a column s2 is off its bound against a reduced cost of 1.5
+        let violated: Vec<usize> = report.variables.iter().filter(|pair| pair.violated).map(|pair| pair.index).collect();
+        assert_eq!(violated, vec![4]);
+
+        //x3 is nonbasic with reduced cost 0.5, moving it off its bound breaks its pair
+        let mut solution = expect_optimal(wyndor_x3().solve().unwrap());
+        solution.x[2] = 1.0;
+        let report = complementary_slackness_report(&SolveResult::Optimal(Box::new(solution)), 1e-9).unwrap();
+        let violated: Vec<(usize, f64, f64)> = report.variables.iter().filter(|pair| pair.violated).map(|pair| (pair.index, pair.primal, pair.dual)).collect();
+        assert_eq!(violated, vec![(2, 1.0, 0.5)]);
+
+        //a dual value on the first row, which has 2 of slack left
+        let mut solution = expect_optimal(wyndor_x3().solve().unwrap());
+        let last_row = solution.tableau.nrows() - 1;
+        solution.tableau[[last_row, 3]] = 1.0;
+        let report = complementary_slackness_report(&SolveResult::Optimal(Box::new(solution)), 1e-9).unwrap();
+        let violated: Vec<(usize, f64, f64)> = report.violations().map(|pair| (pair.index, pair.primal, pair.dual)).collect();
+        assert_eq!(violated, vec![(0, 2.0, 1.0)]);
+    }
+}
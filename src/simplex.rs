@@ -0,0 +1,2527 @@
+use ndarray::prelude::*;
+use ndarray::Array1;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::cmp::Ordering;
+use core::fmt;
+use core::time::Duration;
+
+use crate::problem::solve;
+use crate::scalar::Scalar;
+
+//relation of a constraint row to its rhs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Relation {
+    Le, //a·x <= b
+    Ge, //a·x >= b
+    Eq, //a·x = b
+}
+
+//direction of the optimization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Sense {
+    #[default]
+    Maximize,
+    Minimize,
+}
+
+//optimal vertex found by the solver
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Solution<T = f64> {
+    pub x: Array1<T>,
+    pub objective: T,
+    pub iterations: usize, //pivots it took to get there
+    pub nodes: usize,      //branch and bound nodes whose relaxation got solved, 0 unless it came from solve_integer
+    pub solve_time: Duration, //wall-clock time of the solve, always zero without the std feature
+    pub basis: Vec<usize>, //basic variable of each constraint row, an index past x is a leftover artificial of a redundant row
+    pub num_vars: usize,        //structural columns at the front of x, the rest are slack/surplus
+    pub var_names: Vec<String>, //names of the leading structural columns of x, empty unless the problem had them
+    pub sense: Sense,           //minimized problems were solved as max -c·x
+    pub tableau: Array2<T>,     //final tableau, its objective row holds the reduced costs
+    pub costs: Array1<T>,       //objective coeff of every tableau column in the maximized form
+    pub unit_columns: Vec<usize>, //column that started as row i's identity column, B⁻¹ ends up under these
+    pub row_signs: Vec<T>,      //-1 for rows that were negated to get a nonnegative rhs
+    pub at_upper: Vec<bool>,    //column was complemented, the tableau column holds u_j - x_j
+    pub upper_bounds: Vec<Option<T>>, //hi - lo of every column of x, None if it has no upper bound
+    pub lower_bounds: Vec<T>,   //lo of every column of x, the tableau holds x_j - lo_j
+    pub alternate_optima: Vec<usize>, //nonbasic columns of x with a zero reduced cost, each one leads to another optimal vertex
+    pub degenerate: Vec<usize>, //basic columns of x sitting at 0 (or at their bound), empty if the vertex isn't degenerate
+    pub binding_constraints: Vec<usize>, //rows whose slack is 0 within epsilon, hard = rows always are
+    pub soft_violations: Vec<T>, //how far each row ended up violated, 0 for hard rows, empty unless the problem had soft ones
+    pub history: Vec<TableauSnapshot<T>>, //starting tableau and one per iteration, empty unless options.record_history
+    pub log: Vec<IterationLog<T>>, //one entry per iteration, empty unless options.record_log
+    pub pivot_sizes: Option<(T, T)>, //smallest and largest |pivot element| over the iterations, None if there were none
+    pub rewrite: Option<Rewrite<T>>, //how x sits on the tableau columns when solve_with had to rewrite the problem, None when they line up
+}
+
+//what solve_with actually solved when it rewrote the problem first: free vars split into x⁺ - x⁻, ranged rows made
+//= rows with a range column, soft rows given violation columns. the tableau and every per-column field of the
+//solution (basis, costs, at_upper, alternate_optima, ...) follow the columns of that problem, x and the per-row
+//fields follow the problem as given
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Rewrite<T = f64> {
+    pub x: Array1<T>,    //x of the rewritten problem, one entry per tableau column before the artificials
+    pub num_vars: usize, //structural columns at the front of it
+    pub columns: Vec<Vec<(usize, T)>>, //every column of x as (column of the rewritten x, coeff) terms, x_j is their sum
+    pub violations: Vec<Vec<(usize, T)>>, //the same for soft_violations, one entry per row, empty without soft rows
+}
+
+//tableau right after one iteration of the pivot loop
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TableauSnapshot<T = f64> {
+    pub tableau: Array2<T>,
+    pub pivot_row: Option<usize>, //None for the starting tableau and for a bound flip, which doesn't pivot
+    pub pivot_col: Option<usize>, //None for the starting tableau
+}
+
+//what one iteration of the pivot loop did, the columns use the [structural | slack/surplus | artificial] layout
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct IterationLog<T = f64> {
+    pub iteration: usize,         //1 for the first pivot, counted across phases like Solution::iterations
+    pub entering: usize,          //column that entered the basis, or was flipped to its other bound
+    pub leaving: Option<usize>,   //column that left the basis, None for a bound flip
+    pub pivot_row: Option<usize>, //None for a bound flip
+    pub ratio: T,                 //the minimum ratio, how far the entering var moved
+    pub objective: T,             //tableau objective value after the step, maximized form, -(sum of artificials) in phase one
+}
+
+//outcome of a solve
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SolveResult<T = f64> {
+    Optimal(Box<Solution<T>>),
+    Unbounded,
+    Infeasible,
+    IterationLimit(Array2<T>), //tableau at the point the limit was hit
+}
+
+//errors that stop the solver before it reaches an outcome
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolverError {
+    ZeroPivot { row: usize, col: usize }, //the chosen pivot element was zero
+    DimensionMismatch { expected: usize, found: usize }, //c, b or relations don't match the shape of a
+    NotDualFeasible { col: usize }, //the dual simplex was handed a tableau with a negative reduced cost
+    NoStartingBasis, //solve_tableau got a tableau without a unit column for every row, or with a negative rhs
+    NonFiniteInput { location: InputLocation }, //a NaN or infinity in c, a or b
+    NumericalBreakdown { iteration: usize }, //the tableau picked up a NaN or infinity while pivoting
+}
+
+//where in the input a bad value sits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputLocation {
+    Objective { col: usize },
+    Constraint { row: usize, col: usize },
+    Rhs { row: usize },
+}
+
+impl fmt::Display for InputLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputLocation::Objective { col } => write!(f, "objective coeff {}", col),
+            InputLocation::Constraint { row, col } => write!(f, "constraint row {}, column {}", row, col),
+            InputLocation::Rhs { row } => write!(f, "rhs of row {}", row),
+        }
+    }
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::ZeroPivot { row, col } => write!(f, "pivot value at ({}, {}) is zero, cannot divide", row, col),
+            SolverError::DimensionMismatch { expected, found } => {
+                write!(f, "input dimensions don't match: expected length {}, found {}", expected, found)
+            }
+            SolverError::NotDualFeasible { col } => write!(f, "column {} has a negative reduced cost, the basis isn't dual feasible", col),
+            SolverError::NoStartingBasis => write!(f, "the tableau has no feasible identity basis to start from"),
+            SolverError::NonFiniteInput { location } => write!(f, "{} is NaN or infinite", location),
+            SolverError::NumericalBreakdown { iteration } => write!(f, "the tableau became NaN or infinite at iteration {}", iteration),
+        }
+    }
+}
+
+impl core::error::Error for SolverError {}
+
+//rule for choosing the entering and leaving variables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PivotRule {
+    #[default]
+    Dantzig,       //most negative reduced cost
+    Bland,         //lowest index, never cycles
+    SteepestEdge,  //most negative reduced cost per unit length of the edge, d_j / sqrt(1 + |column j|²)
+    Devex,         //approximate steepest edge, the edge lengths are reference weights updated from the pivot row
+    Lexicographic, //most negative reduced cost, ratio ties broken by comparing the tied rows / pivot entry, never cycles
+}
+
+//how ties between equally good entering columns, or leaving rows, are settled. Bland and the lexicographic ratio test
+//settle their own ties, that's what keeps them from cycling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    #[default]
+    LowestIndex,         //the first one in column / row order, every run takes the same pivots
+    Random { seed: u64 }, //a pseudo-random one, the same seed gives the same pivots
+}
+
+//rule for choosing the variable solve_integer_with branches on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Branching {
+    #[default]
+    MostFractional, //the fractional part closest to 0.5
+    Strong { candidates: usize }, //solve both children of this many most fractional vars, take the one that worsens both the most
+}
+
+//settings shared by all the solver entry points
+#[derive(Debug, Clone)]
+pub struct SolverOptions {
+    pub pivot_rule: PivotRule,
+    pub max_iterations: Option<usize>,     //None means 50 * (vars + constraints)
+    pub epsilon: f64,                      //tolerance for every float comparison against 0 or 1
+    pub record_history: bool,              //keep a copy of the tableau after every iteration in Solution::history
+    pub record_log: bool,                  //keep an IterationLog of every iteration in Solution::log
+    pub verbose: bool,                     //trace every iteration to stderr, off by default so library use stays silent, ignored without std
+    pub refactor_frequency: Option<usize>, //revised simplex recomputes B⁻¹ from the basis columns after this many pivots, None never does
+    pub partial_pricing: Option<usize>,    //tableau solvers price a rotating block of this many columns first, None prices all of them
+    pub branching: Branching,              //variable selection of branch and bound
+    pub tie_break: TieBreak,               //which of several tied entering columns or leaving rows is taken
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            pivot_rule: PivotRule::default(),
+            max_iterations: None,
+            epsilon: 1e-9,
+            record_history: false,
+            record_log: false,
+            verbose: false,
+            refactor_frequency: Some(100),
+            partial_pricing: None,
+            branching: Branching::default(),
+            tie_break: TieBreak::default(),
+        }
+    }
+}
+
+//upper bound of every tableau column (None if it has none), a flipped column holds u_j - x_j instead of x_j
+#[derive(Debug, Clone)]
+pub(crate) struct ColumnBounds<T> {
+    upper: Vec<Option<T>>,
+    flipped: Vec<bool>,
+    frozen: Vec<bool>, //held where it is by an earlier stage of simplex_solver_staged
+}
+
+impl<T: Scalar> ColumnBounds<T> {
+    pub(crate) fn unbounded(num_cols: usize) -> Self {
+        ColumnBounds { upper: vec![None; num_cols], flipped: vec![false; num_cols], frozen: vec![false; num_cols] }
+    }
+
+    //bounds of a solved tableau, only the x columns can have one
+    pub(crate) fn of_solution(solution: &Solution<T>) -> Self {
+        let mut bounds = ColumnBounds::unbounded(solution.tableau.ncols() - 1);
+        bounds.upper.splice(..solution.upper_bounds.len(), solution.upper_bounds.iter().cloned());
+        bounds.flipped.splice(..solution.at_upper.len(), solution.at_upper.iter().copied());
+        bounds
+    }
+
+    pub(crate) fn upper(&self, col: usize) -> Option<&T> {
+        self.upper[col].as_ref()
+    }
+
+    pub(crate) fn is_flipped(&self, col: usize) -> bool {
+        self.flipped[col]
+    }
+
+    //lo == hi or frozen, the column can't move off its bound so it's never worth entering
+    pub(crate) fn is_fixed(&self, col: usize) -> bool {
+        self.frozen[col] || self.upper[col].as_ref().is_some_and(|upper| upper.is_zero())
+    }
+
+    //turns column values read off the tableau back into x
+    pub(crate) fn unflip(&self, mut values: Array1<T>) -> Array1<T> {
+        for (j, val) in values.iter_mut().enumerate() {
+            if let (true, Some(upper)) = (self.flipped[j], &self.upper[j]) {
+                *val = upper.clone() - val.clone();
+            }
+        }
+        values
+    }
+}
+
+//what the ratio test decided
+pub(crate) enum Step {
+    Pivot(usize),        //basic var of the row leaves at 0
+    PivotAtUpper(usize), //basic var of the row leaves at its upper bound
+    Flip,                //entering var hits its own upper bound before any basic var blocks it
+}
+
+//how the pivot loop stopped
+pub(crate) enum LoopExit {
+    Optimal,
+    Unbounded,
+    IterationLimit,
+}
+
+pub fn simplex_solver<T: Scalar>(
+    c: Array1<T>,     //objective function coeffs, a shorter c gets zeros for the remaining (slack) columns of a
+    a: &Array2<T>,    //constraint coeffs
+    b: &Array1<T>,    //RHS values
+    options: &SolverOptions,
+) -> Result<SolveResult<T>, SolverError> {
+    let c = if c.len() < a.ncols() { pad_objective(c, a.ncols()) } else { c };
+    check_dimensions(&c, a, b, None)?;
+    let num_constraints = a.nrows();
+    let num_vars = a.ncols();
+    let mut tableau = Array2::<T>::zeros((num_constraints + 1, num_vars + 1));
+
+    //tableau init
+    tableau.slice_mut(s![..-1, ..num_vars]).assign(a);
+    tableau.slice_mut(s![..-1, -1]).assign(b);
+    tableau.slice_mut(s![-1, ..num_vars]).assign(&c.mapv(|val| -val));
+
+    //the slack columns in a give the starting basis, without them every row is an equality that needs phase one
+    //so does a negative rhs, the slack basis would start out at an infeasible point
+    if identity_basis(&tableau).is_none() || b.iter().any(|val| val.is_negative()) {
+        return simplex_solver_two_phase(c, a, b, &vec![Relation::Eq; num_constraints], options);
+    }
+    solve_tableau(&mut tableau, options).map(|(result, _)| result)
+}
+
+//the pivot loop on a tableau built by hand: constraint rows on top, the objective row (-c, so max c·x) last,
+//the rhs in the last column. every row needs a unit column and a nonnegative rhs to start from, the objective
+//row gets priced out over that basis first. the tableau is left as the loop leaves it, the basis is returned
+//along with the result, for Unbounded and IterationLimit too. the costs of the solution are read off the
+//starting objective row
+pub fn solve_tableau<T: Scalar>(tableau: &mut Array2<T>, options: &SolverOptions) -> Result<(SolveResult<T>, Vec<usize>), SolverError> {
+    let timer = Timer::start();
+    let last_row_index = tableau.nrows() - 1;
+    let num_cols = tableau.ncols() - 1;
+    let mut basis = identity_basis(tableau).ok_or(SolverError::NoStartingBasis)?;
+    if tableau.slice(s![..last_row_index, num_cols]).iter().any(|val| val.is_negative()) {
+        return Err(SolverError::NoStartingBasis);
+    }
+    let costs = tableau.slice(s![last_row_index, ..num_cols]).mapv(|val| T::zero() - val);
+    price_out(tableau, &basis);
+    let unit_columns = basis.clone();
+    let mut column_bounds = ColumnBounds::unbounded(num_cols);
+    let mut trace = Trace::new();
+    trace.record(options, tableau, None, None);
+
+    let exit = run_simplex(tableau, &mut basis, &mut column_bounds, options, num_cols, &mut trace)?;
+    if let Some(result) = stopped(exit, tableau) {
+        return Ok((result, basis));
+    }
+
+    let eps = epsilon(options);
+    let x = basic_values(tableau, &basis);
+    let objective = tableau[[last_row_index, num_cols]].clone();
+    let alternate_optima = zero_reduced_costs(tableau, &basis, num_cols, &eps);
+    let degenerate = degenerate_columns(tableau, &basis, num_cols, &eps);
+    let binding_constraints = binding_rows(tableau, &unit_columns, num_cols, &x, &eps);
+    let solution = Solution {
+        x,
+        objective,
+        iterations: trace.iterations,
+        nodes: 0,
+        solve_time: timer.elapsed(),
+        basis: basis.clone(),
+        num_vars: num_cols,
+        var_names: Vec::new(),
+        sense: Sense::Maximize,
+        tableau: tableau.clone(),
+        costs,
+        unit_columns,
+        row_signs: vec![T::one(); last_row_index],
+        at_upper: vec![false; num_cols],
+        upper_bounds: vec![None; num_cols],
+        lower_bounds: vec![T::zero(); num_cols],
+        alternate_optima,
+        degenerate,
+        binding_constraints,
+        soft_violations: Vec::new(),
+        rewrite: None,
+        history: trace.history,
+        log: trace.log,
+        pivot_sizes: trace.pivot_sizes,
+    };
+    Ok((SolveResult::Optimal(Box::new(solution)), basis))
+}
+
+//c extended with zeros to `len` entries
+fn pad_objective<T: Scalar>(c: Array1<T>, len: usize) -> Array1<T> {
+    let mut padded = Array1::zeros(len);
+    padded.slice_mut(s![..c.len()]).assign(&c);
+    padded
+}
+
+//minimizes c·x by maximizing -c·x, the objective is reported back in minimization units
+pub fn simplex_solver_min<T: Scalar>(
+    c: Array1<T>,
+    a: &Array2<T>,
+    b: &Array1<T>,
+    options: &SolverOptions,
+) -> Result<SolveResult<T>, SolverError> {
+    Ok(flip_objective(simplex_solver(-c, a, b, options)?))
+}
+
+//negates the reported objective of a problem that was solved with -c
+pub(crate) fn flip_objective<T: Scalar>(result: SolveResult<T>) -> SolveResult<T> {
+    match result {
+        SolveResult::Optimal(mut solution) => {
+            solution.objective = -solution.objective.clone();
+            solution.sense = Sense::Minimize;
+            SolveResult::Optimal(solution)
+        }
+        other => other,
+    }
+}
+
+//two-phase method for problems with >= and = rows, a only holds the structural columns
+//the returned solution has the structural vars followed by one slack/surplus var for each <= and >= row
+pub fn simplex_solver_two_phase<T: Scalar>(
+    c: Array1<T>,
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    options: &SolverOptions,
+) -> Result<SolveResult<T>, SolverError> {
+    check_dimensions(&c, a, b, Some(relations))?;
+    two_phase(c, a, b, relations, VariableBounds::none(a.ncols()), None, options)
+}
+
+//two-phase method started from a basis, e.g. Solution::basis of a similar problem solved before
+//`start` uses the [structural | slack/surplus | artificial] column layout, phase one is skipped when it is a feasible basis
+//if it isn't one, or the vertex it gives is infeasible, this solves from scratch like simplex_solver_two_phase
+pub fn simplex_solver_warm<T: Scalar>(
+    c: Array1<T>,
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    start: &[usize],
+    options: &SolverOptions,
+) -> Result<SolveResult<T>, SolverError> {
+    check_dimensions(&c, a, b, Some(relations))?;
+    two_phase(c, a, b, relations, VariableBounds::none(a.ncols()), Some(start), options)
+}
+
+//any point satisfying the constraints and x >= 0: phase one of the two-phase method with a zero objective,
+//so phase two has nothing to improve. only the structural vars are returned, None if the rows can't all hold
+//or phase one ran into the iteration limit
+pub fn find_feasible<T: Scalar>(
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    options: &SolverOptions,
+) -> Result<Option<Array1<T>>, SolverError> {
+    let c = Array1::zeros(a.ncols());
+    check_dimensions(&c, a, b, Some(relations))?;
+    Ok(match two_phase(c, a, b, relations, VariableBounds::none(a.ncols()), None, options)? {
+        SolveResult::Optimal(solution) => Some(solution.x.slice(s![..a.ncols()]).to_owned()),
+        _ => None,
+    })
+}
+
+//one two-phase solve per objective over the same a, b and relations, results in the order of `objectives`
+//every solve after the first starts from the last optimal basis, so phase one normally runs only once
+//and an infeasible region is infeasible for all of them
+pub fn solve_many<T: Scalar>(
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    objectives: &[Array1<T>],
+    options: &SolverOptions,
+) -> Result<Vec<SolveResult<T>>, SolverError> {
+    let mut results = Vec::with_capacity(objectives.len());
+    let mut start: Option<Vec<usize>> = None;
+    for c in objectives {
+        check_dimensions(c, a, b, Some(relations))?;
+        let result = two_phase(c.clone(), a, b, relations, VariableBounds::none(a.ncols()), start.as_deref(), options)?;
+        match &result {
+            SolveResult::Optimal(solution) => start = Some(solution.basis.clone()),
+            SolveResult::Infeasible => {
+                results.resize_with(objectives.len(), || SolveResult::Infeasible);
+                return Ok(results);
+            }
+            _ => {}
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+//two-phase method with lo_j <= x_j <= hi_j on the structural variables, missing entries default to (0, inf)
+//lower bounds are shifted out of the problem, upper bounds are handled by the ratio test instead of extra rows
+pub fn simplex_solver_bounded(
+    c: Array1<f64>,
+    a: &Array2<f64>,
+    b: &Array1<f64>,
+    relations: &[Relation],
+    bounds: &[(f64, f64)],
+    options: &SolverOptions,
+) -> Result<SolveResult, SolverError> {
+    bounded_from(c, a, b, relations, bounds, None, options)
+}
+
+//simplex_solver_bounded trying `start` first, like simplex_solver_warm. a vertex breaking an upper bound counts as
+//infeasible, and the split for vars without a lower bound always solves from scratch
+pub(crate) fn bounded_from(
+    c: Array1<f64>,
+    a: &Array2<f64>,
+    b: &Array1<f64>,
+    relations: &[Relation],
+    bounds: &[(f64, f64)],
+    start: Option<&[usize]>,
+    options: &SolverOptions,
+) -> Result<SolveResult, SolverError> {
+    check_dimensions(&c, a, b, Some(relations))?;
+    if bounds.len() > a.ncols() {
+        return Err(SolverError::DimensionMismatch { expected: a.ncols(), found: bounds.len() });
+    }
+    //lo > hi leaves a var nothing to take, tableau columns can't have a negative width
+    if bounds.iter().any(|&(lo, hi)| lo > hi) {
+        return Ok(SolveResult::Infeasible);
+    }
+    //a var without a lower bound can't be shifted out, it gets split into x⁺ - x⁻ so each half has a finite one
+    //and the ratio test sees the direction it can run off in
+    if bounds.iter().any(|&(lo, _)| lo == f64::NEG_INFINITY) {
+        return solve(&c, a, b, relations, bounds, Sense::Maximize, options);
+    }
+    let bound = |j: usize| bounds.get(j).copied().unwrap_or((0.0, f64::INFINITY));
+    let bounds = VariableBounds {
+        lower: (0..a.ncols()).map(|j| bound(j).0).collect(),
+        width: (0..a.ncols()).map(|j| Some(bound(j).1 - bound(j).0).filter(|width| width.is_finite())).collect(),
+    };
+    two_phase(c, a, b, relations, bounds, start, options)
+}
+
+//lower bound and hi - lo of every structural variable, None for the ones without an upper bound
+pub(crate) struct VariableBounds<T> {
+    pub(crate) lower: Array1<T>,
+    pub(crate) width: Vec<Option<T>>,
+}
+
+impl<T: Scalar> VariableBounds<T> {
+    fn none(num_vars: usize) -> Self {
+        VariableBounds { lower: Array1::zeros(num_vars), width: vec![None; num_vars] }
+    }
+}
+
+//shared by the two-phase entry points
+pub(crate) fn two_phase<T: Scalar>(
+    c: Array1<T>,
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    bounds: VariableBounds<T>,
+    start: Option<&[usize]>,
+    options: &SolverOptions,
+) -> Result<SolveResult<T>, SolverError> {
+    Ok(staged(&[(c, Sense::Maximize)], a, b, relations, bounds, start, options)?.0)
+}
+
+//what one stage of simplex_solver_staged reached
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageResult<T = f64> {
+    pub objective: T,      //the stage's c·x when it finished
+    pub basis: Vec<usize>, //basis it finished with, the next stage starts from it
+    pub iterations: usize, //pivots of this stage alone
+}
+
+//lexicographic solve in one run: phase one, then a phase two per (objective, sense) stage, each one carrying on from
+//the basis the stage before finished with. once a stage is optimal its nonbasic columns with a nonzero reduced cost
+//are frozen, so the later stages only move along its optimal face and it keeps the value it reached. the solution is
+//the last stage's, its objective and sense included, with what every stage reached alongside. a stage that isn't
+//optimal stops the run, with the stages before it
+pub fn simplex_solver_staged<T: Scalar>(
+    stages: &[(Array1<T>, Sense)],
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    options: &SolverOptions,
+) -> Result<(SolveResult<T>, Vec<StageResult<T>>), SolverError> {
+    for (c, _) in stages {
+        check_dimensions(c, a, b, Some(relations))?;
+    }
+    staged(stages, a, b, relations, VariableBounds::none(a.ncols()), None, options)
+}
+
+//phase one (skipped for a feasible warm start), then the stages, two_phase is a single maximized one
+fn staged<T: Scalar>(
+    stages: &[(Array1<T>, Sense)],
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    bounds: VariableBounds<T>,
+    start: Option<&[usize]>,
+    options: &SolverOptions,
+) -> Result<(SolveResult<T>, Vec<StageResult<T>>), SolverError> {
+    let timer = Timer::start();
+    let num_vars = a.ncols();
+    let eps = epsilon::<T>(options);
+    let VariableBounds { lower, width } = bounds;
+    let shift: Array1<T> = a.rows().into_iter().map(|row| dot(row, lower.view())).collect();
+    let b = b - &shift;
+
+    let (mut tableau, mut basis, first_artificial) = standard_tableau(a, &b, relations);
+    let last_row_index = tableau.nrows() - 1;
+    let last_col_index = tableau.ncols() - 1;
+    let unit_columns = basis.clone();
+    let mut column_bounds = ColumnBounds::unbounded(last_col_index);
+    column_bounds.upper.splice(..num_vars, width);
+    let mut trace = Trace::new();
+    let mut results = Vec::with_capacity(stages.len());
+
+    let warm = start.and_then(|start| install_basis(&tableau, start, first_artificial, &eps)).filter(|(warm_tableau, warm_basis)| {
+        warm_basis.iter().enumerate().all(|(row, &col)| {
+            column_bounds.upper(col).is_none_or(|upper| warm_tableau[[row, last_col_index]] <= upper.clone() + eps.clone())
+        })
+    });
+    let warm_started = warm.is_some();
+    if let Some((warm_tableau, warm_basis)) = warm {
+        tableau = warm_tableau;
+        basis = warm_basis;
+    } else {
+        crash_basis(&tableau, &mut basis, num_vars, first_artificial, &column_bounds);
+        //phase one: maximize -(sum of artificials)
+        tableau.slice_mut(s![last_row_index, first_artificial..last_col_index]).fill(T::one());
+        price_out(&mut tableau, &basis);
+        trace.record(options, &tableau, None, None);
+        let exit = run_simplex(&mut tableau, &mut basis, &mut column_bounds, options, last_col_index, &mut trace)?;
+        if let Some(result) = stopped(exit, &tableau) {
+            return Ok((result, results));
+        }
+
+        if tableau[[last_row_index, last_col_index]] < -eps.clone() {
+            return Ok((SolveResult::Infeasible, results));
+        }
+    }
+    drive_out_artificials(&mut tableau, &mut basis, first_artificial, &eps)?;
+
+    //x in terms of the problem as given, complemented columns undone and the lower bounds added back
+    let current_x = |tableau: &Array2<T>, basis: &[usize], column_bounds: &ColumnBounds<T>| {
+        let mut x = column_bounds.unflip(basic_values(tableau, basis)).slice(s![..first_artificial]).to_owned();
+        x.slice_mut(s![..num_vars]).zip_mut_with(&lower, |val, lo| *val += lo.clone());
+        x
+    };
+
+    //phase two per stage: its objective in maximized form, artificials stay in the tableau but can't enter anymore
+    //flipped columns hold u_j - x_j, so their cost changes sign and c_j·u_j moves into the objective value
+    let mut costs = Array1::zeros(last_col_index);
+    let mut sense = Sense::Maximize;
+    for (k, (c, stage_sense)) in stages.iter().enumerate() {
+        if k > 0 {
+            //the finished stage's objective can only get worse along a column with a nonzero reduced cost
+            for j in (0..first_artificial).filter(|j| !basis.contains(j)) {
+                if tableau[[last_row_index, j]] > eps.clone() {
+                    column_bounds.frozen[j] = true;
+                }
+            }
+        }
+        let c = match stage_sense {
+            Sense::Maximize => c.clone(),
+            Sense::Minimize => c.mapv(|val| -val),
+        };
+        tableau.row_mut(last_row_index).fill(T::zero());
+        for (j, cost) in c.iter().enumerate() {
+            match (column_bounds.flipped[j], &column_bounds.upper[j]) {
+                (true, Some(upper)) => {
+                    tableau[[last_row_index, j]] = cost.clone();
+                    tableau[[last_row_index, last_col_index]] += cost.clone() * upper.clone();
+                }
+                _ => tableau[[last_row_index, j]] = -cost.clone(),
+            }
+        }
+        price_out(&mut tableau, &basis);
+        if warm_started || k > 0 {
+            trace.record(options, &tableau, None, None);
+        }
+        let iterations_before = trace.iterations;
+        let exit = run_simplex(&mut tableau, &mut basis, &mut column_bounds, options, first_artificial, &mut trace)?;
+        if let Some(result) = stopped(exit, &tableau) {
+            return Ok((result, results));
+        }
+
+        let x = current_x(&tableau, &basis, &column_bounds);
+        let value = dot(c.view(), x.slice(s![..c.len()]));
+        results.push(StageResult {
+            objective: if *stage_sense == Sense::Minimize { -value } else { value },
+            basis: basis.clone(),
+            iterations: trace.iterations - iterations_before,
+        });
+        costs.fill(T::zero());
+        costs.slice_mut(s![..c.len()]).assign(&c);
+        sense = *stage_sense;
+    }
+
+    let x = current_x(&tableau, &basis, &column_bounds);
+    let objective = results.last().map_or_else(T::zero, |result| result.objective.clone());
+    let alternate_optima = zero_reduced_costs(&tableau, &basis, first_artificial, &eps);
+    let degenerate = degenerate_columns(&tableau, &basis, first_artificial, &eps);
+    let binding_constraints = binding_rows(&tableau, &unit_columns, num_vars, &x, &eps);
+    let solution = Solution {
+        x,
+        objective,
+        iterations: trace.iterations,
+        nodes: 0,
+        solve_time: timer.elapsed(),
+        basis,
+        num_vars: stages.last().map_or(num_vars, |(c, _)| c.len()),
+        var_names: Vec::new(),
+        sense,
+        tableau,
+        costs,
+        unit_columns,
+        row_signs: row_signs(&b),
+        at_upper: column_bounds.flipped[..first_artificial].to_vec(),
+        upper_bounds: column_bounds.upper[..first_artificial].to_vec(),
+        lower_bounds: lower.iter().cloned().chain(core::iter::repeat(T::zero())).take(first_artificial).collect(),
+        alternate_optima,
+        degenerate,
+        binding_constraints,
+        soft_violations: Vec::new(),
+        rewrite: None,
+        history: trace.history,
+        log: trace.log,
+        pivot_sizes: trace.pivot_sizes,
+    };
+    Ok((SolveResult::Optimal(Box::new(solution)), results))
+}
+
+//big-M method: same problems as the two-phase solver, but the artificials are penalized by M in a single phase
+pub fn simplex_solver_big_m<T: Scalar>(
+    c: Array1<T>,
+    a: &Array2<T>,
+    b: &Array1<T>,
+    relations: &[Relation],
+    options: &SolverOptions,
+) -> Result<SolveResult<T>, SolverError> {
+    let timer = Timer::start();
+    check_dimensions(&c, a, b, Some(relations))?;
+    let eps = epsilon::<T>(options);
+    let (mut tableau, mut basis, first_artificial) = standard_tableau(a, b, relations);
+    let last_row_index = tableau.nrows() - 1;
+    let last_col_index = tableau.ncols() - 1;
+    let unit_columns = basis.clone();
+    let mut column_bounds = ColumnBounds::unbounded(last_col_index);
+    let mut trace = Trace::new();
+
+    //M has to dominate every real objective coeff
+    let largest = c.iter().map(|val| val.abs()).fold(T::one(), |max, val| if val > max { val } else { max });
+    let big_m = T::from_f64(1e4).unwrap_or_else(T::one) * largest;
+
+    tableau.slice_mut(s![last_row_index, ..c.len()]).assign(&c.mapv(|val| -val));
+    tableau.slice_mut(s![last_row_index, first_artificial..last_col_index]).fill(big_m.clone());
+    price_out(&mut tableau, &basis);
+    trace.record(options, &tableau, None, None);
+    let exit = run_simplex(&mut tableau, &mut basis, &mut column_bounds, options, last_col_index, &mut trace)?;
+    if let Some(result) = stopped(exit, &tableau) {
+        return Ok(result);
+    }
+
+    //an artificial still basic at a nonzero level means the constraints can't all hold
+    if basis.iter().enumerate().any(|(row, &col)| col >= first_artificial && tableau[[row, last_col_index]] > eps) {
+        return Ok(SolveResult::Infeasible);
+    }
+
+    //the objective row still carries M-sized rounding, so recompute the value from x
+    let x = basic_values(&tableau, &basis).slice(s![..first_artificial]).to_owned();
+    let objective = dot(c.view(), x.slice(s![..c.len()]));
+    let mut costs = Array1::from_elem(last_col_index, -big_m);
+    costs.slice_mut(s![..first_artificial]).fill(T::zero());
+    costs.slice_mut(s![..c.len()]).assign(&c);
+
+    let alternate_optima = zero_reduced_costs(&tableau, &basis, first_artificial, &eps);
+    let degenerate = degenerate_columns(&tableau, &basis, first_artificial, &eps);
+    let binding_constraints = binding_rows(&tableau, &unit_columns, a.ncols(), &x, &eps);
+    Ok(SolveResult::Optimal(Box::new(Solution {
+        x,
+        objective,
+        iterations: trace.iterations,
+        nodes: 0,
+        solve_time: timer.elapsed(),
+        basis,
+        num_vars: c.len(),
+        var_names: Vec::new(),
+        sense: Sense::Maximize,
+        tableau,
+        costs,
+        unit_columns,
+        row_signs: row_signs(b),
+        at_upper: vec![false; first_artificial],
+        upper_bounds: vec![None; first_artificial],
+        lower_bounds: vec![T::zero(); first_artificial],
+        alternate_optima,
+        degenerate,
+        binding_constraints,
+        soft_violations: Vec::new(),
+        rewrite: None,
+        history: trace.history,
+        log: trace.log,
+        pivot_sizes: trace.pivot_sizes,
+    })))
+}
+
+//c has to have one entry per column of a, b and relations one per row
+//the shapes first, then that every entry is finite, so a NaN can't reach the pivot comparisons
+pub(crate) fn check_dimensions<T: Scalar>(c: &Array1<T>, a: &Array2<T>, b: &Array1<T>, relations: Option<&[Relation]>) -> Result<(), SolverError> {
+    let lengths = [(a.ncols(), c.len()), (a.nrows(), b.len()), (a.nrows(), relations.map_or(a.nrows(), <[Relation]>::len))];
+    if let Some(&(expected, found)) = lengths.iter().find(|(expected, found)| expected != found) {
+        return Err(SolverError::DimensionMismatch { expected, found });
+    }
+    let location = if let Some(col) = c.iter().position(|val| !is_finite(val)) {
+        InputLocation::Objective { col }
+    } else if let Some(((row, col), _)) = a.indexed_iter().find(|(_, val)| !is_finite(*val)) {
+        InputLocation::Constraint { row, col }
+    } else if let Some(row) = b.iter().position(|val| !is_finite(val)) {
+        InputLocation::Rhs { row }
+    } else {
+        return Ok(());
+    };
+    Err(SolverError::NonFiniteInput { location })
+}
+
+//NaN - NaN and inf - inf are both NaN, which isn't zero. always true for exact types
+pub(crate) fn is_finite<T: Scalar>(val: &T) -> bool {
+    (val.clone() - val.clone()).is_zero()
+}
+
+//pivot until the objective row has no negative entry among the first `num_eligible` columns
+//trace.iterations keeps counting across calls so the limit covers every phase of a solve
+fn run_simplex<T: Scalar>(
+    tableau: &mut Array2<T>,
+    basis: &mut [usize],
+    bounds: &mut ColumnBounds<T>,
+    options: &SolverOptions,
+    num_eligible: usize,
+    trace: &mut Trace<T>,
+) -> Result<LoopExit, SolverError> {
+    let eps = epsilon::<T>(options);
+    let max_iterations = options.max_iterations.unwrap_or(50 * (tableau.nrows() + tableau.ncols() - 2));
+    let mut devex_weights = vec![T::one(); tableau.ncols() - 1]; //every phase starts from a fresh reference framework
+
+    //an improving column that nothing blocks is an unbounded ray already, no need to pivot towards it
+    let last_row_index = tableau.nrows() - 1;
+    let unbounded_ray = (0..num_eligible).any(|col| {
+        tableau[[last_row_index, col]] < -eps.clone() && !bounds.is_fixed(col) && find_pivot_row(tableau, col, last_row_index, basis, bounds, Selection::new(options, 0), &eps).is_none()
+    });
+    if unbounded_ray {
+        return Ok(LoopExit::Unbounded);
+    }
+
+    //partial pricing: every iteration looks at the next block of columns, wrapping around. Bland needs the lowest
+    //improving index over all columns to avoid cycling, so it always prices everything
+    let block_size = options.partial_pricing.filter(|&size| size > 0 && size < num_eligible && options.pivot_rule != PivotRule::Bland);
+    let mut block = Vec::with_capacity(block_size.unwrap_or(0));
+    let mut block_start = 0;
+
+    loop {
+        let last_row_index = tableau.nrows() - 1;
+        let candidates = block_size.map(|size| {
+            block.clear();
+            block.extend((block_start..block_start + size).map(|col| col % num_eligible));
+            block_start = (block_start + size) % num_eligible;
+            block.as_slice()
+        });
+
+        //find the pivot column idx, if there is none the solution is optimal
+        let selection = Selection::new(options, trace.iterations);
+        let pivot_col = match find_pivot_column(tableau, num_eligible, candidates, bounds, selection, &eps, &devex_weights) {
+            Some(col) => col,
+            None => return Ok(LoopExit::Optimal),
+        };
+
+        //find the pivot row
+        let step = match find_pivot_row(tableau, pivot_col, last_row_index, basis, bounds, selection, &eps) {
+            Some(step) => step,
+            None => return Ok(LoopExit::Unbounded), //no valid leaving variable, the caller reports it
+        };
+        if trace.iterations >= max_iterations {
+            return Ok(LoopExit::IterationLimit);
+        }
+        let ratio = step_ratio(tableau, basis, bounds, &step, pivot_col);
+        let leaving = match step {
+            Step::Pivot(row) | Step::PivotAtUpper(row) => Some((row, basis[row])),
+            Step::Flip => None,
+        };
+        if let Step::Pivot(row) | Step::PivotAtUpper(row) = &step {
+            trace.note_pivot(&tableau[[*row, pivot_col]]);
+            if options.pivot_rule == PivotRule::Devex {
+                update_devex_weights(&mut devex_weights, tableau.row(*row), pivot_col, basis[*row]);
+            }
+        }
+        let pivot_row = apply_step(tableau, basis, bounds, step, pivot_col)?;
+        trace.iterations += 1;
+        //a NaN anywhere spreads into the objective value within a pivot or two, checking it is enough to stop early
+        if !is_finite(&tableau[[last_row_index, tableau.ncols() - 1]]) {
+            return Err(SolverError::NumericalBreakdown { iteration: trace.iterations });
+        }
+        trace.record(options, tableau, pivot_row, Some(pivot_col));
+        trace.log(options, pivot_col, leaving, ratio, tableau[[last_row_index, tableau.ncols() - 1]].clone());
+        #[cfg(feature = "std")]
+        if options.verbose {
+            match pivot_row {
+                Some(row) => eprintln!("iteration {}: pivot on row {}, column {}", trace.iterations, row, pivot_col),
+                None => eprintln!("iteration {}: column {} flipped to its other bound", trace.iterations, pivot_col),
+            }
+            eprintln!("{:?}", tableau);
+        }
+    }
+}
+
+//devex update before pivoting on (pivot_row, entering): w_j = max(w_j, (α_rj / α_rq)² w_q), the leaving column gets max(w_q / α_rq², 1)
+pub(crate) fn update_devex_weights<T: Scalar>(weights: &mut [T], pivot_row: ArrayView1<T>, entering: usize, leaving: usize) {
+    let pivot = pivot_row[entering].clone();
+    let entering_weight = weights[entering].clone();
+    for (weight, alpha) in weights.iter_mut().zip(pivot_row.iter()) {
+        let ratio = alpha.clone() / pivot.clone();
+        let estimate = ratio.clone() * ratio * entering_weight.clone();
+        if estimate > *weight {
+            *weight = estimate;
+        }
+    }
+    let leaving_weight = entering_weight / (pivot.clone() * pivot);
+    weights[leaving] = if leaving_weight > T::one() { leaving_weight } else { T::one() };
+}
+
+//how far the entering var moves in a step of find_pivot_row
+fn step_ratio<T: Scalar>(tableau: &Array2<T>, basis: &[usize], bounds: &ColumnBounds<T>, step: &Step, pivot_col: usize) -> T {
+    let rhs_col = tableau.ncols() - 1;
+    match *step {
+        Step::Pivot(row) => tableau[[row, rhs_col]].clone() / tableau[[row, pivot_col]].clone(),
+        Step::PivotAtUpper(row) => {
+            let upper = bounds.upper(basis[row]).cloned().unwrap_or_else(T::zero);
+            (upper - tableau[[row, rhs_col]].clone()) / -tableau[[row, pivot_col]].clone()
+        }
+        Step::Flip => bounds.upper(pivot_col).cloned().unwrap_or_else(T::zero),
+    }
+}
+
+//carries out what the ratio test decided, returns the pivot row unless it was a bound flip
+fn apply_step<T: Scalar>(
+    tableau: &mut Array2<T>,
+    basis: &mut [usize],
+    bounds: &mut ColumnBounds<T>,
+    step: Step,
+    pivot_col: usize,
+) -> Result<Option<usize>, SolverError> {
+    match step {
+        Step::Pivot(pivot_row) => {
+            pivot_operation(tableau, pivot_row, pivot_col)?;
+            basis[pivot_row] = pivot_col;
+            Ok(Some(pivot_row))
+        }
+        Step::PivotAtUpper(pivot_row) => {
+            //the leaving var ends at its bound, so complement it first and it leaves at 0 like usual
+            complement_column(tableau, basis, bounds, basis[pivot_row]);
+            pivot_operation(tableau, pivot_row, pivot_col)?;
+            basis[pivot_row] = pivot_col;
+            Ok(Some(pivot_row))
+        }
+        Step::Flip => {
+            complement_column(tableau, basis, bounds, pivot_col);
+            Ok(None)
+        }
+    }
+}
+
+//measures Solution::solve_time, without std there is no clock to read
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Timer {
+    #[cfg(feature = "std")]
+    start: std::time::Instant,
+}
+
+impl Timer {
+    pub(crate) fn start() -> Self {
+        Timer {
+            #[cfg(feature = "std")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(feature = "std")]
+        return self.start.elapsed();
+        #[cfg(not(feature = "std"))]
+        return Duration::ZERO;
+    }
+}
+
+//what a solve collects along the way for Solution::iterations, history, log and pivot_sizes
+pub(crate) struct Trace<T> {
+    pub(crate) iterations: usize,
+    pub(crate) history: Vec<TableauSnapshot<T>>,
+    pub(crate) log: Vec<IterationLog<T>>,
+    pub(crate) pivot_sizes: Option<(T, T)>,
+}
+
+impl<T: Scalar> Trace<T> {
+    pub(crate) fn new() -> Self {
+        Trace { iterations: 0, history: Vec::new(), log: Vec::new(), pivot_sizes: None }
+    }
+
+    //adds a log entry for the iteration just counted when the caller asked for the log
+    pub(crate) fn log(&mut self, options: &SolverOptions, entering: usize, leaving: Option<(usize, usize)>, ratio: T, objective: T) {
+        if options.record_log {
+            self.log.push(IterationLog {
+                iteration: self.iterations,
+                entering,
+                leaving: leaving.map(|(_, col)| col),
+                pivot_row: leaving.map(|(row, _)| row),
+                ratio,
+                objective,
+            });
+        }
+    }
+
+    //adds a snapshot when the caller asked for the history
+    fn record(&mut self, options: &SolverOptions, tableau: &Array2<T>, pivot_row: Option<usize>, pivot_col: Option<usize>) {
+        if options.record_history {
+            self.history.push(TableauSnapshot { tableau: tableau.clone(), pivot_row, pivot_col });
+        }
+    }
+
+    pub(crate) fn note_pivot(&mut self, pivot: &T) {
+        let size = pivot.abs();
+        self.pivot_sizes = Some(match self.pivot_sizes.take() {
+            Some((smallest, largest)) => (
+                if size < smallest { size.clone() } else { smallest },
+                if size > largest { size } else { largest },
+            ),
+            None => (size.clone(), size),
+        });
+    }
+}
+
+//result to return early when the loop didn't reach an optimum
+fn stopped<T: Scalar>(exit: LoopExit, tableau: &Array2<T>) -> Option<SolveResult<T>> {
+    match exit {
+        LoopExit::Optimal => None,
+        LoopExit::Unbounded => Some(SolveResult::Unbounded),
+        LoopExit::IterationLimit => Some(SolveResult::IterationLimit(tableau.clone())),
+    }
+}
+
+//entering column among the first num_eligible ones. with candidates only those are priced at first, the rest only
+//once none of them improves, so None still means optimal
+pub(crate) fn find_pivot_column<T: Scalar>(
+    tableau: &Array2<T>,
+    num_eligible: usize,
+    candidates: Option<&[usize]>,
+    bounds: &ColumnBounds<T>,
+    selection: Selection,
+    eps: &T,
+    devex_weights: &[T],
+) -> Option<usize> {
+    let last_row_index = tableau.nrows() - 1;
+    let objective_row = tableau.row(last_row_index); //the last row holds the objective coeffs
+    let edge_weight = |col: usize| match selection.pivot_rule {
+        PivotRule::Devex => devex_weights[col].clone(),
+        _ => tableau.slice(s![..last_row_index, col]).iter().fold(T::one(), |sum, val| sum + val.clone() * val.clone()),
+    };
+    let movable = |col: &usize| *col < num_eligible && !bounds.is_fixed(*col);
+    if let Some(candidates) = candidates {
+        let entering = choose_entering(objective_row, candidates.iter().copied().filter(movable), selection, eps, edge_weight);
+        if entering.is_some() {
+            return entering;
+        }
+    }
+    choose_entering(objective_row, (0..num_eligible).filter(movable), selection, eps, edge_weight)
+}
+
+//entering column from the reduced costs, `edge_weight` gives the squared edge length (1 + |updated column|² or its devex estimate)
+//and is only called for the steepest edge rules. only the given columns are looked at, in the order given, and the
+//ones within eps of the best score count as tied
+pub(crate) fn choose_entering<T: Scalar>(
+    objective_row: ArrayView1<T>,
+    columns: impl Iterator<Item = usize>,
+    selection: Selection,
+    eps: &T,
+    edge_weight: impl Fn(usize) -> T,
+) -> Option<usize> {
+    let neg_eps = -eps.clone();
+    let mut candidates = columns
+        .map(|idx| (idx, &objective_row[idx])) //make it into (index, val) tuples
+        .filter(|&(_, val)| *val < neg_eps); //take only the negative values
+
+    //every candidate gets a score where higher is better
+    let scored: Vec<(usize, T)> = match selection.pivot_rule {
+        PivotRule::Bland => return candidates.next().map(|(idx, _)| idx), //lowest index with a negative value
+        PivotRule::Dantzig | PivotRule::Lexicographic => candidates.map(|(idx, val)| (idx, -val.clone())).collect(), //the most negative value
+        PivotRule::SteepestEdge | PivotRule::Devex => candidates
+            .map(|(idx, val)| (idx, val.clone() * val.clone() / edge_weight(idx))) //d_j² / (1 + |a_j|²), so no square root is needed
+            .collect(),
+    };
+    let best = scored.iter().map(|(_, score)| score).max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))?.clone();
+    let first = scored[0].0; //a NaN score ties with nothing, then the first candidate is taken
+    let tied: Vec<usize> = scored.into_iter().filter(|(_, score)| best.clone() - score.clone() <= *eps).map(|(idx, _)| idx).collect();
+    Some(tied.get(selection.pick(tied.len(), ENTERING_SALT)).copied().unwrap_or(first))
+}
+
+//ratio test, a basic var can block the entering one at 0 or at its upper bound, and the entering var can hit its own bound
+//None means nothing blocks it: no positive entry in the column, no negative one under a bounded basic var and no
+//upper bound of its own, so the objective improves without limit along it
+pub(crate) fn find_pivot_row<T: Scalar>(
+    tableau: &Array2<T>,
+    pivot_col: usize,
+    last_row_index: usize,
+    basis: &[usize],
+    bounds: &ColumnBounds<T>,
+    selection: Selection,
+    eps: &T,
+) -> Option<Step> {
+    let rhs_col = tableau.ncols() - 1;
+    let neg_eps = -eps.clone();
+    let ratios: Vec<(usize, T, bool)> = tableau
+        .slice(s![..last_row_index, pivot_col])//takes all rows except the last one (constraint coeffs) and only take those from the previously found col index
+        .iter().enumerate()//matches them into (idx, val)
+        .filter_map(|(row, val)| {
+            let rhs = tableau[[row, rhs_col]].clone();
+            if val > eps {
+                Some((row, rhs / val.clone(), false)) //basic var drops to 0
+            } else if *val < neg_eps {
+                let upper = bounds.upper[basis[row]].clone()?;
+                Some((row, (upper - rhs) / -val.clone(), true)) //basic var climbs to its bound
+            } else {
+                None
+            }
+        })
+        .collect();
+    let min_ratio = ratios.iter().map(|(_, ratio, _)| ratio).min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal)).cloned(); //take the lowest val of them
+
+    //the entering var reaches its own upper bound first
+    if let Some(own_bound) = &bounds.upper[pivot_col] {
+        if min_ratio.as_ref().is_none_or(|ratio| own_bound <= ratio) {
+            return Some(Step::Flip);
+        }
+    }
+    let min_ratio = min_ratio?;
+
+    let tied = ratios.iter().filter(|(_, ratio, _)| ratio.clone() - min_ratio.clone() <= *eps);
+    let (row, _, at_upper) = match selection.pivot_rule {
+        PivotRule::Dantzig | PivotRule::SteepestEdge | PivotRule::Devex => {
+            let tied: Vec<_> = tied.collect();
+            tied.get(selection.pick(tied.len(), LEAVING_SALT)).copied()
+        }
+        PivotRule::Bland => tied.min_by_key(|&&(row, _, _)| basis[row]), //the row whose basic variable has the lowest index
+        PivotRule::Lexicographic => tied.min_by(|&&(a, _, _), &&(b, _, _)| lexicographic_order(tableau, pivot_col, a, b, eps)),
+    }?;
+    Some(if *at_upper { Step::PivotAtUpper(*row) } else { Step::Pivot(*row) })
+}
+
+//pivot rule of one iteration with its tie-break. a random pick hashes the seed with the iteration count, so no rng
+//state has to be carried through the phases and the same seed always gives the same pivots
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Selection {
+    pub(crate) pivot_rule: PivotRule,
+    tie_seed: Option<u64>, //None takes the lowest index
+}
+
+//tell the entering and leaving picks of one iteration apart
+const ENTERING_SALT: u64 = 1;
+const LEAVING_SALT: u64 = 2;
+
+impl Selection {
+    pub(crate) fn new(options: &SolverOptions, iteration: usize) -> Self {
+        let tie_seed = match options.tie_break {
+            TieBreak::LowestIndex => None,
+            TieBreak::Random { seed } => Some(splitmix64(seed ^ splitmix64(iteration as u64))),
+        };
+        Selection { pivot_rule: options.pivot_rule, tie_seed }
+    }
+
+    //index into `count` tied candidates in order, 0 for the lowest one
+    fn pick(&self, count: usize, salt: u64) -> usize {
+        match self.tie_seed {
+            Some(seed) if count > 1 => (splitmix64(seed ^ salt) % count as u64) as usize,
+            _ => 0,
+        }
+    }
+}
+
+//splitmix64 finalizer, a cheap well mixed hash of z
+pub(crate) fn splitmix64(z: u64) -> u64 {
+    let z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    let z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+//compares rows a and b divided by their pivot column entries, the rhs ties already so it goes over the columns from the
+//last one down, that way the starting slack/artificial identity comes first and every row starts out lexicographically positive
+fn lexicographic_order<T: Scalar>(tableau: &Array2<T>, pivot_col: usize, a: usize, b: usize, eps: &T) -> Ordering {
+    let (pivot_a, pivot_b) = (tableau[[a, pivot_col]].abs(), tableau[[b, pivot_col]].abs());
+    for col in (0..tableau.ncols() - 1).rev() {
+        let (val_a, val_b) = (tableau[[a, col]].clone() / pivot_a.clone(), tableau[[b, col]].clone() / pivot_b.clone());
+        if (val_a.clone() - val_b.clone()).abs() > *eps {
+            return val_a.partial_cmp(&val_b).unwrap_or(Ordering::Equal);
+        }
+    }
+    Ordering::Equal
+}
+
+//substitutes x_j = u_j - x_j' in every row, a basic column's row gets negated so it stays a unit column
+pub(crate) fn complement_column<T: Scalar>(tableau: &mut Array2<T>, basis: &[usize], bounds: &mut ColumnBounds<T>, col: usize) {
+    let Some(upper) = bounds.upper[col].clone() else {
+        return; //only bounded columns ever get complemented
+    };
+    let rhs_col = tableau.ncols() - 1;
+    for row in 0..tableau.nrows() {
+        let val = tableau[[row, col]].clone();
+        tableau[[row, rhs_col]] -= val.clone() * upper.clone();
+        tableau[[row, col]] = -val;
+    }
+    if let Some(row) = basis.iter().position(|&basic| basic == col) {
+        tableau.row_mut(row).map_inplace(|val| *val = -val.clone());
+    }
+    bounds.flipped[col] = !bounds.flipped[col];
+}
+
+pub(crate) fn pivot_operation<T: Scalar>(tableau: &mut Array2<T>, pivot_row: usize, pivot_col: usize) -> Result<(), SolverError> {
+    let pivot_value = tableau[[pivot_row, pivot_col]].clone();
+    if pivot_value.is_zero() {
+        return Err(SolverError::ZeroPivot { row: pivot_row, col: pivot_col });
+    }
+    //divide each val in the pivot row by the found pivot val
+    tableau.row_mut(pivot_row).map_inplace(|x| *x /= pivot_value.clone());
+
+    for i in 0..tableau.nrows() {
+        if i != pivot_row {
+            let row_factor = tableau[[i, pivot_col]].clone();
+            for j in 0..tableau.ncols() {
+                let delta = row_factor.clone() * tableau[[pivot_row, j]].clone();
+                tableau[[i, j]] -= delta;
+            }
+        }
+    }
+    Ok(())
+}
+
+//builds the constraint rows as [structural | slack/surplus | artificial | rhs] with a zero objective row
+//returns the tableau, the starting basis (one column per row) and the index of the first artificial column
+pub(crate) fn standard_tableau<T: Scalar>(a: &Array2<T>, b: &Array1<T>, relations: &[Relation]) -> (Array2<T>, Vec<usize>, usize) {
+    let num_constraints = a.nrows();
+    let num_vars = a.ncols();
+
+    //rows with a negative rhs get multiplied by -1 so the starting basis is feasible
+    let relations: Vec<Relation> = relations.iter().zip(b.iter())
+        .map(|(&rel, rhs)| match (rel, rhs.is_negative()) {
+            (Relation::Le, true) => Relation::Ge,
+            (Relation::Ge, true) => Relation::Le,
+            (rel, _) => rel,
+        })
+        .collect();
+
+    let num_slack = relations.iter().filter(|&&rel| rel != Relation::Eq).count();
+    let num_artificial = relations.iter().filter(|&&rel| rel != Relation::Le).count();
+    let first_artificial = num_vars + num_slack;
+    let num_cols = first_artificial + num_artificial;
+
+    let mut tableau = Array2::<T>::zeros((num_constraints + 1, num_cols + 1));
+    let mut basis = Vec::with_capacity(num_constraints);
+    let mut slack_col = num_vars;
+    let mut artificial_col = first_artificial;
+
+    for (i, &rel) in relations.iter().enumerate() {
+        let sign = if b[i].is_negative() { -T::one() } else { T::one() };
+        tableau.slice_mut(s![i, ..num_vars]).assign(&a.row(i).mapv(|val| val * sign.clone()));
+        tableau[[i, num_cols]] = b[i].clone() * sign;
+
+        match rel {
+            Relation::Le => {
+                tableau[[i, slack_col]] = T::one();
+                basis.push(slack_col);
+                slack_col += 1;
+            }
+            Relation::Ge => {
+                tableau[[i, slack_col]] = -T::one();
+                tableau[[i, artificial_col]] = T::one();
+                basis.push(artificial_col);
+                slack_col += 1;
+                artificial_col += 1;
+            }
+            Relation::Eq => {
+                tableau[[i, artificial_col]] = T::one();
+                basis.push(artificial_col);
+                artificial_col += 1;
+            }
+        }
+    }
+
+    (tableau, basis, first_artificial)
+}
+
+//-1 for the rows standard_tableau negates
+pub(crate) fn row_signs<T: Scalar>(b: &Array1<T>) -> Vec<T> {
+    b.iter().map(|rhs| if rhs.is_negative() { -T::one() } else { T::one() }).collect()
+}
+
+//finds a unit column for every constraint row, None if some row has none
+fn identity_basis<T: Scalar>(tableau: &Array2<T>) -> Option<Vec<usize>> {
+    let last_row_index = tableau.nrows() - 1;
+    (0..last_row_index)
+        .map(|row| {
+            (0..tableau.ncols() - 1).find(|&col| {
+                tableau.slice(s![..last_row_index, col]).iter().enumerate()
+                    .all(|(i, val)| if i == row { val.is_one() } else { val.is_zero() })
+            })
+        })
+        .collect()
+}
+
+//zeroes the objective row under every basic column
+fn price_out<T: Scalar>(tableau: &mut Array2<T>, basis: &[usize]) {
+    let last_row_index = tableau.nrows() - 1;
+    for (row, &col) in basis.iter().enumerate() {
+        let factor = tableau[[last_row_index, col]].clone();
+        if !factor.is_zero() {
+            let pivot_row = tableau.row(row).to_owned();
+            tableau.row_mut(last_row_index).zip_mut_with(&pivot_row, |val, pivot| *val -= factor.clone() * pivot.clone());
+        }
+    }
+}
+
+//pivots the `start` columns into a fresh standard tableau, taking the free row with the largest entry for each
+//None if they don't form a basis or the vertex they give is infeasible, an artificial may only stay basic at zero
+fn install_basis<T: Scalar>(tableau: &Array2<T>, start: &[usize], first_artificial: usize, eps: &T) -> Option<(Array2<T>, Vec<usize>)> {
+    let num_rows = tableau.nrows() - 1;
+    let last_col_index = tableau.ncols() - 1;
+    if start.len() != num_rows || start.iter().any(|&col| col >= last_col_index) {
+        return None;
+    }
+
+    let mut tableau = tableau.clone();
+    let mut basis: Vec<Option<usize>> = vec![None; num_rows];
+    for &col in start {
+        let row = (0..num_rows)
+            .filter(|&row| basis[row].is_none())
+            .max_by(|&r1, &r2| tableau[[r1, col]].abs().partial_cmp(&tableau[[r2, col]].abs()).unwrap_or(Ordering::Equal))?;
+        if tableau[[row, col]].abs() <= *eps {
+            return None; //linearly dependent on the columns before it
+        }
+        pivot_operation(&mut tableau, row, col).ok()?;
+        basis[row] = Some(col);
+    }
+
+    let basis: Vec<usize> = basis.into_iter().collect::<Option<_>>()?;
+    let neg_eps = -eps.clone();
+    let feasible = basis.iter().enumerate().all(|(row, &col)| {
+        let value = &tableau[[row, last_col_index]];
+        *value >= neg_eps && (col < first_artificial || value <= eps)
+    });
+    feasible.then_some((tableau, basis))
+}
+
+//a basis of the rows without `row` to warm start from once it is deleted: `start` is installed, then the row's
+//slack (the artificial of an = row) enters as a free var, in whichever direction the ratio test finds a blocking
+//row first so the other basic vars stay nonnegative, and leaves the problem together with the row. the slack columns
+//after it move down by one. None if start isn't a feasible basis or keeps an artificial
+pub(crate) fn basis_without_row(a: &Array2<f64>, b: &Array1<f64>, relations: &[Relation], start: &[usize], row: usize, eps: f64) -> Option<Vec<usize>> {
+    let (tableau, unit_columns, first_artificial) = standard_tableau(a, b, relations);
+    let (tableau, mut basis) = install_basis(&tableau, start, first_artificial, &eps)?;
+    let rhs_col = tableau.ncols() - 1;
+    let slack = (relations[row] != Relation::Eq).then(|| a.ncols() + relations[..row].iter().filter(|&&rel| rel != Relation::Eq).count());
+    let freed = slack.unwrap_or(unit_columns[row]);
+
+    let leaving = match basis.iter().position(|&col| col == freed) {
+        Some(leaving) => leaving,
+        None => {
+            let ratio = |sign: f64| {
+                (0..basis.len())
+                    .filter(|&i| sign * tableau[[i, freed]] > eps)
+                    .min_by(|&i1, &i2| (tableau[[i1, rhs_col]] / tableau[[i1, freed]].abs()).total_cmp(&(tableau[[i2, rhs_col]] / tableau[[i2, freed]].abs())))
+            };
+            ratio(1.0).or_else(|| ratio(-1.0))?
+        }
+    };
+    basis.remove(leaving);
+    if basis.iter().any(|&col| col >= first_artificial) {
+        return None;
+    }
+    Some(basis.into_iter().map(|col| if slack.is_some_and(|slack| col > slack) { col - 1 } else { col }).collect())
+}
+
+//swaps the artificial of a >= or = row for a structural column that already is that row's unit column (a 1 there,
+//0 in every other row) and whose upper bound allows the rhs, so phase one has one artificial less to drive out.
+//the artificial column stays in the tableau as the row's identity column, only nonbasic
+fn crash_basis<T: Scalar>(tableau: &Array2<T>, basis: &mut [usize], num_vars: usize, first_artificial: usize, bounds: &ColumnBounds<T>) {
+    let num_rows = tableau.nrows() - 1;
+    let rhs_col = tableau.ncols() - 1;
+    for row in 0..num_rows {
+        if basis[row] < first_artificial {
+            continue;
+        }
+        let is_unit = |col: usize| (0..num_rows).all(|i| if i == row { tableau[[i, col]] == T::one() } else { tableau[[i, col]].is_zero() });
+        let crash = (0..num_vars).find(|&col| {
+            is_unit(col) && !basis.contains(&col) && bounds.upper(col).is_none_or(|upper| tableau[[row, rhs_col]] <= *upper)
+        });
+        if let Some(col) = crash {
+            basis[row] = col;
+        }
+    }
+}
+
+//artificials left basic at zero after phase one get swapped for any real column with a nonzero entry in their row
+//if the row has none it is redundant and the artificial just stays at zero
+fn drive_out_artificials<T: Scalar>(tableau: &mut Array2<T>, basis: &mut [usize], first_artificial: usize, eps: &T) -> Result<(), SolverError> {
+    for row in 0..basis.len() {
+        if basis[row] < first_artificial {
+            continue;
+        }
+        if let Some(col) = (0..first_artificial).find(|&col| tableau[[row, col]].abs() > *eps) {
+            pivot_operation(tableau, row, col)?;
+            basis[row] = col;
+        }
+    }
+    Ok(())
+}
+
+//extract solution and objective value from the tabeau
+//a column is basic when exactly one constraint entry is close to 1 and the rest of the column (reduced cost included) is close to 0
+pub(crate) fn extract_solution<T: Scalar>(tableau: &Array2<T>, eps: &T) -> Array1<T> {
+    let last_row_index = tableau.nrows() - 1;
+    let last_col_index = tableau.ncols() - 1;
+    let mut solution = Array1::zeros(last_col_index);
+    let mut taken_rows = vec![false; last_row_index]; //two identical unit columns can't both own the same row
+
+    for j in 0..last_col_index {
+        let column = tableau.column(j);
+        let mut ones = column.iter().enumerate().filter(|&(_, val)| (val.clone() - T::one()).abs() <= *eps);
+        let basic_row_index = match (ones.next(), ones.next()) {
+            (Some((row, _)), None) if row < last_row_index => row,
+            _ => continue,
+        };
+        let rest_is_zero = column.iter().enumerate().all(|(i, val)| i == basic_row_index || val.abs() <= *eps);
+        if rest_is_zero && !taken_rows[basic_row_index] {
+            taken_rows[basic_row_index] = true;
+            solution[j] = tableau[[basic_row_index, last_col_index]].clone();
+        }
+    }
+    solution
+}
+
+//options.epsilon in the solver's number type
+pub(crate) fn epsilon<T: Scalar>(options: &SolverOptions) -> T {
+    T::from_f64(options.epsilon).unwrap_or_else(T::zero)
+}
+
+//c·x the way the solvers report it, for either sense (a minimized problem reports c·x too, not -c·x)
+//x may be longer than c, e.g. a Solution::x with its slacks, the extra entries don't count
+pub fn evaluate_objective<T: Scalar>(c: &Array1<T>, x: &Array1<T>) -> T {
+    dot(c.view(), x.slice(s![..c.len().min(x.len())]))
+}
+
+pub(crate) fn dot<T: Scalar>(u: ArrayView1<T>, v: ArrayView1<T>) -> T {
+    u.iter().zip(v.iter()).fold(T::zero(), |sum, (x, y)| sum + x.clone() * y.clone())
+}
+
+//nonbasic columns among the first `num_cols` whose objective row entry is zero
+pub(crate) fn zero_reduced_costs<T: Scalar>(tableau: &Array2<T>, basis: &[usize], num_cols: usize, eps: &T) -> Vec<usize> {
+    let last_row_index = tableau.nrows() - 1;
+    (0..num_cols)
+        .filter(|col| !basis.contains(col) && tableau[[last_row_index, *col]].abs() <= *eps)
+        .collect()
+}
+
+//basic columns among the first `num_cols` whose rhs is zero
+pub(crate) fn degenerate_columns<T: Scalar>(tableau: &Array2<T>, basis: &[usize], num_cols: usize, eps: &T) -> Vec<usize> {
+    let last_col_index = tableau.ncols() - 1;
+    basis
+        .iter()
+        .enumerate()
+        .filter(|&(row, &col)| col < num_cols && tableau[[row, last_col_index]].abs() <= *eps)
+        .map(|(_, &col)| col)
+        .collect()
+}
+
+//rows whose slack column is at 0, and the = rows, which have none
+pub(crate) fn binding_rows<T: Scalar>(tableau: &Array2<T>, unit_columns: &[usize], num_vars: usize, x: &Array1<T>, eps: &T) -> Vec<usize> {
+    (0..unit_columns.len())
+        .filter(|&row| slack_column(tableau, unit_columns, num_vars, x.len(), row, eps).is_none_or(|col| x[col].abs() <= *eps))
+        .collect()
+}
+
+//the slack or surplus column of a row: its starting identity column when that's one of the first num_x, otherwise
+//(an artificial) the slack/surplus column that is its negative, which is the surplus of a >= row. None for = rows
+pub(crate) fn slack_column<T: Scalar>(tableau: &Array2<T>, unit_columns: &[usize], num_vars: usize, num_x: usize, row: usize, eps: &T) -> Option<usize> {
+    let unit = unit_columns[row];
+    if unit < num_x {
+        return Some(unit);
+    }
+    let num_rows = tableau.nrows() - 1;
+    let artificial = tableau.slice(s![..num_rows, unit]);
+    (num_vars..num_x).find(|&col| {
+        tableau.slice(s![..num_rows, col]).iter().zip(artificial.iter()).all(|(val, art)| (val.clone() + art.clone()).abs() <= *eps)
+    })
+}
+
+//value of every tableau column as the tableau holds it, read through the basis
+pub(crate) fn basic_values<T: Scalar>(tableau: &Array2<T>, basis: &[usize]) -> Array1<T> {
+    let last_col_index = tableau.ncols() - 1;
+    let mut values = Array1::zeros(last_col_index);
+    for (row, &col) in basis.iter().enumerate() {
+        values[col] = tableau[[row, last_col_index]].clone();
+    }
+    values
+}
+
+//sum of coeff * x[col] over the terms of every column
+pub(crate) fn combine<T: Scalar>(columns: &[Vec<(usize, T)>], x: &Array1<T>) -> Array1<T> {
+    columns.iter().map(|terms| terms.iter().fold(T::zero(), |sum, (col, coeff)| sum + coeff.clone() * x[*col].clone())).collect()
+}
+
+impl<T: Scalar> Solution<T> {
+    //this solution as one of the problem solve_with rewrote the given one into, so x lines up with the tableau
+    //columns again. None unless it was rewritten
+    pub fn rewritten(&self) -> Option<Solution<T>> {
+        let rewrite = self.rewrite.as_ref()?;
+        Some(Solution { x: rewrite.x.clone(), num_vars: rewrite.num_vars, var_names: Vec::new(), soft_violations: Vec::new(), rewrite: None, ..self.clone() })
+    }
+
+    //a solution of the rewritten problem, rewritten() pivoted further, read back through the rewrite of this one
+    pub(crate) fn read_back(&self, solution: Solution<T>) -> Solution<T> {
+        let Some(rewrite) = &self.rewrite else {
+            return solution;
+        };
+        Solution {
+            x: combine(&rewrite.columns, &solution.x),
+            num_vars: self.num_vars,
+            var_names: self.var_names.clone(),
+            soft_violations: combine(&rewrite.violations, &solution.x).to_vec(),
+            rewrite: Some(Rewrite { x: solution.x.clone(), ..rewrite.clone() }),
+            ..solution
+        }
+    }
+
+    //this solution of a rewritten problem in terms of the problem it was rewritten from, with columns and violations
+    //over this x like in Rewrite. when this one was rewritten itself the terms are carried down to its tableau columns
+    pub(crate) fn rewritten_as(self, num_vars: usize, columns: Vec<Vec<(usize, T)>>, violations: Vec<Vec<(usize, T)>>) -> Solution<T> {
+        let expand = |terms: &Vec<(usize, T)>| -> Vec<(usize, T)> {
+            match &self.rewrite {
+                Some(inner) => terms
+                    .iter()
+                    .flat_map(|(col, coeff)| inner.columns[*col].iter().map(move |(inner_col, inner_coeff)| (*inner_col, inner_coeff.clone() * coeff.clone())))
+                    .collect(),
+                None => terms.clone(),
+            }
+        };
+        let rewrite = Rewrite {
+            x: self.rewrite.as_ref().map_or_else(|| self.x.clone(), |inner| inner.x.clone()),
+            num_vars: self.rewrite.as_ref().map_or(self.num_vars, |inner| inner.num_vars),
+            columns: columns.iter().map(expand).collect(),
+            violations: violations.iter().map(expand).collect(),
+        };
+        let soft_violations = if violations.is_empty() { self.soft_violations.clone() } else { combine(&violations, &self.x).to_vec() };
+        Solution { x: combine(&columns, &self.x), num_vars, soft_violations, rewrite: Some(rewrite), ..self }
+    }
+
+    //the structural vars of x, one per column of the problem
+    pub fn structural(&self) -> ArrayView1<'_, T> {
+        self.x.slice(s![..self.num_vars.min(self.x.len())])
+    }
+
+    //the slack/surplus vars of x, one per <= and >= row in row order (s1, s2, ... of named_values)
+    pub fn slacks(&self) -> ArrayView1<'_, T> {
+        self.x.slice(s![self.num_vars.min(self.x.len())..])
+    }
+
+    //value of every column of x by name, structural ones use var_names where given and x1, x2, ... otherwise,
+    //slack/surplus ones are s1, s2, ... in row order
+    pub fn named_values(&self) -> BTreeMap<String, T> {
+        self.x
+            .iter()
+            .enumerate()
+            .map(|(j, val)| {
+                let name = match self.var_names.get(j) {
+                    Some(name) if j < self.num_vars => name.clone(),
+                    _ if j < self.num_vars => format!("x{}", j + 1),
+                    _ => format!("s{}", j + 1 - self.num_vars),
+                };
+                (name, val.clone())
+            })
+            .collect()
+    }
+
+    //the value of one variable under its named_values name, None for a name no column has
+    pub fn value_of(&self, name: &str) -> Option<T> {
+        let col = match self.var_names.iter().take(self.num_vars).position(|known| known == name) {
+            Some(col) => col,
+            None => {
+                let parse = |index: &str| index.parse::<usize>().ok().filter(|&index| index >= 1);
+                if let Some(index) = name.strip_prefix('x').and_then(parse) {
+                    if index > self.num_vars || self.var_names.get(index - 1).is_some() {
+                        return None;
+                    }
+                    index - 1
+                } else {
+                    self.num_vars.checked_add(name.strip_prefix('s').and_then(parse)? - 1)?
+                }
+            }
+        };
+        self.x.get(col).cloned()
+    }
+
+    //the tableau as format_tableau prints it, columns named like named_values with a1, a2, ... for the artificials
+    pub fn format_tableau(&self) -> String
+    where
+        T: fmt::Display,
+    {
+        let num_cols = self.tableau.ncols() - 1;
+        let names: Vec<String> = (0..num_cols)
+            .map(|j| match self.var_names.get(j) {
+                Some(name) if j < self.num_vars => name.clone(),
+                _ if j < self.num_vars => format!("x{}", j + 1),
+                _ if j < self.x.len() => format!("s{}", j + 1 - self.num_vars),
+                _ => format!("a{}", j + 1 - self.x.len()),
+            })
+            .collect();
+        format_tableau(&self.tableau, &names)
+    }
+
+    //largest / smallest pivot magnitude of the solve, a rough sign of how well conditioned the final basis is:
+    //near 1 is healthy, a huge value means tiny pivots were divided by and the result may be unreliable. 1 without pivots
+    pub fn condition_estimate(&self) -> T {
+        match &self.pivot_sizes {
+            Some((smallest, largest)) if !smallest.is_zero() => largest.clone() / smallest.clone(),
+            _ => T::one(),
+        }
+    }
+
+    //B⁻¹ of the final basis, for the rows as given (undoing the negation of rows with a negative rhs) and the
+    //columns as given (undoing complemented ones). it's read off the final tableau under unit_columns, no inverting
+    pub fn basis_inverse(&self) -> Array2<T> {
+        let num_rows = self.basis.len();
+        let mut inverse = Array2::<T>::zeros((num_rows, num_rows));
+        for (i, &unit_col) in self.unit_columns.iter().enumerate() {
+            let sign = if self.at_upper.get(unit_col).copied().unwrap_or(false) { -self.row_signs[i].clone() } else { self.row_signs[i].clone() };
+            for row in 0..num_rows {
+                inverse[[row, i]] = sign.clone() * self.tableau[[row, unit_col]].clone();
+            }
+        }
+        for (row, &col) in self.basis.iter().enumerate() {
+            if self.at_upper.get(col).copied().unwrap_or(false) {
+                inverse.row_mut(row).map_inplace(|val| *val = -val.clone());
+            }
+        }
+        inverse
+    }
+
+    //B, the columns of [a | slack/surplus] of the basic variables in basis order, a slack is +e_i for a <= row and
+    //its surplus -e_i for a >= row. a leftover artificial of a redundant row is e_i. a and relations are the problem
+    //this solved, as given to the solver. B · basis_inverse() is the identity
+    pub fn basis_matrix(&self, a: &Array2<T>, relations: &[Relation]) -> Array2<T> {
+        let num_rows = self.basis.len();
+        let slack_rows: Vec<usize> = (0..relations.len()).filter(|&row| relations[row] != Relation::Eq).collect();
+        let mut matrix = Array2::<T>::zeros((num_rows, num_rows));
+        for (k, &col) in self.basis.iter().enumerate() {
+            if col < a.ncols() {
+                matrix.column_mut(k).assign(&a.column(col));
+            } else if let Some(&row) = slack_rows.get(col - a.ncols()).filter(|_| col < self.x.len()) {
+                matrix[[row, k]] = if relations[row] == Relation::Ge { -T::one() } else { T::one() };
+            } else if let Some(row) = self.unit_columns.iter().position(|&unit_col| unit_col == col) {
+                matrix[[row, k]] = self.row_signs[row].clone();
+            }
+        }
+        matrix
+    }
+
+    //constraint rows phase one found to be linear combinations of the others: no real column had a nonzero entry
+    //left to pivot their zero-level artificial out with, so it stays basic and the row constrains nothing
+    pub fn redundant_rows(&self) -> Vec<usize> {
+        (0..self.basis.len()).filter(|&row| self.basis[row] >= self.x.len()).collect()
+    }
+
+    //brings one of the alternate_optima columns into the basis, which gives another vertex with the same objective
+    //None if col isn't one of them or nothing blocks it, then the optimal face is unbounded along that edge. col is
+    //a tableau column like the alternate_optima entries, a rewritten solution pivots on its rewritten problem
+    pub fn alternate_vertex(&self, col: usize, options: &SolverOptions) -> Result<Option<Solution<T>>, SolverError> {
+        if !self.alternate_optima.contains(&col) {
+            return Ok(None);
+        }
+        if let Some(rewritten) = self.rewritten() {
+            return Ok(rewritten.alternate_vertex(col, options)?.map(|next| self.read_back(next)));
+        }
+        let eps = epsilon::<T>(options);
+        let mut bounds = ColumnBounds::of_solution(self);
+        let before = bounds.unflip(basic_values(&self.tableau, &self.basis));
+
+        let last_row_index = self.tableau.nrows() - 1;
+        let step = match find_pivot_row(&self.tableau, col, last_row_index, &self.basis, &bounds, Selection::new(options, self.iterations), &eps) {
+            Some(step) => step,
+            None => return Ok(None),
+        };
+        let mut tableau = self.tableau.clone();
+        let mut basis = self.basis.clone();
+        let mut trace = Trace { pivot_sizes: self.pivot_sizes.clone(), ..Trace::new() };
+        if let Step::Pivot(row) | Step::PivotAtUpper(row) = &step {
+            trace.note_pivot(&tableau[[*row, col]]);
+        }
+        apply_step(&mut tableau, &mut basis, &mut bounds, step, col)?;
+
+        //x can include shifted lower bounds, so move it by how much the columns changed instead of reading it off again
+        let after = bounds.unflip(basic_values(&tableau, &basis));
+        let mut x = self.x.clone();
+        for (j, val) in x.iter_mut().enumerate() {
+            *val += after[j].clone() - before[j].clone();
+        }
+
+        Ok(Some(Solution {
+            binding_constraints: binding_rows(&tableau, &self.unit_columns, self.num_vars, &x, &eps),
+            x,
+            iterations: self.iterations + 1,
+            pivot_sizes: trace.pivot_sizes,
+            alternate_optima: zero_reduced_costs(&tableau, &basis, self.x.len(), &eps),
+            degenerate: degenerate_columns(&tableau, &basis, self.x.len(), &eps),
+            at_upper: bounds.flipped[..self.at_upper.len()].to_vec(),
+            basis,
+            tableau,
+            history: Vec::new(),
+            log: Vec::new(),
+            ..self.clone()
+        }))
+    }
+}
+
+//fixed-width table of a tableau, a header of var_names (x1, x2, ... for any missing) and rhs,
+//then the constraint rows and the objective row below a rule
+pub fn format_tableau<T: fmt::Display>(tableau: &Array2<T>, var_names: &[String]) -> String {
+    let num_cols = tableau.ncols();
+    let mut header: Vec<String> = (0..num_cols.saturating_sub(1))
+        .map(|j| var_names.get(j).cloned().unwrap_or_else(|| format!("x{}", j + 1)))
+        .collect();
+    header.push("rhs".to_string());
+    let cells: Vec<Vec<String>> = tableau.rows().into_iter().map(|row| row.iter().map(|val| format!("{:.4}", val)).collect()).collect();
+    let widths: Vec<usize> = (0..num_cols)
+        .map(|j| cells.iter().map(|row| row[j].len()).chain(core::iter::once(header[j].len())).max().unwrap_or(0))
+        .collect();
+
+    let line = |row: &[String]| row.iter().zip(&widths).map(|(cell, &width)| format!("{:>width$}", cell)).collect::<Vec<_>>().join(" | ");
+    let rule = widths.iter().map(|&width| "-".repeat(width)).collect::<Vec<_>>().join("-+-");
+    let mut lines = vec![line(&header), rule.clone()];
+    for (i, row) in cells.iter().enumerate() {
+        if i + 1 == cells.len() {
+            lines.push(rule.clone()); //the objective row
+        }
+        lines.push(line(row));
+    }
+    lines.join("\n")
+}
+
+//find basic variables
+pub fn find_basis(tableau: &Array2<f64>) -> Vec<usize> {
+    let mut basis = Vec::new();
+    for col in 0..tableau.ncols() - 1 {
+        let column = tableau.slice(s![..-1, col]);
+        if column.iter().filter(|&&x| x != 0.0).count() == 1 && column.iter().sum::<f64>() == 1.0 {
+            basis.push(col);
+        }
+    }
+    basis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem::LpProblem;
+    use crate::test_support::{expect_optimal, wyndor};
+
+    #[test]
+    fn value_of_finds_named_and_fallback_columns() {
+        let solution = expect_optimal(wyndor().solve().unwrap());
+        assert_eq!(solution.value_of("x1"), Some(solution.x[0]));
+        assert_eq!(solution.value_of("x2"), Some(solution.x[1]));
+        assert_eq!(solution.value_of("s1"), Some(solution.x[2]));
+        assert_eq!(solution.value_of("s3"), Some(solution.x[4]));
+        assert_eq!(solution.value_of("x3"), None);
+        assert_eq!(solution.value_of("s4"), None);
+        assert_eq!(solution.value_of("x0"), None);
+
+        let named = expect_optimal(wyndor().var_names(vec!["doors".to_string(), "windows".to_string()]).solve().unwrap());
+        assert_eq!(named.value_of("windows"), Some(named.x[1]));
+        assert_eq!(named.value_of("x2"), None); //named columns don't answer to their fallback name
+    }
+
+    #[test]
+    fn value_of_rejects_odd_names_without_panicking() {
+        let solution = expect_optimal(wyndor().solve().unwrap());
+        for name in ["", "x", "s", "é1", "x1é", "ß", "x-1", "s18446744073709551616"] {
+            assert_eq!(solution.value_of(name), None, "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn log_follows_the_pivots() {
+        let options = SolverOptions { record_log: true, record_history: true, ..SolverOptions::default() };
+        //x1 <= 4 as a bound as well, so the bounded path can flip instead of pivot
+        for problem in [wyndor(), wyndor().bound(0, 0.0, 4.0), wyndor().bound(1, 0.0, 1.0)] {
+            let solution = expect_optimal(problem.solve_with(&options).unwrap());
+            assert_eq!(solution.log.len(), solution.iterations);
+            assert_eq!(solution.history.len(), solution.iterations + 1);
+            for (k, (entry, snapshot)) in solution.log.iter().zip(&solution.history[1..]).enumerate() {
+                assert_eq!(entry.iteration, k + 1);
+                assert_eq!(Some(entry.entering), snapshot.pivot_col);
+                assert_eq!(entry.pivot_row, snapshot.pivot_row);
+                assert_eq!(entry.leaving.is_none(), entry.pivot_row.is_none());
+                assert!(entry.ratio >= 0.0);
+                let rows = snapshot.tableau.nrows();
+                let cols = snapshot.tableau.ncols();
+                assert!((entry.objective - snapshot.tableau[[rows - 1, cols - 1]]).abs() < 1e-9);
+            }
+            let last = solution.log.last().expect("the origin isn't optimal");
+            assert!((last.objective - solution.objective).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn edge_pricing_takes_fewer_iterations_than_dantzig() {
+        let rules = [PivotRule::Dantzig, PivotRule::SteepestEdge, PivotRule::Devex];
+        let mut iterations = [0; 3];
+        for seed in 0..10 {
+            let (problem, _) = crate::test_support::random_feasible_lp(seed, 20, 40);
+            let solutions: Vec<Solution> =
+                rules.iter().map(|&pivot_rule| expect_optimal(problem.solve_with(&SolverOptions { pivot_rule, ..SolverOptions::default() }).unwrap())).collect();
+            for (k, solution) in solutions.iter().enumerate() {
+                crate::test_support::assert_objective_close(solutions[0].objective, solution.objective, 1e-9);
+                iterations[k] += solution.iterations;
+            }
+        }
+        //461 dantzig, 283 steepest edge and 400 devex iterations when this was written
+        assert!(iterations[1] < iterations[0] && iterations[2] < iterations[0], "{:?}", iterations);
+    }
+
+    #[test]
+    fn partial_pricing_falls_back_to_every_column() {
+        //objective row with an improving column 3 only, outside the candidate block
+        let tableau = array![[1.0, 0.0, 2.0, 1.0, 0.0, 4.0], [0.0, 1.0, 1.0, 3.0, 1.0, 6.0], [0.0, 0.0, 1.0, -2.0, 0.5, 0.0]];
+        let bounds = ColumnBounds::unbounded(5);
+        let selection = Selection::new(&SolverOptions::default(), 0);
+        let find = |candidates: Option<&[usize]>| find_pivot_column(&tableau, 5, candidates, &bounds, selection, &1e-9, &[1.0; 5]);
+        assert_eq!(find(None), Some(3));
+        assert_eq!(find(Some(&[0, 1, 2])), Some(3));
+        assert_eq!(find(Some(&[3])), Some(3));
+        assert_eq!(find(Some(&[])), Some(3));
+    }
+
+    #[test]
+    fn partial_pricing_reaches_the_full_pricing_optimum() {
+        for seed in 0..10 {
+            let (problem, _) = crate::test_support::random_feasible_lp(seed, 10, 200);
+            let full = expect_optimal(problem.solve().unwrap());
+            for block in [1, 16, 50, 199, 1000] {
+                let partial = expect_optimal(problem.solve_with(&SolverOptions { partial_pricing: Some(block), ..SolverOptions::default() }).unwrap());
+                crate::test_support::assert_objective_close(full.objective, partial.objective, 1e-9);
+                assert!(crate::verify::verify_solution(&problem, &partial.x, 1e-7).is_ok(), "seed {} block {}", seed, block);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_pivot_is_an_error_not_a_panic() {
+        let mut tableau = array![[0.0, 1.0, 1.0, 4.0], [2.0, 1.0, 0.0, 6.0], [-3.0, -5.0, 0.0, 0.0]];
+        let before = tableau.clone();
+        assert_eq!(pivot_operation(&mut tableau, 0, 0), Err(SolverError::ZeroPivot { row: 0, col: 0 }));
+        assert_eq!(tableau, before); //nothing got divided by the zero
+        assert_eq!(pivot_operation(&mut tableau, 1, 0), Ok(()));
+        assert_eq!(tableau.column(0).to_vec(), vec![0.0, 1.0, 0.0]);
+        assert_eq!(tableau[[2, 3]], 9.0);
+        assert!(SolverError::ZeroPivot { row: 0, col: 0 }.to_string().contains("zero"));
+    }
+
+    //Beale's example, Dantzig pricing with lowest-index ties cycles through six degenerate bases at the origin.
+    //optimum 1/20 at x1 = 1/25, x3 = 1
+    fn beale() -> (Array1<f64>, Array2<f64>, Array1<f64>) {
+        let c = array![0.75, -150.0, 0.02, -6.0];
+        let a = array![[0.25, -60.0, -0.04, 9.0], [0.5, -90.0, -0.02, 3.0], [0.0, 0.0, 1.0, 0.0]];
+        (c, a, array![0.0, 0.0, 1.0])
+    }
+
+    fn solve_beale(pivot_rule: PivotRule) -> SolveResult {
+        let (c, a, b) = beale();
+        let options = SolverOptions { pivot_rule, max_iterations: Some(1000), ..SolverOptions::default() };
+        simplex_solver_two_phase(c, &a, &b, &[Relation::Le; 3], &options).unwrap()
+    }
+
+    #[test]
+    fn bland_does_not_cycle_on_beale() {
+        assert!(matches!(solve_beale(PivotRule::Dantzig), SolveResult::IterationLimit(_)));
+        let solution = expect_optimal(solve_beale(PivotRule::Bland));
+        assert!((solution.objective - 0.05).abs() < 1e-9);
+        crate::test_support::assert_solution_close(&[0.04, 0.0, 1.0, 0.0], &solution.structural().to_owned(), 1e-9);
+    }
+
+    #[test]
+    fn bland_enters_the_lowest_improving_column() {
+        let tableau = array![[1.0, 0.0, 1.0, 1.0, 4.0], [0.0, 1.0, 1.0, 3.0, 6.0], [0.0, 0.0, -1.0, -5.0, 0.0]];
+        let bounds = ColumnBounds::unbounded(4);
+        let pick = |pivot_rule| {
+            let selection = Selection::new(&SolverOptions { pivot_rule, ..SolverOptions::default() }, 0);
+            find_pivot_column(&tableau, 4, None, &bounds, selection, &1e-9, &[1.0; 4])
+        };
+        assert_eq!(pick(PivotRule::Dantzig), Some(3));
+        assert_eq!(pick(PivotRule::Bland), Some(2));
+    }
+
+    #[test]
+    fn bland_breaks_ratio_ties_on_the_lowest_basic_var() {
+        //both rows allow a step of 2 in column 2, row 0 has x4 basic and row 1 has x2
+        let tableau = array![[0.0, 0.0, 1.0, 1.0, 2.0], [0.0, 1.0, 2.0, 0.0, 4.0], [0.0, 0.0, -1.0, 0.0, 0.0]];
+        let basis = [3, 1];
+        let bounds = ColumnBounds::unbounded(4);
+        let leave = |pivot_rule| {
+            let selection = Selection::new(&SolverOptions { pivot_rule, ..SolverOptions::default() }, 0);
+            find_pivot_row(&tableau, 2, 2, &basis, &bounds, selection, &1e-9)
+        };
+        assert!(matches!(leave(PivotRule::Dantzig), Some(Step::Pivot(0))));
+        assert!(matches!(leave(PivotRule::Bland), Some(Step::Pivot(1))));
+    }
+
+    #[test]
+    fn min_reports_the_objective_in_minimization_units() {
+        //simplex_solver takes the slack columns as part of a, c gets padded with zeros for them
+        let a = array![[1.0, 0.0, 1.0, 0.0, 0.0], [0.0, 2.0, 0.0, 1.0, 0.0], [3.0, 2.0, 0.0, 0.0, 1.0]];
+        let b = array![4.0, 12.0, 18.0];
+        let max = expect_optimal(simplex_solver(array![3.0, 5.0], &a, &b, &SolverOptions::default()).unwrap());
+        let min = expect_optimal(simplex_solver_min(array![-3.0, -5.0], &a, &b, &SolverOptions::default()).unwrap());
+        assert_eq!((max.objective, max.sense), (36.0, Sense::Maximize));
+        assert_eq!((min.objective, min.sense), (-36.0, Sense::Minimize));
+        assert_eq!(min.x, max.x);
+        assert_eq!(max.structural().to_vec(), vec![2.0, 6.0, 2.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn min_and_max_over_the_same_rows() {
+        //x1 + x2 >= 4, x1 + 3x2 >= 6, x1 + x2 <= 10: min 2x1 + 3x2 is 9 at (3, 1), max is 30 at (0, 10)
+        let problem = |sense| {
+            let problem = LpProblem::new()
+                .objective(vec![2.0, 3.0])
+                .add_constraint(vec![1.0, 1.0], Relation::Ge, 4.0)
+                .add_constraint(vec![1.0, 3.0], Relation::Ge, 6.0)
+                .add_constraint(vec![1.0, 1.0], Relation::Le, 10.0);
+            match sense {
+                Sense::Maximize => problem.maximize(),
+                Sense::Minimize => problem.minimize(),
+            }
+        };
+        let min = expect_optimal(problem(Sense::Minimize).solve().unwrap());
+        let max = expect_optimal(problem(Sense::Maximize).solve().unwrap());
+        assert!((min.objective - 9.0).abs() < 1e-9 && (max.objective - 30.0).abs() < 1e-9);
+        crate::test_support::assert_solution_close(&[3.0, 1.0], &min.structural().to_owned(), 1e-9);
+        crate::test_support::assert_solution_close(&[0.0, 10.0], &max.structural().to_owned(), 1e-9);
+        let unbounded = problem(Sense::Maximize);
+        let unbounded = LpProblem { constraints: unbounded.constraints[..2].to_vec(), ..unbounded };
+        assert_eq!(unbounded.solve().unwrap(), SolveResult::Unbounded);
+    }
+
+    #[test]
+    fn extract_solution_reads_unit_columns_with_rounding() {
+        let tableau = array![
+            [0.9999999998, 0.5, 1e-12, 0.0, 2.0],
+            [3e-11, 0.25, 1.0000000001, 1.0, 3.0],
+            [1e-13, 1.5, -2e-12, 0.0, 7.0]
+        ];
+        //column 3 is the same unit column as column 2 but row 1 is already taken
+        assert_eq!(extract_solution(&tableau, &1e-9).to_vec(), vec![2.0, 0.0, 3.0, 0.0]);
+        //with a tolerance below the rounding none of them count
+        assert_eq!(extract_solution(&tableau, &1e-14).to_vec(), vec![0.0, 0.0, 0.0, 3.0]);
+    }
+
+    #[test]
+    fn extract_solution_agrees_with_the_basis() {
+        //the vertex iterator reads x off the recorded tableaus this way
+        for seed in 0..20 {
+            let (problem, _) = crate::test_support::random_feasible_lp(seed, 6, 8);
+            let solution = expect_optimal(problem.solve().unwrap());
+            let extracted = extract_solution(&solution.tableau, &1e-9);
+            crate::test_support::assert_solution_close(&solution.x.to_vec(), &extracted.slice(s![..solution.x.len()]).to_owned(), 1e-9);
+        }
+    }
+
+    #[test]
+    fn optimum_on_an_upper_bound() {
+        //x2 <= 5 cuts wyndor off before 2x2 <= 12 does: 33 at (8/3, 5), with x2 nonbasic at its bound
+        let solution = expect_optimal(wyndor().bound(1, 0.0, 5.0).solve().unwrap());
+        assert!((solution.objective - 33.0).abs() < 1e-9);
+        crate::test_support::assert_solution_close(&[8.0 / 3.0, 5.0], &solution.structural().to_owned(), 1e-9);
+        assert!(solution.at_upper[1] && !solution.basis.contains(&1));
+        assert_eq!(solution.tableau.nrows(), 4); //no row for the bound
+
+        //x1 >= 3 moves the optimum to 31.5 at (3, 4.5)
+        let raised = expect_optimal(wyndor().bound(0, 3.0, f64::INFINITY).solve().unwrap());
+        assert!((raised.objective - 31.5).abs() < 1e-9);
+        crate::test_support::assert_solution_close(&[3.0, 4.5], &raised.structural().to_owned(), 1e-9);
+    }
+
+    #[test]
+    fn bounds_agree_with_bound_rows() {
+        for seed in 0..20 {
+            let (problem, point) = crate::test_support::random_feasible_lp(seed, 4, 5);
+            let (c, a, b, relations) = problem.to_arrays();
+            //the known point stays feasible inside [point - 1, point + 1] clamped at 0
+            let bounds: Vec<(f64, f64)> = point.iter().map(|&val| ((val - 1.0).max(0.0), val + 1.0)).collect();
+            let bounded = expect_optimal(simplex_solver_bounded(c, &a, &b, &relations, &bounds, &SolverOptions::default()).unwrap());
+            let mut rows = problem.clone();
+            for (var, &(lo, hi)) in bounds.iter().enumerate() {
+                rows = rows.bound(var, lo, hi);
+            }
+            let rows = expect_optimal(rows.bounds_as_constraints().solve().unwrap());
+            crate::test_support::assert_objective_close(rows.objective, bounded.objective, 1e-9);
+            assert!(bounded.structural().iter().zip(&bounds).all(|(&val, &(lo, hi))| val >= lo - 1e-9 && val <= hi + 1e-9));
+        }
+    }
+
+    #[test]
+    fn history_has_a_snapshot_per_iteration_and_the_start() {
+        let (a, b) = (array![[1.0, 1.0], [1.0, 3.0], [1.0, 1.0]], array![4.0, 6.0, 10.0]);
+        let relations = [Relation::Ge, Relation::Ge, Relation::Le];
+        let recording = SolverOptions { record_history: true, ..SolverOptions::default() };
+        let solution = expect_optimal(simplex_solver_two_phase(array![2.0, 3.0], &a, &b, &relations, &recording).unwrap());
+        assert_eq!(solution.history.len(), solution.iterations + 1);
+        assert!(solution.iterations > 0);
+        let start = &solution.history[0];
+        assert_eq!((start.pivot_row, start.pivot_col), (None, None));
+        assert!(solution.history[1..].iter().all(|snapshot| snapshot.pivot_col.is_some() && snapshot.pivot_row.is_some()));
+        assert_eq!(solution.history.last().unwrap().tableau.ncols(), solution.tableau.ncols());
+        let quiet = expect_optimal(simplex_solver_two_phase(array![2.0, 3.0], &a, &b, &relations, &SolverOptions::default()).unwrap());
+        assert!(quiet.history.is_empty());
+        assert_eq!(quiet.iterations, solution.iterations);
+    }
+
+    #[test]
+    fn solves_in_f32() {
+        let a = array![[1.0f32, 0.0], [0.0, 2.0], [3.0, 2.0]];
+        let options = SolverOptions { epsilon: 1e-5, ..SolverOptions::default() };
+        let result = simplex_solver_two_phase(array![3.0f32, 5.0], &a, &array![4.0, 12.0, 18.0], &[Relation::Le; 3], &options).unwrap();
+        let SolveResult::Optimal(solution) = result else { panic!("wyndor has an optimum") };
+        assert!((solution.objective - 36.0).abs() < 1e-4);
+        assert!((solution.x[0] - 2.0).abs() < 1e-5 && (solution.x[1] - 6.0).abs() < 1e-5);
+    }
+
+    #[cfg(feature = "exact")]
+    #[test]
+    fn solves_in_big_rationals() {
+        use num_rational::BigRational;
+        let q = |num: i64, den: i64| BigRational::new(num.into(), den.into());
+        //max x1 + x2 st 3x1 + x2 <= 1, x1 + 3x2 <= 1, exactly 1/2 at (1/4, 1/4)
+        let a = array![[q(3, 1), q(1, 1)], [q(1, 1), q(3, 1)]];
+        let b = array![q(1, 1), q(1, 1)];
+        let options = SolverOptions { epsilon: 0.0, ..SolverOptions::default() };
+        let result = simplex_solver_two_phase(array![q(1, 1), q(1, 1)], &a, &b, &[Relation::Le; 2], &options).unwrap();
+        let SolveResult::Optimal(solution) = result else { panic!("the problem has an optimum") };
+        assert_eq!(solution.objective, q(1, 2));
+        assert_eq!(solution.x.slice(s![..2]).to_vec(), vec![q(1, 4), q(1, 4)]);
+    }
+
+    #[test]
+    fn mismatched_dimensions_fail_up_front() {
+        let a = array![[1.0, 0.0, 1.0, 0.0, 0.0], [0.0, 2.0, 0.0, 1.0, 0.0], [3.0, 2.0, 0.0, 0.0, 1.0]];
+        let options = SolverOptions::default();
+        let b = array![4.0, 12.0, 18.0];
+        assert_eq!(
+            simplex_solver(Array1::from(vec![1.0; 6]), &a, &b, &options),
+            Err(SolverError::DimensionMismatch { expected: 5, found: 6 })
+        );
+        assert_eq!(simplex_solver(array![3.0, 5.0], &a, &array![4.0, 12.0], &options), Err(SolverError::DimensionMismatch { expected: 3, found: 2 }));
+        let structural = a.slice(s![.., ..2]).to_owned();
+        let two_phase = |c: Array1<f64>, b: &Array1<f64>, relations: &[Relation]| simplex_solver_two_phase(c, &structural, b, relations, &options);
+        assert_eq!(two_phase(array![3.0], &b, &[Relation::Le; 3]), Err(SolverError::DimensionMismatch { expected: 2, found: 1 }));
+        assert_eq!(two_phase(array![3.0, 5.0], &Array1::from(vec![1.0; 4]), &[Relation::Le; 3]), Err(SolverError::DimensionMismatch { expected: 3, found: 4 }));
+        assert_eq!(two_phase(array![3.0, 5.0], &b, &[Relation::Le; 2]), Err(SolverError::DimensionMismatch { expected: 3, found: 2 }));
+        assert!(two_phase(array![3.0, 5.0], &b, &[Relation::Le; 3]).is_ok());
+        let message = SolverError::DimensionMismatch { expected: 3, found: 2 }.to_string();
+        assert!(message.contains('3') && message.contains('2'), "{}", message);
+    }
+
+    #[test]
+    fn parallel_edge_has_alternate_optima() {
+        //2x1 + 4x2 is parallel to x1 + 2x2 <= 8, every point from (0, 4) to (4, 2) gets 16
+        let problem = LpProblem::new()
+            .objective(vec![2.0, 4.0])
+            .add_constraint(vec![1.0, 2.0], Relation::Le, 8.0)
+            .add_constraint(vec![1.0, 0.0], Relation::Le, 4.0)
+            .maximize();
+        let solution = expect_optimal(problem.solve().unwrap());
+        assert_eq!(solution.objective, 16.0);
+        assert!(!solution.alternate_optima.is_empty());
+        let col = solution.alternate_optima[0];
+        let other = solution.alternate_vertex(col, &SolverOptions::default()).unwrap().expect("the edge is bounded");
+        assert!((other.objective - 16.0).abs() < 1e-9);
+        assert_ne!(other.structural(), solution.structural());
+        for x in [solution.structural(), other.structural()] {
+            assert!((x[0] + 2.0 * x[1] - 8.0).abs() < 1e-9 && x[0] <= 4.0 + 1e-9, "{:?}", x);
+        }
+        //columns that aren't alternate optima don't give a vertex
+        assert!(solution.alternate_vertex(solution.basis[0], &SolverOptions::default()).unwrap().is_none());
+        assert!(expect_optimal(wyndor().solve().unwrap()).alternate_optima.is_empty());
+    }
+
+    #[test]
+    fn redundant_row_through_the_optimum_is_degenerate() {
+        //x1 + x2 <= 8 passes through (2, 6) as well, one of the three tight rows keeps its slack basic at 0
+        let solution = expect_optimal(wyndor().add_constraint(vec![1.0, 1.0], Relation::Le, 8.0).solve().unwrap());
+        assert!((solution.objective - 36.0).abs() < 1e-9);
+        assert_eq!(solution.degenerate.len(), 1, "{:?}", solution.degenerate);
+        let col = solution.degenerate[0];
+        assert!(solution.basis.contains(&col) && col >= 2 && solution.x[col].abs() < 1e-9);
+        assert!(expect_optimal(wyndor().solve().unwrap()).degenerate.is_empty());
+    }
+
+    #[test]
+    fn warm_start_from_the_previous_basis() {
+        let options = SolverOptions::default();
+        for seed in 0..10 {
+            let (problem, _) = crate::test_support::random_feasible_lp(seed, 8, 10);
+            let (c, a, b, relations) = problem.to_arrays();
+            let cold = expect_optimal(simplex_solver_two_phase(c.clone(), &a, &b, &relations, &options).unwrap());
+            //a slightly looser problem, the old basis stays feasible and optimal
+            let b = &b + &Array1::from_iter(relations.iter().map(|rel| match rel {
+                Relation::Le => 0.01,
+                Relation::Ge => -0.01,
+                Relation::Eq => 0.0,
+            }));
+            let fresh = expect_optimal(simplex_solver_two_phase(c.clone(), &a, &b, &relations, &options).unwrap());
+            let warm = expect_optimal(simplex_solver_warm(c, &a, &b, &relations, &cold.basis, &options).unwrap());
+            crate::test_support::assert_objective_close(fresh.objective, warm.objective, 1e-9);
+            assert!(warm.iterations < fresh.iterations, "seed {}: {} warm vs {} cold", seed, warm.iterations, fresh.iterations);
+        }
+    }
+
+    #[test]
+    fn warm_start_falls_back_on_a_bad_basis() {
+        let (c, a, b) = (array![3.0, 5.0], array![[1.0, 0.0], [0.0, 2.0], [3.0, 2.0]], array![4.0, 12.0, 18.0]);
+        let relations = [Relation::Le; 3];
+        //too short, a repeated column, a column past the tableau, and a feasible basis at (4, 3) that isn't optimal
+        for start in [vec![0, 1], vec![0, 0, 1], vec![9, 1, 2], vec![0, 1, 3]] {
+            let solution: Solution = expect_optimal(simplex_solver_warm(c.clone(), &a, &b, &relations, &start, &SolverOptions::default()).unwrap());
+            assert!((solution.objective - 36.0).abs() < 1e-9, "{:?}", start);
+        }
+    }
+
+    #[test]
+    fn every_pivot_rule_reaches_the_same_optimum() {
+        let rules = [PivotRule::Dantzig, PivotRule::Bland, PivotRule::SteepestEdge, PivotRule::Devex, PivotRule::Lexicographic];
+        for seed in 0..15 {
+            let (problem, _) = crate::test_support::random_feasible_lp(seed, 6, 6);
+            let objectives: Vec<f64> =
+                rules.iter().map(|&pivot_rule| expect_optimal(problem.solve_with(&SolverOptions { pivot_rule, ..SolverOptions::default() }).unwrap()).objective).collect();
+            for objective in &objectives {
+                crate::test_support::assert_objective_close(objectives[0], *objective, 1e-9);
+            }
+        }
+        assert!((expect_optimal(solve_beale(PivotRule::Bland)).objective - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn named_values_map_names_to_values() {
+        let named = expect_optimal(wyndor().var_names(vec!["doors".to_string(), "windows".to_string()]).solve().unwrap());
+        let values = named.named_values();
+        let expected = [("doors", 2.0), ("windows", 6.0), ("s1", 2.0), ("s2", 0.0), ("s3", 0.0)];
+        assert_eq!(values.len(), expected.len());
+        for (name, value) in expected {
+            assert!((values[name] - value).abs() < 1e-9, "{}: {}", name, values[name]);
+        }
+        let unnamed = expect_optimal(wyndor().solve().unwrap()).named_values();
+        assert_eq!(unnamed.keys().cloned().collect::<Vec<_>>(), vec!["s1", "s2", "s3", "x1", "x2"]);
+        assert!((unnamed["x2"] - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn negative_rhs_rows_are_not_started_from_their_slack() {
+        //min x1 + 2x2 st -x1 - x2 <= -2, x1 <= 1 is 3 at (1, 1), the slack basis would start at the infeasible origin
+        let problem = LpProblem::new()
+            .objective(vec![1.0, 2.0])
+            .add_constraint(vec![-1.0, -1.0], Relation::Le, -2.0)
+            .add_constraint(vec![1.0, 0.0], Relation::Le, 1.0)
+            .minimize();
+        let solution = expect_optimal(problem.solve().unwrap());
+        assert!((solution.objective - 3.0).abs() < 1e-9);
+        crate::test_support::assert_solution_close(&[1.0, 1.0], &solution.structural().to_owned(), 1e-9);
+        assert!(crate::verify::verify_solution(&problem, &solution.x, 1e-9).is_ok());
+
+        //the same rows with their slack columns handed to simplex_solver
+        let a = array![[-1.0, -1.0, 1.0, 0.0], [1.0, 0.0, 0.0, 1.0]];
+        let min: Solution = expect_optimal(simplex_solver_min(array![1.0, 2.0], &a, &array![-2.0, 1.0], &SolverOptions::default()).unwrap());
+        assert!((min.objective - 3.0).abs() < 1e-9, "{:?}", min.x);
+        assert!(min.x.iter().all(|&val| val >= -1e-9));
+
+        //a >= row with a negative rhs holds at the origin: x1 - x2 >= -1 and x1 <= 3, max x1 + x2 is 7 at (3, 4)
+        let ge = LpProblem::new().objective(vec![1.0, 1.0]).add_constraint(vec![1.0, -1.0], Relation::Ge, -1.0).add_constraint(vec![1.0, 0.0], Relation::Le, 3.0).maximize();
+        let solution = expect_optimal(ge.solve().unwrap());
+        assert!((solution.objective - 7.0).abs() < 1e-9);
+        assert_eq!(solution.row_signs, vec![-1.0, 1.0]);
+    }
+
+    #[test]
+    fn pivots_and_solve_time_are_reported() {
+        let solution = expect_optimal(wyndor().solve().unwrap());
+        assert!(solution.iterations > 0);
+        assert!(solution.solve_time > Duration::ZERO);
+        //the revised solver counts the same pivots
+        let (c, a, b, relations) = wyndor().to_arrays();
+        let revised = expect_optimal(crate::revised::revised_simplex(c, &a, &b, &relations, &SolverOptions::default()).unwrap());
+        assert_eq!(revised.iterations, solution.iterations);
+        //a problem that's optimal at its starting basis takes none
+        assert_eq!(expect_optimal(wyndor().set_objective_coeff(0, -3.0).set_objective_coeff(1, -5.0).solve().unwrap()).iterations, 0);
+    }
+
+    #[test]
+    fn lexicographic_ratio_test_does_not_cycle_on_beale() {
+        let solution = expect_optimal(solve_beale(PivotRule::Lexicographic));
+        assert!((solution.objective - 0.05).abs() < 1e-9);
+        crate::test_support::assert_solution_close(&[0.04, 0.0, 1.0, 0.0], &solution.structural().to_owned(), 1e-9);
+        //it keeps Dantzig's entering column, so it pivots no more than Bland does here
+        assert!(solution.iterations <= expect_optimal(solve_beale(PivotRule::Bland)).iterations);
+    }
+
+    #[test]
+    fn lexicographic_ratio_test_picks_one_tied_row() {
+        //both rows allow a step of 2. divided by their pivot entries row 0 has 1 under column 3 and row 1 has 0, the
+        //comparison starts from the last column so row 1 is the smaller one (Dantzig's lowest index takes row 0)
+        let tableau = array![[0.0, 0.0, 1.0, 1.0, 2.0], [0.0, 1.0, 2.0, 0.0, 4.0], [0.0, 0.0, -1.0, 0.0, 0.0]];
+        let selection = Selection::new(&SolverOptions { pivot_rule: PivotRule::Lexicographic, ..SolverOptions::default() }, 0);
+        let step = find_pivot_row(&tableau, 2, 2, &[3, 1], &ColumnBounds::unbounded(4), selection, &1e-9);
+        assert!(matches!(step, Some(Step::Pivot(1))));
+    }
+
+    #[test]
+    fn unbounded_is_a_status_not_a_message() {
+        //max x1 + x2 st -x1 + x2 <= 1, x1 - 2x2 <= 2: the ray (2, 1) never leaves the region
+        let (c, a, b) = (array![1.0, 1.0], array![[-1.0, 1.0], [1.0, -2.0]], array![1.0, 2.0]);
+        let options = SolverOptions::default();
+        let with_slacks = ndarray::concatenate![Axis(1), a, Array2::eye(2)];
+        assert_eq!(simplex_solver(c.clone(), &with_slacks, &b, &options).unwrap(), SolveResult::Unbounded);
+        assert_eq!(simplex_solver_two_phase(c.clone(), &a, &b, &[Relation::Le; 2], &options).unwrap(), SolveResult::Unbounded);
+        assert_eq!(crate::revised::revised_simplex(c, &a, &b, &[Relation::Le; 2], &options).unwrap(), SolveResult::Unbounded);
+    }
+
+    //one region, several objectives close to each other like in a scenario analysis
+    fn scenarios(seed: u64) -> (Array2<f64>, Array1<f64>, Vec<Relation>, Vec<Array1<f64>>) {
+        let (c, a, b, relations) = crate::test_support::random_feasible_lp(seed, 10, 8).0.to_arrays();
+        let objectives = (0..6).map(|k| c.iter().enumerate().map(|(j, &coeff)| coeff + ((j * 7 + k * 3) % 5) as f64 * 0.25).collect()).collect();
+        (a, b, relations, objectives)
+    }
+
+    #[test]
+    fn solve_many_matches_independent_solves_in_fewer_pivots() {
+        let options = SolverOptions::default();
+        let (mut batch_pivots, mut independent_pivots) = (0, 0);
+        for seed in 0..10 {
+            let (a, b, relations, objectives) = scenarios(seed);
+            let batch = solve_many(&a, &b, &relations, &objectives, &options).unwrap();
+            assert_eq!(batch.len(), objectives.len());
+            for (c, result) in objectives.iter().zip(batch) {
+                let alone = expect_optimal(simplex_solver_two_phase(c.clone(), &a, &b, &relations, &options).unwrap());
+                let warm = expect_optimal(result);
+                crate::test_support::assert_objective_close(alone.objective, warm.objective, 1e-9);
+                batch_pivots += warm.iterations;
+                independent_pivots += alone.iterations;
+            }
+        }
+        assert!(batch_pivots < independent_pivots, "{} batch pivots against {} independent", batch_pivots, independent_pivots);
+    }
+
+    #[test]
+    fn solve_many_is_infeasible_for_every_objective() {
+        let (a, b) = (array![[1.0, 1.0], [1.0, 1.0]], array![1.0, 3.0]);
+        let objectives = [array![1.0, 0.0], array![0.0, 1.0], array![1.0, 1.0]];
+        let results = solve_many(&a, &b, &[Relation::Le, Relation::Ge], &objectives, &SolverOptions::default()).unwrap();
+        assert_eq!(results, vec![SolveResult::Infeasible; 3]);
+    }
+
+    #[test]
+    fn format_tableau_aligns_labeled_columns() {
+        let tableau = array![[1.0, 0.5, 0.0, 4.0], [0.0, -12.25, 1.0, 100.0], [0.0, 3.0, 0.0, 36.0]];
+        let text = format_tableau(&tableau, &["doors".to_string(), "w".to_string()]);
+        let lines: Vec<&str> = text.lines().collect();
+        //header, rule, two constraint rows, rule, objective row
+        assert_eq!(lines.len(), 6);
+        let header: Vec<&str> = lines[0].split('|').map(str::trim).collect();
+        assert_eq!(header, vec!["doors", "w", "x3", "rhs"]);
+        assert!(lines[1].chars().all(|ch| ch == '-' || ch == '+'));
+        assert_eq!(lines[1], lines[4]);
+        assert!(lines[5].contains("36.0000") && lines[3].contains("-12.2500"));
+        assert!(lines.iter().all(|line| line.chars().count() == lines[0].chars().count()), "{}", text);
+    }
+
+    #[test]
+    fn solution_tableau_names_slacks() {
+        let text = expect_optimal(wyndor().solve().unwrap()).format_tableau();
+        let header: Vec<&str> = text.lines().next().unwrap().split('|').map(str::trim).collect();
+        assert_eq!(header, vec!["x1", "x2", "s1", "s2", "s3", "rhs"]);
+        assert_eq!(text.lines().count(), 2 + 3 + 2); //header and rule, the rows, rule and objective
+    }
+
+    #[test]
+    fn an_unblocked_column_is_unbounded_before_any_pivot() {
+        //max 5x1 + x2 st x1 - x2 <= 1, x1 <= 3: Dantzig's x1 is blocked but nothing blocks x2, so no pivot is needed
+        //to see it and no iterations are allowed at all
+        let (c, a, b) = (array![5.0, 1.0], array![[1.0, -1.0], [1.0, 0.0]], array![1.0, 3.0]);
+        let options = SolverOptions { max_iterations: Some(0), ..SolverOptions::default() };
+        let with_slacks = ndarray::concatenate![Axis(1), a, Array2::eye(2)];
+        assert_eq!(simplex_solver(c.clone(), &with_slacks, &b, &options).unwrap(), SolveResult::Unbounded);
+        assert_eq!(simplex_solver_two_phase(c, &a, &b, &[Relation::Le; 2], &options).unwrap(), SolveResult::Unbounded);
+        //max x1 st x1 - x2 <= 1 only shows its ray along x2 once x1 is in, that one still takes a pivot
+        let (c, a, b) = (array![1.0, 0.0], array![[1.0, -1.0]], array![1.0]);
+        assert_eq!(simplex_solver_two_phase(c.clone(), &a, &b, &[Relation::Le], &SolverOptions::default()).unwrap(), SolveResult::Unbounded);
+        let result = simplex_solver_two_phase(c, &a, &b, &[Relation::Le], &options).unwrap();
+        assert!(matches!(result, SolveResult::IterationLimit(_)), "{:?}", result);
+    }
+
+    #[test]
+    fn find_feasible_returns_a_point_of_the_rows() {
+        //x1 + x2 >= 2, x1 - x2 = 1, x1 <= 4
+        let a = array![[1.0, 1.0], [1.0, -1.0], [1.0, 0.0]];
+        let b = array![2.0, 1.0, 4.0];
+        let relations = [Relation::Ge, Relation::Eq, Relation::Le];
+        let x: Array1<f64> = find_feasible(&a, &b, &relations, &SolverOptions::default()).unwrap().expect("the rows hold at (1.5, 0.5)");
+        assert_eq!(x.len(), 2);
+        let lhs = a.dot(&x);
+        assert!(lhs[0] >= 2.0 - 1e-9 && (lhs[1] - 1.0).abs() < 1e-9 && lhs[2] <= 4.0 + 1e-9, "{:?}", x);
+        assert!(x.iter().all(|&val| val >= -1e-9));
+    }
+
+    #[test]
+    fn find_feasible_is_none_for_contradicting_rows() {
+        let a = array![[1.0, 1.0], [1.0, 1.0]];
+        let found = find_feasible(&a, &array![1.0, 3.0], &[Relation::Le, Relation::Ge], &SolverOptions::default()).unwrap();
+        assert_eq!(found, None);
+    }
+
+    #[test]
+    fn short_objective_gets_zeros_for_the_slacks() {
+        let a = array![[1.0, 0.0, 1.0, 0.0, 0.0], [0.0, 2.0, 0.0, 1.0, 0.0], [3.0, 2.0, 0.0, 0.0, 1.0]];
+        let b = array![4.0, 12.0, 18.0];
+        let options = SolverOptions::default();
+        let short = expect_optimal(simplex_solver(array![3.0, 5.0], &a, &b, &options).unwrap());
+        let padded = expect_optimal(simplex_solver(array![3.0, 5.0, 0.0, 0.0, 0.0], &a, &b, &options).unwrap());
+        assert_eq!(short.x, padded.x);
+        assert_eq!(short.objective, 36.0);
+        assert_eq!(short.costs.len(), 5);
+        //minimizing goes through the same padding, one more column than a has is still a mismatch
+        let min = expect_optimal(simplex_solver_min(array![-3.0, -5.0], &a, &b, &options).unwrap());
+        assert_eq!(min.objective, -36.0);
+        let long = simplex_solver(Array1::from(vec![1.0; 6]), &a, &b, &options);
+        assert!(matches!(long, Err(SolverError::DimensionMismatch { .. })), "{:?}", long);
+    }
+
+    #[test]
+    fn no_rows_leave_it_to_the_bounds() {
+        let (a, b) = (Array2::<f64>::zeros((0, 2)), Array1::<f64>::zeros(0));
+        let options = SolverOptions::default();
+        //x >= 0 only: nothing improves on the origin for negative costs, a positive one goes to infinity
+        let origin = expect_optimal(simplex_solver_two_phase(array![-1.0, -2.0], &a, &b, &[], &options).unwrap());
+        assert_eq!((origin.x.to_vec(), origin.objective), (vec![0.0, 0.0], 0.0));
+        assert_eq!(simplex_solver_two_phase(array![1.0, -2.0], &a, &b, &[], &options).unwrap(), SolveResult::Unbounded);
+        assert_eq!(simplex_solver(array![1.0, 0.0], &a, &b, &options).unwrap(), SolveResult::Unbounded);
+        //finite upper bounds hold it, each var at the end its cost favors
+        let bounded = expect_optimal(simplex_solver_bounded(array![1.0, -2.0], &a, &b, &[], &[(1.0, 3.0), (-1.0, 5.0)], &options).unwrap());
+        crate::test_support::assert_solution_close(&[3.0, -1.0], &bounded.structural().to_owned(), 1e-9);
+        crate::test_support::assert_objective_close(5.0, bounded.objective, 1e-9);
+    }
+
+    #[test]
+    fn unit_columns_of_eq_rows_save_phase_one() {
+        //x1 + x2 + x3 = 4, x1 + 2x2 + x4 = 6: x3 and x4 are already the rows' unit columns. the same rows with x3 and x4
+        //scaled by 2 have none, so both artificials have to be pivoted out
+        let unit = array![[1.0, 1.0, 1.0, 0.0], [1.0, 2.0, 0.0, 1.0]];
+        let scaled = array![[1.0, 1.0, 2.0, 0.0], [1.0, 2.0, 0.0, 2.0]];
+        let (b, relations, options) = (array![4.0, 6.0], [Relation::Eq; 2], SolverOptions::default());
+        //a zero objective leaves phase two nothing to do, every iteration is phase one
+        let phase_one = |a: &Array2<f64>| expect_optimal(simplex_solver_two_phase(Array1::zeros(4), a, &b, &relations, &options).unwrap()).iterations;
+        assert_eq!(phase_one(&unit), 0);
+        assert_eq!(phase_one(&scaled), 2);
+
+        //max 4x1 + 6x2 - 10 once x3 and x4 are substituted, 10 at (2, 2) either way
+        let crashed = expect_optimal(simplex_solver_two_phase(array![2.0, 3.0, -1.0, -1.0], &unit, &b, &relations, &options).unwrap());
+        let cold = expect_optimal(simplex_solver_two_phase(array![2.0, 3.0, -0.5, -0.5], &scaled, &b, &relations, &options).unwrap());
+        assert_eq!(crashed.objective, 10.0);
+        assert_eq!(cold.objective, 10.0);
+        assert_eq!(crashed.structural().to_vec()[..2], cold.structural().to_vec()[..2]);
+        assert!(crashed.iterations < cold.iterations);
+    }
+
+    #[test]
+    fn condition_estimate_flags_a_badly_scaled_problem() {
+        let healthy = expect_optimal(wyndor().solve().unwrap());
+        assert!(healthy.condition_estimate() < 10.0, "{}", healthy.condition_estimate());
+        //max x1 + x2 st 1e-6 x1 <= 1e-6, x2 <= 1 pivots on 1e-6 and on 1
+        let scaled = LpProblem::new().objective(vec![1.0, 1.0]).add_constraint(vec![1e-6, 0.0], Relation::Le, 1e-6).add_constraint(vec![0.0, 1.0], Relation::Le, 1.0).maximize();
+        let scaled = expect_optimal(scaled.solve().unwrap());
+        assert!(scaled.condition_estimate() > 1e5, "{}", scaled.condition_estimate());
+        //no pivots, nothing to warn about
+        let origin = expect_optimal(LpProblem::new().objective(vec![-1.0]).add_constraint(vec![1.0], Relation::Le, 1.0).maximize().solve().unwrap());
+        assert_eq!((origin.pivot_sizes, origin.condition_estimate()), (None, 1.0));
+    }
+
+    #[test]
+    fn solve_tableau_runs_the_loop_on_a_hand_built_tableau() {
+        //wyndor with its slacks, the objective row holds -c
+        let mut tableau = array![
+            [1.0, 0.0, 1.0, 0.0, 0.0, 4.0],
+            [0.0, 2.0, 0.0, 1.0, 0.0, 12.0],
+            [3.0, 2.0, 0.0, 0.0, 1.0, 18.0],
+            [-3.0, -5.0, 0.0, 0.0, 0.0, 0.0]
+        ];
+        let (result, mut basis) = solve_tableau(&mut tableau, &SolverOptions::default()).unwrap();
+        let solution = expect_optimal(result);
+        assert_eq!(solution.objective, 36.0);
+        assert_eq!(solution.x.to_vec(), vec![2.0, 6.0, 2.0, 0.0, 0.0]);
+        assert_eq!(solution.costs.to_vec(), vec![3.0, 5.0, 0.0, 0.0, 0.0]);
+        assert_eq!(tableau[[3, 5]], 36.0);
+        basis.sort_unstable();
+        assert_eq!(basis, vec![0, 1, 2]);
+
+        //a unit column with a cost starts basic and gets priced out: max x1 + x2 st x1 + x2 <= 3 with x2 as the unit
+        let mut priced = array![[1.0, 1.0, 3.0], [-1.0, -2.0, 0.0]];
+        let (result, basis) = solve_tableau(&mut priced, &SolverOptions::default()).unwrap();
+        assert_eq!((expect_optimal(result).objective, basis), (6.0, vec![1]));
+    }
+
+    #[test]
+    fn solve_tableau_needs_a_starting_basis() {
+        let mut no_unit = array![[2.0, 3.0, 4.0], [-1.0, -1.0, 0.0]];
+        assert!(matches!(solve_tableau(&mut no_unit, &SolverOptions::default()), Err(SolverError::NoStartingBasis)));
+        let mut negative = array![[1.0, 1.0, -4.0], [-1.0, 0.0, 0.0]];
+        assert!(matches!(solve_tableau(&mut negative, &SolverOptions::default()), Err(SolverError::NoStartingBasis)));
+    }
+
+    #[test]
+    fn dependent_eq_rows_are_flagged_not_infeasible() {
+        //x1 + x2 = 4, x1 - x2 = 0 and their sum 2x1 = 4: one of the three rows says nothing new. max x1 + 2x2, 6 at (2, 2)
+        let a = array![[1.0, 1.0], [1.0, -1.0], [2.0, 0.0]];
+        let relations = [Relation::Eq; 3];
+        let solution = expect_optimal(simplex_solver_two_phase(array![1.0, 2.0], &a, &array![4.0, 0.0, 4.0], &relations, &SolverOptions::default()).unwrap());
+        assert_eq!(solution.objective, 6.0);
+        assert_eq!(solution.x.to_vec(), vec![2.0, 2.0]);
+        assert_eq!(solution.redundant_rows().len(), 1);
+        //the same rows with a sum that doesn't add up can't all hold
+        let clash = simplex_solver_two_phase(array![1.0, 2.0], &a, &array![4.0, 0.0, 5.0], &relations, &SolverOptions::default()).unwrap();
+        assert_eq!(clash, SolveResult::Infeasible);
+        //nothing redundant in independent rows
+        assert!(expect_optimal(wyndor().solve().unwrap()).redundant_rows().is_empty());
+    }
+
+    #[test]
+    fn non_finite_input_is_an_error_not_a_panic() {
+        let (a, b) = (array![[1.0, 0.0], [0.0, 2.0], [3.0, 2.0]], array![4.0, 12.0, 18.0]);
+        let (relations, options) = ([Relation::Le; 3], SolverOptions::default());
+        let nan_c = simplex_solver_two_phase(array![3.0, f64::NAN], &a, &b, &relations, &options);
+        assert_eq!(nan_c, Err(SolverError::NonFiniteInput { location: InputLocation::Objective { col: 1 } }));
+        let inf_b = simplex_solver_two_phase(array![3.0, 5.0], &a, &array![4.0, f64::INFINITY, 18.0], &relations, &options);
+        assert_eq!(inf_b, Err(SolverError::NonFiniteInput { location: InputLocation::Rhs { row: 1 } }));
+        let mut inf_a = a.clone();
+        inf_a[[2, 0]] = f64::NEG_INFINITY;
+        let result = crate::revised::revised_simplex(array![3.0, 5.0], &inf_a, &b, &relations, &options);
+        assert_eq!(result, Err(SolverError::NonFiniteInput { location: InputLocation::Constraint { row: 2, col: 0 } }));
+        //through the builder too, and the message says where
+        let error = wyndor().objective(vec![f64::NAN, 5.0]).solve().unwrap_err();
+        assert_eq!(error.to_string(), format!("{} is NaN or infinite", InputLocation::Objective { col: 0 }));
+    }
+
+    #[test]
+    fn binding_rows_are_the_ones_without_slack() {
+        //at (2, 6): x1 <= 4 has 2 left, 2x2 <= 12 and 3x1 + 2x2 <= 18 are tight, x1 + x2 >= 2 has 6 over,
+        //x1 - x2 = -4 is an = row and x1 + x2 <= 8 + 1e-12 is tight within epsilon
+        let problem = wyndor()
+            .add_constraint(vec![1.0, 1.0], Relation::Ge, 2.0)
+            .add_constraint(vec![1.0, -1.0], Relation::Eq, -4.0)
+            .add_constraint(vec![1.0, 1.0], Relation::Le, 8.0 + 1e-12);
+        let solution = expect_optimal(problem.solve().unwrap());
+        crate::test_support::assert_solution_close(&[2.0, 6.0], &solution.structural().to_owned(), 1e-9);
+        assert_eq!(solution.binding_constraints, vec![1, 2, 4, 5]);
+        let tight = SolverOptions { epsilon: 1e-14, ..SolverOptions::default() };
+        assert_eq!(expect_optimal(problem.solve_with(&tight).unwrap()).binding_constraints, vec![1, 2, 4]);
+    }
+
+    //max x1 + ... + x4 st x_j <= 1 and x1 + ... + x4 <= 3: every column ties for entering, the last ones for leaving
+    fn all_tied() -> LpProblem {
+        let mut problem = LpProblem::new().objective(vec![1.0; 4]).add_constraint(vec![1.0; 4], Relation::Le, 3.0).maximize();
+        for var in 0..4 {
+            let mut unit = vec![0.0; 4];
+            unit[var] = 1.0;
+            problem = problem.add_constraint(unit, Relation::Le, 1.0);
+        }
+        problem
+    }
+
+    fn pivot_path(tie_break: TieBreak) -> (Vec<(usize, Option<usize>)>, f64) {
+        let options = SolverOptions { tie_break, record_log: true, ..SolverOptions::default() };
+        let solution = expect_optimal(all_tied().solve_with(&options).unwrap());
+        (solution.log.iter().map(|entry| (entry.entering, entry.pivot_row)).collect(), solution.objective)
+    }
+
+    #[test]
+    fn lowest_index_ties_take_the_same_path_every_time() {
+        let (path, objective) = pivot_path(TieBreak::LowestIndex);
+        assert_eq!(objective, 3.0);
+        assert_eq!(path.iter().map(|&(col, _)| col).take(3).collect::<Vec<_>>(), vec![0, 1, 2]);
+        for _ in 0..5 {
+            assert_eq!(pivot_path(TieBreak::LowestIndex).0, path);
+        }
+    }
+
+    #[test]
+    fn seeded_random_ties_repeat_per_seed() {
+        let paths: Vec<_> = (0..20).map(|seed| pivot_path(TieBreak::Random { seed })).collect();
+        for (seed, (path, objective)) in paths.iter().enumerate() {
+            assert_eq!(*objective, 3.0);
+            assert_eq!(&pivot_path(TieBreak::Random { seed: seed as u64 }).0, path, "seed {}", seed);
+        }
+        //the seeds don't all agree with each other, or with the lowest index
+        let lowest = pivot_path(TieBreak::LowestIndex).0;
+        assert!(paths.iter().any(|(path, _)| *path != lowest));
+        assert!(paths.iter().any(|(path, _)| *path != paths[0].0));
+    }
+
+    #[test]
+    fn basis_matrix_times_its_inverse_is_the_identity() {
+        let mut problems: Vec<LpProblem> = (0..10).map(|seed| crate::test_support::random_feasible_lp(seed, 6, 4).0).collect();
+        //a row with a negative rhs gets negated inside the solver, the matrices are for it as given
+        problems.push(wyndor().add_constraint(vec![-1.0, -1.0], Relation::Le, -1.0).add_constraint(vec![1.0, 0.0], Relation::Ge, 1.0));
+        for problem in problems {
+            let (c, a, b, relations) = problem.to_arrays();
+            let solution = expect_optimal(simplex_solver_two_phase(c, &a, &b, &relations, &SolverOptions::default()).unwrap());
+            let (basis_matrix, inverse) = (solution.basis_matrix(&a, &relations), solution.basis_inverse());
+            let product = basis_matrix.dot(&inverse);
+            let identity = Array2::<f64>::eye(b.len());
+            assert!(product.iter().zip(&identity).all(|(val, unit)| (val - unit).abs() < 1e-9), "{:?}", product);
+            //B⁻¹b is the value of every basic var, 0 for a leftover artificial
+            let basic: Vec<f64> = solution.basis.iter().map(|&col| solution.x.get(col).copied().unwrap_or(0.0)).collect();
+            crate::test_support::assert_solution_close(&basic, &inverse.dot(&b), 1e-9);
+        }
+    }
+
+    #[test]
+    fn a_min_stage_hands_its_basis_to_a_max_stage() {
+        //min x1 + x2 over x1 + x2 >= 2, x1 + x2 <= 10, x1 <= 8 ends on the face x1 + x2 = 2, max x2 then moves
+        //along it to (0, 2) without leaving it
+        let a = array![[1.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let (b, relations) = (array![2.0, 10.0, 8.0], [Relation::Ge, Relation::Le, Relation::Le]);
+        let stages = [(array![1.0, 1.0], Sense::Minimize), (array![0.0, 1.0], Sense::Maximize)];
+        let options = SolverOptions { record_log: true, ..SolverOptions::default() };
+        let (result, reached) = simplex_solver_staged(&stages, &a, &b, &relations, &options).unwrap();
+        let solution = expect_optimal(result);
+        assert_eq!(reached.iter().map(|stage| stage.objective).collect::<Vec<_>>(), vec![2.0, 2.0]);
+        assert_eq!(solution.structural().to_vec(), vec![0.0, 2.0]);
+        assert_eq!((solution.sense, solution.objective), (Sense::Maximize, 2.0));
+
+        //the max stage starts from where the min stage stopped: replaying its pivots on that basis gives its final one
+        assert_eq!(solution.basis, reached[1].basis);
+        assert!(reached[1].iterations > 0);
+        let phase_one = solution.iterations - reached.iter().map(|stage| stage.iterations).sum::<usize>();
+        let mut basis = reached[0].basis.clone();
+        for entry in &solution.log[phase_one + reached[0].iterations..] {
+            if let (Some(row), Some(leaving)) = (entry.pivot_row, entry.leaving) {
+                assert_eq!(basis[row], leaving);
+                basis[row] = entry.entering;
+            }
+        }
+        assert_eq!(basis, reached[1].basis);
+    }
+}
@@ -0,0 +1,86 @@
+use ndarray::Array2;
+use alloc::vec::Vec;
+
+use crate::problem::LpProblem;
+use crate::simplex::Relation;
+
+//constraint matrix as (row, col, value) triplets, only the nonzeros are stored
+//repeated positions add up, like they would when assembling a model term by term
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SparseMatrix {
+    pub nrows: usize,
+    pub ncols: usize,
+    pub entries: Vec<(usize, usize, f64)>,
+}
+
+impl SparseMatrix {
+    pub fn new(nrows: usize, ncols: usize) -> Self {
+        SparseMatrix { nrows, ncols, entries: Vec::new() }
+    }
+
+    //row and col have to be inside the shape given to new
+    pub fn push(&mut self, row: usize, col: usize, value: f64) {
+        self.entries.push((row, col, value));
+    }
+
+    pub fn to_dense(&self) -> Array2<f64> {
+        let mut dense = Array2::zeros((self.nrows, self.ncols));
+        for &(row, col, value) in &self.entries {
+            dense[[row, col]] += value;
+        }
+        dense
+    }
+}
+
+impl LpProblem {
+    //one constraint per matrix row with the given relation and rhs, the solvers still work on the dense form
+    pub fn from_sparse(objective: Vec<f64>, matrix: &SparseMatrix, relations: &[Relation], rhs: &[f64]) -> Self {
+        let dense = matrix.to_dense();
+        dense
+            .rows()
+            .into_iter()
+            .zip(relations.iter().zip(rhs))
+            .fold(LpProblem::new().objective(objective), |problem, (row, (&relation, &value))| {
+                problem.add_constraint(row.to_vec(), relation, value)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::SolveResult;
+    use crate::test_support::random_feasible_lp;
+
+    #[test]
+    fn repeated_entries_add_up() {
+        let mut matrix = SparseMatrix::new(2, 3);
+        matrix.push(0, 2, 1.5);
+        matrix.push(1, 0, -1.0);
+        matrix.push(0, 2, 0.5);
+        assert_eq!(matrix.to_dense(), ndarray::array![[0.0, 0.0, 2.0], [-1.0, 0.0, 0.0]]);
+    }
+
+    #[test]
+    fn sparse_and_dense_input_solve_the_same() {
+        for seed in 0..10 {
+            let (dense, _) = random_feasible_lp(seed, 6, 8);
+            let (c, a, b, relations) = dense.to_arrays();
+            let mut matrix = SparseMatrix::new(a.nrows(), a.ncols());
+            for ((row, col), &value) in a.indexed_iter().filter(|(_, val)| **val != 0.0) {
+                //half of every entry twice, the way terms of a model get assembled one by one
+                matrix.push(row, col, value / 2.0);
+                matrix.push(row, col, value / 2.0);
+            }
+            let sparse = LpProblem::from_sparse(c.to_vec(), &matrix, &relations, b.as_slice().unwrap()).maximize();
+            assert_eq!(sparse.constraints, dense.constraints);
+            match (dense.solve().unwrap(), sparse.solve().unwrap()) {
+                (SolveResult::Optimal(dense), SolveResult::Optimal(sparse)) => {
+                    assert_eq!(dense.x, sparse.x);
+                    assert_eq!(dense.objective, sparse.objective);
+                }
+                other => panic!("seed {}: {:?}", seed, other),
+            }
+        }
+    }
+}
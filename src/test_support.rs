@@ -0,0 +1,280 @@
+use ndarray::Array1;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::problem::LpProblem;
+use crate::scalar::Scalar;
+use crate::simplex::{splitmix64, Relation, Sense, Solution, SolveResult};
+use crate::verify::verify_solution;
+
+//both asserts use the same mixed tolerance: a value passes when |expected - actual| <= eps * max(1, |expected|),
+//absolute around 0 and relative for large values, so one eps works for coefficients of any size
+
+fn close(expected: f64, actual: f64, eps: f64) -> bool {
+    (expected - actual).abs() <= eps * expected.abs().max(1.0)
+}
+
+//panics at the caller unless x has the expected length and every entry is close
+#[track_caller]
+pub fn assert_solution_close(expected: &[f64], actual: &Array1<f64>, eps: f64) {
+    assert_eq!(expected.len(), actual.len(), "solution has {} entries, expected {}", actual.len(), expected.len());
+    let wrong: Vec<String> = expected
+        .iter()
+        .zip(actual)
+        .enumerate()
+        .filter(|&(_, (&e, &a))| !close(e, a, eps))
+        .map(|(j, (e, a))| format!("x[{}] = {} (expected {})", j, a, e))
+        .collect();
+    assert!(wrong.is_empty(), "solution differs by more than {}: {}", eps, wrong.join(", "));
+}
+
+#[track_caller]
+pub fn assert_objective_close(expected: f64, actual: f64, eps: f64) {
+    assert!(close(expected, actual, eps), "objective is {}, expected {} (eps {})", actual, expected, eps);
+}
+
+//the solution of an optimal result, panics at the caller with whatever else it was
+#[track_caller]
+pub fn expect_optimal<T: Scalar>(result: SolveResult<T>) -> Solution<T> {
+    match result {
+        SolveResult::Optimal(solution) => *solution,
+        other => panic!("expected an optimum, got {:?}", other),
+    }
+}
+
+//max 3x1 + 5x2 st x1 <= 4, 2x2 <= 12, 3x1 + 2x2 <= 18, the textbook example the tests share: optimum 36 at (2, 6)
+//with the second and third rows binding, duals (0, 1.5, 1)
+pub fn wyndor() -> LpProblem {
+    LpProblem::new()
+        .objective(vec![3.0, 5.0])
+        .add_constraint(vec![1.0, 0.0], Relation::Le, 4.0)
+        .add_constraint(vec![0.0, 2.0], Relation::Le, 12.0)
+        .add_constraint(vec![3.0, 2.0], Relation::Le, 18.0)
+        .maximize()
+}
+
+//panics at the caller unless the result is optimal and its objective is at least as good as at `point`, a feasible
+//point of the problem like the one random_feasible_lp returns. the point's objective gets the usual mixed tolerance
+#[track_caller]
+pub fn assert_at_least_as_good(problem: &LpProblem, result: &SolveResult, point: &Array1<f64>, eps: f64) {
+    let SolveResult::Optimal(solution) = result else {
+        panic!("problem with a feasible point came out {:?}", result);
+    };
+    let at_point = problem.evaluate_objective(point);
+    let slack = eps * at_point.abs().max(1.0);
+    let good_enough = match problem.sense {
+        Sense::Maximize => solution.objective >= at_point - slack,
+        Sense::Minimize => solution.objective <= at_point + slack,
+    };
+    assert!(good_enough, "objective is {}, worse than {} at the feasible point {:?}", solution.objective, at_point, point.to_vec());
+}
+
+//random max problem in n vars with m rows (at least 1) that is feasible and bounded, with a feasible point of it.
+//the point is drawn first and every row is made to hold there: a <= row with room to spare, a >= row with some
+//surplus, every fifth row an = row exactly. row 0 has all positive coeffs, which keeps x >= 0 bounded. coeffs are
+//multiples of 0.25 so the problems print readably, the same seed always gives the same problem
+pub fn random_feasible_lp(seed: u64, m: usize, n: usize) -> (LpProblem, Array1<f64>) {
+    let mut rng = SplitMix(seed);
+    let point: Array1<f64> = (0..n).map(|_| rng.quarters(0.0, 5.0)).collect();
+    let mut problem = LpProblem::new().objective((0..n).map(|_| rng.quarters(-2.0, 8.0)).collect()).maximize();
+    for row in 0..m.max(1) {
+        let coeffs: Vec<f64> = (0..n).map(|_| if row == 0 { rng.quarters(0.25, 4.0) } else { rng.quarters(-2.0, 6.0) }).collect();
+        let lhs: f64 = coeffs.iter().zip(&point).map(|(coeff, val)| coeff * val).sum();
+        let (relation, rhs) = match row {
+            0 => (Relation::Le, lhs + rng.quarters(0.0, 10.0)),
+            _ if row % 5 == 4 => (Relation::Eq, lhs),
+            _ if rng.quarters(0.0, 1.0) < 0.7 => (Relation::Le, lhs + rng.quarters(0.0, 10.0)),
+            _ => (Relation::Ge, lhs - rng.quarters(0.0, 10.0)),
+        };
+        problem = problem.add_constraint(coeffs, relation, rhs);
+    }
+    (problem, point)
+}
+
+//what every optimal result has to satisfy: x within eps of every row and bound, and the reported objective is the
+//problem's objective at x within the mixed tolerance. Err says what failed
+pub fn check_optimal(problem: &LpProblem, result: &SolveResult, eps: f64) -> Result<(), String> {
+    let SolveResult::Optimal(solution) = result else {
+        return Err(format!("expected an optimum, got {:?}", result));
+    };
+    if let Err(violations) = verify_solution(problem, &solution.x, eps) {
+        return Err(format!("x = {:?} violates {:?}", solution.x.to_vec(), violations));
+    }
+    let structural = solution.x.iter().take(problem.num_vars()).copied().collect();
+    let at_x = problem.evaluate_objective(&structural);
+    if !close(at_x, solution.objective, eps) {
+        return Err(format!("reported objective {} but c·x is {}", solution.objective, at_x));
+    }
+    Ok(())
+}
+
+//a random_feasible_lp instance that made a property fail, after shrinking
+#[derive(Debug, Clone)]
+pub struct Counterexample {
+    pub seed: u64,
+    pub problem: LpProblem,
+    pub point: Array1<f64>, //feasible point of the shrunk problem
+    pub message: String,    //what the property said about the shrunk problem
+}
+
+//property test over `cases` seeded instances of 1..=max_rows rows and 1..=max_vars vars, the property gets the
+//problem and its known feasible point. the first failure gets shrunk: rows after the first, then vars are dropped one
+//at a time for as long as the property keeps failing, so the counterexample can't lose anything and still fail.
+//dropping a var moves its part a_ij·point_j into the rhs, which keeps the point feasible
+pub fn check_random_lps(
+    cases: u64,
+    max_rows: usize,
+    max_vars: usize,
+    property: impl Fn(&LpProblem, &Array1<f64>) -> Result<(), String>,
+) -> Result<(), Box<Counterexample>> {
+    for seed in 0..cases {
+        let mut sizes = SplitMix(seed);
+        let rows = 1 + (sizes.next() % max_rows.max(1) as u64) as usize;
+        let vars = 1 + (sizes.next() % max_vars.max(1) as u64) as usize;
+        let (problem, point) = random_feasible_lp(seed, rows, vars);
+        if let Err(message) = property(&problem, &point) {
+            return Err(Box::new(shrink(Counterexample { seed, problem, point, message }, &property)));
+        }
+    }
+    Ok(())
+}
+
+fn shrink(mut case: Counterexample, property: &impl Fn(&LpProblem, &Array1<f64>) -> Result<(), String>) -> Counterexample {
+    loop {
+        let rows = (1..case.problem.constraints.len()).rev().map(|row| {
+            let mut problem = case.problem.clone();
+            problem.constraints.remove(row);
+            (problem, case.point.clone())
+        });
+        let vars = (0..case.problem.num_vars()).rev().filter(|_| case.problem.num_vars() > 1).map(|var| without_var(&case.problem, &case.point, var));
+        let smaller = rows.chain(vars).find_map(|(problem, point)| property(&problem, &point).err().map(|message| (problem, point, message)));
+        match smaller {
+            Some((problem, point, message)) => case = Counterexample { problem, point, message, ..case },
+            None => return case,
+        }
+    }
+}
+
+//the problem with var held at its value in point and its column taken out
+fn without_var(problem: &LpProblem, point: &Array1<f64>, var: usize) -> (LpProblem, Array1<f64>) {
+    let mut problem = problem.clone();
+    let value = point[var];
+    if var < problem.objective.len() {
+        problem.objective_constant += problem.objective.remove(var) * value;
+    }
+    for con in &mut problem.constraints {
+        if var < con.coeffs.len() {
+            let part = con.coeffs.remove(var) * value;
+            con.rhs -= part;
+            con.lower = con.lower.map(|lo| lo - part);
+        }
+    }
+    if var < problem.bounds.len() {
+        problem.bounds.remove(var);
+    }
+    if var < problem.var_names.len() {
+        problem.var_names.remove(var);
+    }
+    let point = point.iter().enumerate().filter(|&(j, _)| j != var).map(|(_, &val)| val).collect();
+    (problem, point)
+}
+
+//the splitmix64 stream over a seed, enough randomness for test problems without pulling in an rng crate
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        let z = splitmix64(self.0);
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z
+    }
+
+    //a multiple of 0.25 in [lo, hi)
+    fn quarters(&mut self, lo: f64, hi: f64) -> f64 {
+        let steps = ((hi - lo) * 4.0) as u64;
+        lo + (self.next() % steps) as f64 * 0.25
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::revised::revised_simplex;
+    use crate::simplex::{simplex_solver_two_phase, SolverOptions};
+    use proptest::prelude::*;
+
+    #[test]
+    fn same_seed_same_problem() {
+        let (first, at) = random_feasible_lp(7, 6, 4);
+        let (second, again) = random_feasible_lp(7, 6, 4);
+        assert_eq!(at, again);
+        assert_eq!(first.objective, second.objective);
+        assert_eq!(first.constraints.len(), 6);
+        assert!(first.constraints.iter().zip(&second.constraints).all(|(a, b)| a.coeffs == b.coeffs && a.rhs == b.rhs));
+        assert!(verify_solution(&first, &at, 1e-9).is_ok());
+    }
+
+    #[test]
+    fn solver_beats_the_known_point() {
+        for seed in 0..200 {
+            let (problem, point) = random_feasible_lp(seed, 1 + seed as usize % 7, 1 + seed as usize % 5);
+            assert_at_least_as_good(&problem, &problem.solve().unwrap(), &point, 1e-9);
+        }
+    }
+
+    #[test]
+    fn random_lps_come_back_optimal() {
+        let checked = check_random_lps(300, 8, 6, |problem, point| {
+            let result = problem.solve().map_err(|err| format!("{:?}", err))?;
+            check_optimal(problem, &result, 1e-6)?;
+            let SolveResult::Optimal(solution) = &result else { unreachable!() };
+            match solution.objective >= problem.evaluate_objective(point) - 1e-6 {
+                true => Ok(()),
+                false => Err(format!("objective {} is worse than at {:?}", solution.objective, point.to_vec())),
+            }
+        });
+        if let Err(case) = checked {
+            panic!("seed {} shrunk to {:?}: {}", case.seed, case.problem, case.message);
+        }
+    }
+
+    #[test]
+    fn a_failing_property_gets_shrunk() {
+        //fails on anything with a second var, so the shrunk problem keeps exactly two and only row 0
+        let checked = check_random_lps(50, 6, 5, |problem, _| match problem.num_vars() {
+            1 => Ok(()),
+            vars => Err(format!("{} vars", vars)),
+        });
+        let case = checked.unwrap_err();
+        assert_eq!((case.problem.num_vars(), case.problem.constraints.len()), (2, 1));
+        assert_eq!(case.point.len(), 2);
+        assert!(verify_solution(&case.problem, &case.point, 1e-9).is_ok());
+    }
+
+    proptest! {
+        //proptest shrinks the sizes and seed of a failing case down to a small reproducer
+        #[test]
+        fn optimum_is_feasible_and_its_objective_is_c_dot_x(seed in any::<u64>(), m in 1usize..10, n in 1usize..8) {
+            let (problem, point) = random_feasible_lp(seed, m, n);
+            let result = problem.solve().unwrap();
+            prop_assert_eq!(check_optimal(&problem, &result, 1e-6), Ok(()));
+            assert_at_least_as_good(&problem, &result, &point, 1e-6);
+        }
+
+        #[test]
+        fn revised_agrees_with_the_tableau(seed in any::<u64>(), m in 1usize..8, n in 1usize..6) {
+            let (problem, _) = random_feasible_lp(seed, m, n);
+            let (c, a, b, relations) = problem.to_arrays();
+            let options = SolverOptions::default();
+            let dense = simplex_solver_two_phase(c.clone(), &a, &b, &relations, &options).unwrap();
+            let revised = revised_simplex(c, &a, &b, &relations, &options).unwrap();
+            prop_assert_eq!(check_optimal(&problem, &revised, 1e-6), Ok(()));
+            if let (SolveResult::Optimal(dense), SolveResult::Optimal(revised)) = (&dense, &revised) {
+                prop_assert!(close(dense.objective, revised.objective, 1e-6), "{} vs {}", dense.objective, revised.objective);
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use ndarray::Array1;
+use alloc::vec::Vec;
+
+use crate::problem::LpProblem;
+use crate::simplex::Relation;
+
+//one way a point fails the problem, residual is how far it is on the wrong side
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    Constraint { row: usize, residual: f64 },
+    Bound { var: usize, residual: f64 }, //outside lo <= x <= hi, which is x >= 0 for variables without bounds
+}
+
+//checks x against every constraint row and variable bound, extra entries past the structural vars (slacks) are ignored
+pub fn verify_solution(problem: &LpProblem, x: &Array1<f64>, eps: f64) -> Result<(), Vec<Violation>> {
+    let num_vars = problem.num_vars();
+    let value = |var: usize| x.get(var).copied().unwrap_or(0.0);
+    let mut violations = Vec::new();
+
+    for (row, con) in problem.constraints.iter().enumerate() {
+        let lhs: f64 = con.coeffs.iter().enumerate().map(|(var, coeff)| coeff * value(var)).sum();
+        let residual = match con.relation {
+            Relation::Le => (lhs - con.rhs).max(con.lower.map_or(f64::NEG_INFINITY, |lo| lo - lhs)),
+            Relation::Ge => con.rhs - lhs,
+            Relation::Eq => (lhs - con.rhs).abs(),
+        };
+        if residual > eps {
+            violations.push(Violation::Constraint { row, residual });
+        }
+    }
+
+    for var in 0..num_vars {
+        let (lo, hi) = problem.bounds.get(var).copied().unwrap_or((0.0, f64::INFINITY));
+        let residual = (lo - value(var)).max(value(var) - hi);
+        if residual > eps {
+            violations.push(Violation::Bound { var, residual });
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use ndarray::array;
+    use crate::test_support::wyndor;
+
+    #[test]
+    fn the_optimum_passes_and_a_perturbed_one_does_not() {
+        assert_eq!(verify_solution(&wyndor(), &array![2.0, 6.0], 1e-9), Ok(()));
+        assert_eq!(verify_solution(&wyndor(), &array![2.0, 6.0, 2.0, 0.0, 0.0], 1e-9), Ok(())); //slacks are ignored
+        let violations = verify_solution(&wyndor(), &array![2.5, 6.5], 1e-9).unwrap_err();
+        assert_eq!(violations, vec![Violation::Constraint { row: 1, residual: 1.0 }, Violation::Constraint { row: 2, residual: 2.5 }]);
+        assert_eq!(verify_solution(&wyndor(), &array![2.0, 6.0 + 1e-10], 1e-9), Ok(()));
+    }
+
+    #[test]
+    fn bounds_equalities_and_ranges_are_checked() {
+        let problem = wyndor().add_constraint(vec![1.0, 1.0], Relation::Eq, 7.0).add_range(vec![1.0, -1.0], -3.0, 1.0);
+        let both = vec![Violation::Constraint { row: 3, residual: 1.0 }, Violation::Constraint { row: 4, residual: 1.0 }];
+        assert_eq!(verify_solution(&problem, &array![2.0, 6.0], 1e-9), Err(both)); //x1 - x2 = -4 is below the range
+        assert_eq!(verify_solution(&problem, &array![3.0, 4.0], 1e-9), Ok(()));
+        let above = vec![
+            Violation::Constraint { row: 0, residual: 0.5 },
+            Violation::Constraint { row: 2, residual: 0.5 },
+            Violation::Constraint { row: 4, residual: 1.0 }, //x1 - x2 = 2 is above it
+        ];
+        assert_eq!(verify_solution(&problem, &array![4.5, 2.5], 1e-9), Err(above));
+        assert_eq!(verify_solution(&wyndor(), &array![-1.0, 0.0], 1e-9), Err(vec![Violation::Bound { var: 0, residual: 1.0 }]));
+        let capped = wyndor().bound(1, 1.0, 5.0);
+        assert_eq!(
+            verify_solution(&capped, &array![0.0, 5.5], 1e-9),
+            Err(vec![Violation::Bound { var: 1, residual: 0.5 }])
+        );
+        assert_eq!(verify_solution(&capped, &array![0.0, 0.0], 1e-9), Err(vec![Violation::Bound { var: 1, residual: 1.0 }]));
+    }
+}
@@ -0,0 +1,158 @@
+use ndarray::prelude::*;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::problem::LpProblem;
+use crate::scalar::Scalar;
+use crate::simplex::{dot, epsilon, extract_solution, Sense, Solution, SolveResult, SolverError, SolverOptions, TableauSnapshot};
+
+//basic feasible solution the solve passed through
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Vertex<T = f64> {
+    pub x: Array1<T>,
+    pub objective: T,
+    pub snapshot: usize, //index into Solution::history it was read from
+}
+
+//decodes Solution::history into vertices, skipping the phase one tableaus that still have an artificial above 0
+//lower bound shifts and the objective constant are taken over from the final solution, a complemented column
+//reads as u_j - x_j until the tableau it flipped back in
+pub struct Vertices<'a, T> {
+    history: core::iter::Enumerate<core::slice::Iter<'a, TableauSnapshot<T>>>,
+    num_x: usize,
+    costs: Array1<T>,
+    x_offset: Array1<T>,
+    objective_offset: T,
+    eps: T,
+}
+
+impl<T: Scalar> Solution<T> {
+    //every vertex recorded in history in the order the solve visited them, the last one being the optimum
+    //empty unless the solve ran with options.record_history
+    pub fn vertices(&self, options: &SolverOptions) -> Vertices<'_, T> {
+        let eps = epsilon::<T>(options);
+        let num_x = self.x.len();
+        let sign = if self.sense == Sense::Minimize { -T::one() } else { T::one() };
+        let costs = self.costs.slice(s![..num_x]).mapv(|cost| sign.clone() * cost);
+        let (x_offset, objective_offset) = match self.history.last() {
+            Some(last) => {
+                let x = extract_solution(&last.tableau, &eps).slice(s![..num_x]).to_owned();
+                let objective = dot(costs.view(), x.view());
+                (&self.x - &x, self.objective.clone() - objective)
+            }
+            None => (Array1::zeros(num_x), T::zero()),
+        };
+        Vertices { history: self.history.iter().enumerate(), num_x, costs, x_offset, objective_offset, eps }
+    }
+}
+
+impl<T: Scalar> Iterator for Vertices<'_, T> {
+    type Item = Vertex<T>;
+
+    fn next(&mut self) -> Option<Vertex<T>> {
+        for (snapshot, entry) in self.history.by_ref() {
+            let values = extract_solution(&entry.tableau, &self.eps);
+            let feasible = values.iter().enumerate().all(|(j, val)| *val >= -self.eps.clone() && (j < self.num_x || *val <= self.eps));
+            if !feasible {
+                continue;
+            }
+            let x = values.slice(s![..self.num_x]).to_owned();
+            let objective = dot(self.costs.view(), x.view()) + self.objective_offset.clone();
+            return Some(Vertex { x: x + &self.x_offset, objective, snapshot });
+        }
+        None
+    }
+}
+
+//every optimal vertex of the problem, structural vars only and without duplicates: from the optimum solve_with
+//finds, each zero reduced cost column is pivoted in from every optimal basis reached. the optimal face can have
+//exponentially many vertices, cap stops the search once that many are found. empty without an optimum
+pub fn enumerate_optimal_vertices(problem: &LpProblem, cap: Option<usize>, options: &SolverOptions) -> Result<Vec<Array1<f64>>, SolverError> {
+    let SolveResult::Optimal(first) = problem.solve_with(options)? else {
+        return Ok(Vec::new());
+    };
+    let same = |x1: &Array1<f64>, x2: &Array1<f64>| x1.iter().zip(x2).all(|(v1, v2)| (v1 - v2).abs() <= options.epsilon * (1.0 + v2.abs()));
+
+    //a degenerate vertex has several bases, each of them can lead somewhere else
+    let mut vertices: Vec<Array1<f64>> = Vec::new();
+    let mut seen: Vec<(Vec<usize>, Vec<bool>)> = Vec::new();
+    let mut pending = vec![first];
+    while let Some(solution) = pending.pop() {
+        if cap.is_some_and(|cap| vertices.len() >= cap) {
+            break;
+        }
+        let mut basis = solution.basis.clone();
+        basis.sort_unstable();
+        let key = (basis, solution.at_upper.clone());
+        if seen.contains(&key) {
+            continue;
+        }
+        seen.push(key);
+        let x = solution.structural().to_owned();
+        if !vertices.iter().any(|vertex| same(vertex, &x)) {
+            vertices.push(x);
+        }
+        for &col in &solution.alternate_optima {
+            if let Some(next) = solution.alternate_vertex(col, options)? {
+                pending.push(Box::new(next));
+            }
+        }
+    }
+    Ok(vertices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::Relation;
+    use crate::test_support::{expect_optimal, wyndor};
+    use crate::verify::verify_solution;
+
+    #[test]
+    fn vertices_walk_from_the_origin_to_the_optimum() {
+        let options = SolverOptions { record_history: true, ..SolverOptions::default() };
+        let problem = wyndor();
+        let solution = expect_optimal(problem.solve_with(&options).unwrap());
+        let vertices: Vec<Vertex> = solution.vertices(&options).collect();
+        assert_eq!(vertices.len(), solution.iterations + 1);
+        assert_eq!(vertices[0].x.slice(s![..2]).to_vec(), vec![0.0, 0.0]);
+        let last = vertices.last().unwrap();
+        assert_eq!(last.x, solution.x);
+        assert!((last.objective - solution.objective).abs() < 1e-9);
+        for pair in vertices.windows(2) {
+            assert!(pair[1].objective >= pair[0].objective - 1e-9);
+        }
+        assert!(vertices.iter().all(|vertex| verify_solution(&problem, &vertex.x, 1e-9).is_ok()));
+    }
+
+    #[test]
+    fn phase_one_tableaus_are_skipped() {
+        //x1 + x2 >= 2 puts the origin outside, the first vertex is the one phase one ends on
+        let options = SolverOptions { record_history: true, ..SolverOptions::default() };
+        let problem = wyndor().add_constraint(vec![1.0, 1.0], Relation::Ge, 2.0);
+        let solution = expect_optimal(problem.solve_with(&options).unwrap());
+        let vertices: Vec<Vertex> = solution.vertices(&options).collect();
+        assert!(vertices.len() < solution.history.len());
+        assert!(vertices.iter().all(|vertex| verify_solution(&problem, &vertex.x, 1e-9).is_ok()));
+        assert_eq!(vertices.last().unwrap().x, solution.x);
+        //without history there is nothing to decode
+        assert_eq!(expect_optimal(problem.solve().unwrap()).vertices(&options).count(), 0);
+    }
+
+    #[test]
+    fn both_ends_of_an_optimal_edge() {
+        //3x1 + 2x2 is parallel to the third row, the whole edge from (4, 3) to (2, 6) is optimal at 18
+        let problem = wyndor().objective(vec![3.0, 2.0]);
+        let options = SolverOptions::default();
+        let mut vertices: Vec<Vec<f64>> = enumerate_optimal_vertices(&problem, None, &options).unwrap().iter().map(|x| x.to_vec()).collect();
+        vertices.sort_by(|x1, x2| x1[0].total_cmp(&x2[0]));
+        assert_eq!(vertices, vec![vec![2.0, 6.0], vec![4.0, 3.0]]);
+        assert_eq!(enumerate_optimal_vertices(&problem, Some(1), &options).unwrap().len(), 1);
+        //a unique optimum, and none at all
+        assert_eq!(enumerate_optimal_vertices(&wyndor(), None, &options).unwrap(), vec![array![2.0, 6.0]]);
+        let infeasible = wyndor().add_constraint(vec![1.0, 1.0], Relation::Ge, 20.0);
+        assert!(enumerate_optimal_vertices(&infeasible, None, &options).unwrap().is_empty());
+    }
+}